@@ -0,0 +1,85 @@
+use crate::{Allocator, Box, Vec};
+
+/// Deep copy into another arena.
+///
+/// Lets a value outlive the [`Allocator`] it was made in, e.g. to cache a parsed module across
+/// compilations. `Cloned` is the same type with its arena lifetime replaced by `'new`.
+pub trait CloneIn<'new>: Sized {
+    type Cloned;
+
+    fn clone_in(&self, allocator: &'new Allocator) -> Self::Cloned;
+}
+
+impl<'new, T: CloneIn<'new>> CloneIn<'new> for Option<T> {
+    type Cloned = Option<T::Cloned>;
+
+    fn clone_in(&self, allocator: &'new Allocator) -> Self::Cloned {
+        self.as_ref().map(|it| it.clone_in(allocator))
+    }
+}
+
+impl<'new, T, C: 'new> CloneIn<'new> for Box<'_, T>
+where
+    T: CloneIn<'new, Cloned = C>,
+{
+    type Cloned = Box<'new, C>;
+
+    fn clone_in(&self, allocator: &'new Allocator) -> Self::Cloned {
+        Box(allocator.alloc(self.0.clone_in(allocator)))
+    }
+}
+
+impl<'new, T, C: 'new> CloneIn<'new> for Vec<'_, T>
+where
+    T: CloneIn<'new, Cloned = C>,
+{
+    type Cloned = Vec<'new, C>;
+
+    fn clone_in(&self, allocator: &'new Allocator) -> Self::Cloned {
+        Vec::from_iter_in(self.iter().map(|it| it.clone_in(allocator)), allocator)
+    }
+}
+
+impl<'new> CloneIn<'new> for &str {
+    type Cloned = &'new str;
+
+    fn clone_in(&self, allocator: &'new Allocator) -> Self::Cloned {
+        allocator.alloc_str(self)
+    }
+}
+
+macro_rules! clone_in_by_copy {
+    ($($ty:ty),*) => {
+        $(
+            impl<'new> CloneIn<'new> for $ty {
+                type Cloned = Self;
+
+                fn clone_in(&self, _allocator: &'new Allocator) -> Self {
+                    *self
+                }
+            }
+        )*
+    };
+}
+
+clone_in_by_copy!(bool, f64, u32, usize);
+
+#[cfg(test)]
+mod test {
+    use super::CloneIn;
+    use crate::{Allocator, Box, Vec};
+
+    #[test]
+    fn outlives_source() {
+        let allocator = Allocator::default();
+        let cloned = {
+            let old = Allocator::default();
+            let values = [Some(Box(old.alloc(1.0))), None, Some(Box(old.alloc(2.0)))];
+            let values = Vec::from_iter_in(values, &old);
+            values.clone_in(&allocator)
+        };
+        let values =
+            cloned.iter().map(|it| it.as_ref().map(|it| **it)).collect::<std::vec::Vec<_>>();
+        assert_eq!(values, [Some(1.0), None, Some(2.0)]);
+    }
+}
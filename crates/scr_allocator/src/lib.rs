@@ -1,19 +1,28 @@
 mod arena;
+mod clone_in;
+mod pool;
 
 use std::ops::Deref;
 
 pub use arena::{Box, String, Vec};
 use bumpalo::Bump;
+pub use clone_in::CloneIn;
+pub use pool::AllocatorGuard;
 
+/// The arena an AST is allocated in
+///
+/// It's `Send` but not `Sync`, so each thread parses into its own arena, see [`AllocatorGuard`].
 #[derive(Debug)]
 pub struct Allocator {
     bump: Bump,
 }
 
-// SAFETY: Make Bump Sync and Send, it's our responsibility to never
-// simultaneously mutate across threads.
-unsafe impl Send for Allocator {}
-unsafe impl Sync for Allocator {}
+impl Allocator {
+    /// Frees everything allocated so far, keeping the memory for reuse.
+    pub fn reset(&mut self) {
+        self.bump.reset();
+    }
+}
 
 impl Default for Allocator {
     fn default() -> Self {
@@ -0,0 +1,102 @@
+//! Arenas reused from one parse to the next on the same thread
+//!
+//! An [`Allocator`] is `Send` but not `Sync`, and an AST borrows the arena it was allocated in,
+//! so the AST can't leave the thread parsing it while the arena could be written to from another.
+//! To parse many files in parallel, e.g. with rayon, each task takes its own arena with
+//! [`AllocatorGuard::new`]. When the guard drops, the arena is reset and goes back to the pool of
+//! the thread dropping it, so a worker thread reuses the same memory from file to file.
+
+use std::{cell::RefCell, ops::Deref};
+
+use crate::Allocator;
+
+/// How many arenas a thread keeps for reuse, any more are freed when their guard drops
+const MAX_POOLED: usize = 4;
+
+thread_local! {
+    static POOL: RefCell<Vec<Allocator>> = const { RefCell::new(Vec::new()) };
+}
+
+/// An arena taken from the current thread's pool, which is returned to the pool on drop
+///
+/// Everything allocated in it borrows the guard, so it can't outlive the guard. It can't leave
+/// the thread either: an arena `Vec` is only `Send` when the arena is `Sync`, which it isn't. An
+/// AST is used where it was parsed, e.g. to collect the URLs a module loads, and only owned
+/// results such as those URLs are sent to other threads.
+#[derive(Debug)]
+pub struct AllocatorGuard {
+    allocator: Allocator,
+}
+
+impl AllocatorGuard {
+    /// Takes an arena from the current thread's pool, or creates one if the pool is empty.
+    #[must_use]
+    pub fn new() -> Self {
+        let allocator = POOL.with(|pool| pool.borrow_mut().pop()).unwrap_or_default();
+        Self { allocator }
+    }
+}
+
+impl Default for AllocatorGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for AllocatorGuard {
+    type Target = Allocator;
+
+    fn deref(&self) -> &Self::Target {
+        &self.allocator
+    }
+}
+
+impl Drop for AllocatorGuard {
+    fn drop(&mut self) {
+        let mut allocator = std::mem::take(&mut self.allocator);
+        allocator.reset();
+        // The pool is already gone if the thread is exiting, the arena is freed instead.
+        let _ = POOL.try_with(|pool| {
+            let mut pool = pool.borrow_mut();
+            if pool.len() < MAX_POOLED {
+                pool.push(allocator);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::thread;
+
+    use super::AllocatorGuard;
+    use crate::Vec;
+
+    #[test]
+    fn reuses_arenas() {
+        {
+            let allocator = AllocatorGuard::new();
+            Vec::from_iter_in(0..1000_u32, &allocator);
+        }
+        // The largest chunk is kept, which held the whole `Vec`.
+        let allocator = AllocatorGuard::new();
+        assert!(allocator.allocated_bytes() >= 4000);
+        assert_eq!(*allocator.alloc(1), 1);
+    }
+
+    #[test]
+    fn one_arena_per_thread() {
+        let sums = thread::scope(|scope| {
+            [0, 1, 2, 3_u32]
+                .map(|n| {
+                    scope.spawn(move || {
+                        let allocator = AllocatorGuard::new();
+                        let values = Vec::from_iter_in(0..=n, &allocator);
+                        values.iter().sum::<u32>()
+                    })
+                })
+                .map(|handle| handle.join().unwrap())
+        });
+        assert_eq!(sums, [0, 1, 3, 6]);
+    }
+}
@@ -0,0 +1,5 @@
+mod codegen;
+
+fn main() {
+    codegen::run();
+}
@@ -0,0 +1,82 @@
+//! Generates the node constructors of `AstBuilder`.
+//!
+//! Every struct node gets a method taking its fields in order, except `id`, which the builder
+//! assigns. Every enum variant holding a boxed struct node gets a method taking that node's fields
+//! and returning the enum, e.g. `expression_number` for `Expression::Number`.
+
+use std::fmt::Write;
+
+use crate::codegen::schema::{to_snake_case, Field, NodeKind, Schema, Shape};
+
+pub fn generate_ast_builder(schema: &Schema) -> String {
+    let mut methods = String::new();
+
+    for node in &schema.nodes {
+        match &node.kind {
+            NodeKind::Struct(fields) => {
+                let inits = fields
+                    .iter()
+                    .map(|field| match field.name.as_str() {
+                        "id" => "id: self.next_id()",
+                        name => name,
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                writeln!(
+                    methods,
+                    "    pub fn {}(&self, {}) -> {} {{\n        {} {{ {inits} }}\n    }}\n",
+                    node.snake_name(),
+                    params(fields),
+                    node.type_name(),
+                    node.name,
+                )
+                .unwrap();
+            }
+            NodeKind::Enum(variants) => {
+                for variant in variants {
+                    let Some(Shape::Box(inner)) = &variant.shape else { continue };
+                    let Shape::Node(name) = inner.as_ref() else { continue };
+                    let boxed = schema.get(name);
+                    let NodeKind::Struct(fields) = &boxed.kind else { continue };
+                    writeln!(
+                        methods,
+                        "    pub fn {}_{}(&self, {}) -> {} {{\n        {}::{}(self.alloc(self.{}({})))\n    }}\n",
+                        node.snake_name(),
+                        to_snake_case(&variant.name),
+                        params(fields),
+                        node.type_name(),
+                        node.name,
+                        variant.name,
+                        boxed.snake_name(),
+                        args(fields),
+                    )
+                    .unwrap();
+                }
+            }
+        }
+    }
+
+    format!(
+        "// Generated by `codegen/ast_builder.rs` from the node definitions in `src/node`, do not edit.
+
+impl<'a> AstBuilder<'a> {{
+{methods}}}
+"
+    )
+}
+
+fn constructor_fields(fields: &[Field]) -> impl Iterator<Item = &Field> {
+    fields.iter().filter(|field| field.name != "id")
+}
+
+/// `span: Span, value: f64, ...`
+fn params(fields: &[Field]) -> String {
+    let params = constructor_fields(fields).map(|field| format!("{}: {}", field.name, field.ty));
+    params.collect::<Vec<_>>().join(", ")
+}
+
+/// `span, value, ...`
+fn args(fields: &[Field]) -> String {
+    let args = constructor_fields(fields).map(|field| field.name.as_str());
+    args.collect::<Vec<_>>().join(", ")
+}
@@ -0,0 +1,74 @@
+//! Generates `AstKind`, a reference to any struct node, and `span()` on every enum node.
+
+use std::fmt::Write;
+
+use crate::codegen::schema::{NodeKind, Schema, Shape};
+
+pub fn generate_ast_kind(schema: &Schema) -> String {
+    let mut variants = String::new();
+    let mut ids = String::new();
+    let mut spans = String::new();
+    let mut enum_spans = String::new();
+
+    for node in &schema.nodes {
+        let name = &node.name;
+        let ty = node.type_name();
+        match &node.kind {
+            NodeKind::Struct(_) => {
+                writeln!(variants, "    {name}(&'n {ty}),").unwrap();
+                writeln!(ids, "            Self::{name}(it) => it.id,").unwrap();
+                writeln!(spans, "            Self::{name}(it) => it.span,").unwrap();
+            }
+            NodeKind::Enum(node_variants) => {
+                let generics = if node.has_lifetime { "<'a>" } else { "" };
+                let mut arms = String::new();
+                for variant in node_variants {
+                    let field = match &variant.shape {
+                        Some(Shape::Box(_)) => "it.span",
+                        Some(Shape::Node(_)) => "it.span()",
+                        _ => {
+                            panic!("{name}::{}: enum node variants must hold a node", variant.name)
+                        }
+                    };
+                    writeln!(arms, "            Self::{}(it) => {field},", variant.name).unwrap();
+                }
+                write!(
+                    enum_spans,
+                    "
+impl{generics} {ty} {{
+    #[must_use]
+    pub fn span(&self) -> Span {{
+        match self {{
+{arms}        }}
+    }}
+}}
+"
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    format!(
+        "// Generated by `codegen/ast_kind.rs` from the node definitions in `src/node`, do not edit.
+
+#[derive(Debug, Clone, Copy)]
+pub enum AstKind<'a, 'n> {{
+{variants}}}
+
+impl AstKind<'_, '_> {{
+    #[must_use]
+    pub const fn id(self) -> NodeId {{
+        match self {{
+{ids}        }}
+    }}
+
+    #[must_use]
+    pub const fn span(self) -> Span {{
+        match self {{
+{spans}        }}
+    }}
+}}
+{enum_spans}"
+    )
+}
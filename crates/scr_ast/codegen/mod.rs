@@ -0,0 +1,27 @@
+//! Code generated from the node definitions in `src/node`, written to `OUT_DIR` and pulled in with
+//! `include!` so it can never drift from the AST.
+
+mod ast_builder;
+mod ast_kind;
+mod schema;
+mod visit;
+
+use std::{env, fs, path::Path};
+
+use schema::Schema;
+
+pub fn run() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=codegen");
+    println!("cargo:rerun-if-changed=src/node");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let schema = Schema::load(&Path::new(&manifest_dir).join("src/node"));
+
+    let out_dir = Path::new(&out_dir);
+    fs::write(out_dir.join("ast_builder.rs"), ast_builder::generate_ast_builder(&schema)).unwrap();
+    fs::write(out_dir.join("ast_kind.rs"), ast_kind::generate_ast_kind(&schema)).unwrap();
+    fs::write(out_dir.join("visit.rs"), visit::generate_visit(&schema)).unwrap();
+    fs::write(out_dir.join("visit_mut.rs"), visit::generate_visit_mut(&schema)).unwrap();
+}
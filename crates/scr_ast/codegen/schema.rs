@@ -0,0 +1,308 @@
+//! Reads the node definitions in `src/node` into a [`Schema`].
+
+use std::{collections::HashSet, fmt::Write, fs, path::Path};
+
+use syn::{Fields, GenericArgument, Item, PathArguments, Type};
+
+pub struct Schema {
+    /// Node types in definition order, files sorted by name.
+    pub nodes: Vec<Node>,
+}
+
+pub struct Node {
+    pub name: String,
+    pub has_lifetime: bool,
+    pub kind: NodeKind,
+}
+
+pub enum NodeKind {
+    Struct(Vec<Field>),
+    Enum(Vec<Variant>),
+}
+
+pub struct Field {
+    pub name: String,
+    /// The type as written, e.g. `Option<Vec<'a, Statement<'a>>>`
+    pub ty: String,
+    pub shape: Shape,
+}
+
+pub struct Variant {
+    pub name: String,
+    /// `None` for unit variants.
+    pub shape: Option<Shape>,
+}
+
+/// The structure of a field or variant type, as far as traversal cares.
+#[derive(Clone)]
+pub enum Shape {
+    Node(String),
+    Box(std::boxed::Box<Self>),
+    Option(std::boxed::Box<Self>),
+    Vec(std::boxed::Box<Self>),
+    Other(String),
+}
+
+impl Shape {
+    fn from_type(ty: &Type) -> Self {
+        let Type::Path(path) = ty else {
+            return Self::Other(String::new());
+        };
+        let segment = path.path.segments.last().unwrap();
+        let name = segment.ident.to_string();
+        let inner = match &segment.arguments {
+            PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(Self::from_type(ty)),
+                _ => None,
+            }),
+            _ => None,
+        };
+        match (name.as_str(), inner) {
+            ("Box", Some(inner)) => Self::Box(inner.into()),
+            ("Option", Some(inner)) => Self::Option(inner.into()),
+            ("Vec", Some(inner)) => Self::Vec(inner.into()),
+            _ => Self::Other(name),
+        }
+    }
+
+    /// Resolves `Other` names that turned out to be nodes.
+    fn resolve(&mut self, nodes: &HashSet<String>) {
+        match self {
+            Self::Other(name) if nodes.contains(name) => *self = Self::Node(name.clone()),
+            Self::Box(inner) | Self::Option(inner) | Self::Vec(inner) => inner.resolve(nodes),
+            _ => {}
+        }
+    }
+
+    pub fn contains_node(&self) -> bool {
+        match self {
+            Self::Node(_) => true,
+            Self::Box(inner) | Self::Option(inner) | Self::Vec(inner) => inner.contains_node(),
+            Self::Other(_) => false,
+        }
+    }
+
+    /// The node type this shape ultimately holds.
+    pub fn node_name(&self) -> Option<&str> {
+        match self {
+            Self::Node(name) => Some(name),
+            Self::Box(inner) | Self::Option(inner) | Self::Vec(inner) => inner.node_name(),
+            Self::Other(_) => None,
+        }
+    }
+}
+
+impl Schema {
+    pub fn load(dir: &Path) -> Self {
+        let mut paths = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        let mut nodes = vec![];
+        for path in paths {
+            let file = syn::parse_file(&fs::read_to_string(&path).unwrap())
+                .unwrap_or_else(|err| panic!("failed to parse {}: {err}", path.display()));
+            for item in file.items {
+                if let Some(node) = Node::from_item(&item) {
+                    nodes.push(node);
+                }
+            }
+        }
+
+        // Structs with a span are nodes, enums are nodes once one of their variants holds a node.
+        let mut names = nodes
+            .iter()
+            .filter(|node| matches!(&node.kind, NodeKind::Struct(fields) if fields.iter().any(|f| f.name == "span")))
+            .map(|node| node.name.clone())
+            .collect::<HashSet<_>>();
+        loop {
+            let len = names.len();
+            for node in &mut nodes {
+                node.resolve(&names);
+                if let NodeKind::Enum(variants) = &node.kind {
+                    if variants.iter().any(|v| v.shape.as_ref().is_some_and(Shape::contains_node)) {
+                        names.insert(node.name.clone());
+                    }
+                }
+            }
+            if names.len() == len {
+                break;
+            }
+        }
+        nodes.retain(|node| names.contains(&node.name));
+
+        Self { nodes }
+    }
+
+    pub fn get(&self, name: &str) -> &Node {
+        self.nodes.iter().find(|node| node.name == name).unwrap()
+    }
+
+    /// Node types held in a `Vec`, in order of first appearance.
+    pub fn list_element_types(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = vec![];
+        for node in &self.nodes {
+            let shapes: Vec<&Shape> = match &node.kind {
+                NodeKind::Struct(fields) => fields.iter().map(|field| &field.shape).collect(),
+                NodeKind::Enum(variants) => {
+                    variants.iter().filter_map(|v| v.shape.as_ref()).collect()
+                }
+            };
+            for name in shapes.into_iter().filter_map(Shape::list_element) {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        names
+    }
+}
+
+impl Shape {
+    fn list_element(&self) -> Option<&str> {
+        match self {
+            Self::Vec(inner) => inner.node_name(),
+            Self::Option(inner) | Self::Box(inner) => inner.list_element(),
+            _ => None,
+        }
+    }
+}
+
+impl Node {
+    fn from_item(item: &Item) -> Option<Self> {
+        match item {
+            Item::Struct(item) => {
+                let Fields::Named(fields) = &item.fields else {
+                    return None;
+                };
+                let fields = fields
+                    .named
+                    .iter()
+                    .map(|field| Field {
+                        name: field.ident.as_ref().unwrap().to_string(),
+                        ty: type_to_string(&field.ty),
+                        shape: Shape::from_type(&field.ty),
+                    })
+                    .collect();
+                Some(Self {
+                    name: item.ident.to_string(),
+                    has_lifetime: item.generics.lifetimes().next().is_some(),
+                    kind: NodeKind::Struct(fields),
+                })
+            }
+            Item::Enum(item) => {
+                let variants = item
+                    .variants
+                    .iter()
+                    .map(|variant| {
+                        let shape = match &variant.fields {
+                            Fields::Unit => None,
+                            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                                Some(Shape::from_type(&fields.unnamed[0].ty))
+                            }
+                            _ => panic!(
+                                "{}::{}: enum variants must be unit or hold a single type",
+                                item.ident, variant.ident
+                            ),
+                        };
+                        Variant { name: variant.ident.to_string(), shape }
+                    })
+                    .collect();
+                Some(Self {
+                    name: item.ident.to_string(),
+                    has_lifetime: item.generics.lifetimes().next().is_some(),
+                    kind: NodeKind::Enum(variants),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn resolve(&mut self, nodes: &HashSet<String>) {
+        match &mut self.kind {
+            NodeKind::Struct(fields) => {
+                for field in fields {
+                    field.shape.resolve(nodes);
+                }
+            }
+            NodeKind::Enum(variants) => {
+                variants
+                    .iter_mut()
+                    .filter_map(|v| v.shape.as_mut())
+                    .for_each(|shape| shape.resolve(nodes));
+            }
+        }
+    }
+
+    /// `Name<'a>` or `Name`
+    pub fn type_name(&self) -> String {
+        if self.has_lifetime {
+            format!("{}<'a>", self.name)
+        } else {
+            self.name.clone()
+        }
+    }
+
+    pub fn snake_name(&self) -> String {
+        to_snake_case(&self.name)
+    }
+}
+
+pub fn to_snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i != 0 {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// `statement` -> `statements`, `media_query` -> `media_queries`
+pub fn to_plural(name: &str) -> String {
+    if let Some(stem) = name.strip_suffix('y') {
+        if !stem.ends_with(['a', 'e', 'i', 'o', 'u']) {
+            return format!("{stem}ies");
+        }
+    }
+    if name.ends_with('s') || name.ends_with('x') || name.ends_with("ch") {
+        return format!("{name}es");
+    }
+    format!("{name}s")
+}
+
+fn type_to_string(ty: &Type) -> String {
+    let path = match ty {
+        Type::Path(path) => path,
+        Type::Reference(reference) => {
+            let lifetime = reference.lifetime.as_ref().expect("node references need a lifetime");
+            return format!("&'{} {}", lifetime.ident, type_to_string(&reference.elem));
+        }
+        _ => panic!("node fields must have path or reference types"),
+    };
+    let segments = path.path.segments.iter().map(|segment| {
+        let mut s = segment.ident.to_string();
+        if let PathArguments::AngleBracketed(args) = &segment.arguments {
+            let args = args
+                .args
+                .iter()
+                .map(|arg| match arg {
+                    GenericArgument::Lifetime(lifetime) => format!("'{}", lifetime.ident),
+                    GenericArgument::Type(ty) => type_to_string(ty),
+                    _ => panic!("node fields may only have lifetime and type arguments"),
+                })
+                .collect::<Vec<_>>();
+            write!(s, "<{}>", args.join(", ")).unwrap();
+        }
+        s
+    });
+    segments.collect::<Vec<_>>().join("::")
+}
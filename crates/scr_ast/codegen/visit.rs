@@ -0,0 +1,168 @@
+//! Generates the `Visit` and `VisitMut` traits together with their `walk` functions.
+
+use std::fmt::Write;
+
+use crate::codegen::schema::{to_plural, to_snake_case, Node, NodeKind, Schema, Shape};
+
+struct Flavor {
+    trait_name: &'static str,
+    walk_mod: &'static str,
+    reference: &'static str,
+    iter: &'static str,
+    /// Call `enter_node` and `leave_node` around every struct node.
+    node_hooks: bool,
+}
+
+const VISIT: Flavor = Flavor {
+    trait_name: "Visit",
+    walk_mod: "walk",
+    reference: "&",
+    iter: "iter",
+    node_hooks: true,
+};
+
+const VISIT_MUT: Flavor = Flavor {
+    trait_name: "VisitMut",
+    walk_mod: "walk_mut",
+    reference: "&mut ",
+    iter: "iter_mut",
+    node_hooks: false,
+};
+
+pub fn generate_visit(schema: &Schema) -> String {
+    generate(schema, &VISIT)
+}
+
+pub fn generate_visit_mut(schema: &Schema) -> String {
+    generate(schema, &VISIT_MUT)
+}
+
+fn generate(schema: &Schema, flavor: &Flavor) -> String {
+    let Flavor { trait_name, walk_mod, reference, .. } = flavor;
+    let mut trait_methods = String::new();
+    let mut walkers = String::new();
+
+    if flavor.node_hooks {
+        trait_methods.push_str(
+            "    fn enter_node(&mut self, _kind: AstKind<'a, '_>) {}\n\n    fn leave_node(&mut self, _kind: AstKind<'a, '_>) {}\n\n",
+        );
+    }
+
+    for node in &schema.nodes {
+        let snake = node.snake_name();
+        let ty = node.type_name();
+        let body = walk_body(schema, node, flavor);
+        let (visitor, it) = if body.is_empty() { ("_visitor", "_it") } else { ("visitor", "it") };
+
+        writeln!(
+            trait_methods,
+            "    fn visit_{snake}(&mut self, it: {reference}{ty}) {{\n        {walk_mod}::walk_{snake}(self, it);\n    }}\n"
+        )
+        .unwrap();
+        writeln!(
+            walkers,
+            "    pub fn walk_{snake}<'a, V: {trait_name}<'a>>({visitor}: &mut V, {it}: {reference}{ty}) {{\n{body}    }}\n"
+        )
+        .unwrap();
+    }
+
+    for name in schema.list_element_types() {
+        let node = schema.get(name);
+        let snake = node.snake_name();
+        let plural = to_plural(&snake);
+        let ty = node.type_name();
+        let iter = flavor.iter;
+
+        writeln!(
+            trait_methods,
+            "    fn visit_{plural}(&mut self, it: {reference}Vec<'a, {ty}>) {{\n        {walk_mod}::walk_{plural}(self, it);\n    }}\n"
+        )
+        .unwrap();
+        writeln!(
+            walkers,
+            "    pub fn walk_{plural}<'a, V: {trait_name}<'a>>(visitor: &mut V, it: {reference}Vec<'a, {ty}>) {{\n        for it in it.{iter}() {{\n            visitor.visit_{snake}(it);\n        }}\n    }}\n"
+        )
+        .unwrap();
+    }
+
+    format!(
+        "// Generated by `codegen/visit.rs` from the node definitions in `src/node`, do not edit.
+
+pub trait {trait_name}<'a>: Sized {{
+{trait_methods}}}
+
+pub mod {walk_mod} {{
+    use super::*;
+
+{walkers}}}
+"
+    )
+}
+
+fn walk_body(schema: &Schema, node: &Node, flavor: &Flavor) -> String {
+    let reference = flavor.reference;
+    let mut body = String::new();
+    match &node.kind {
+        NodeKind::Struct(fields) => {
+            let hook =
+                |name: &str| format!("        visitor.{name}(AstKind::{}(it));\n", node.name);
+            if flavor.node_hooks {
+                body.push_str(&hook("enter_node"));
+            }
+            for field in fields {
+                let field_name = &field.name;
+                if let Shape::Option(inner) = &field.shape {
+                    if let Some(call) = visit_call(schema, inner, "x") {
+                        writeln!(
+                            body,
+                            "        if let Some(x) = {reference}it.{field_name} {{\n            {call};\n        }}"
+                        )
+                        .unwrap();
+                    }
+                } else if let Some(call) =
+                    visit_call(schema, &field.shape, &format!("{reference}it.{field_name}"))
+                {
+                    writeln!(body, "        {call};").unwrap();
+                }
+            }
+            if flavor.node_hooks {
+                body.push_str(&hook("leave_node"));
+            }
+        }
+        NodeKind::Enum(variants) => {
+            let mut arms = String::new();
+            let mut has_other = false;
+            for variant in variants {
+                match variant.shape.as_ref().and_then(|shape| visit_call(schema, shape, "x")) {
+                    Some(call) => {
+                        writeln!(arms, "            {}::{}(x) => {call},", node.name, variant.name)
+                            .unwrap();
+                    }
+                    None => has_other = true,
+                }
+            }
+            if has_other {
+                arms.push_str("            _ => {}\n");
+            }
+            if !arms.is_empty() {
+                write!(body, "        match it {{\n{arms}        }}\n").unwrap();
+            }
+        }
+    }
+    body
+}
+
+/// `visitor.visit_*(binding)` for a shape holding nodes.
+fn visit_call(schema: &Schema, shape: &Shape, binding: &str) -> Option<String> {
+    match shape {
+        Shape::Node(name) => {
+            Some(format!("visitor.visit_{}({binding})", schema.get(name).snake_name()))
+        }
+        Shape::Box(inner) => visit_call(schema, inner, binding),
+        Shape::Vec(inner) => {
+            let name = inner.node_name()?;
+            Some(format!("visitor.visit_{}({binding})", to_plural(&to_snake_case(name))))
+        }
+        Shape::Option(_) | Shape::Other(_) => None,
+    }
+}
@@ -0,0 +1,68 @@
+//! Constructing nodes in an arena
+//!
+//! [`AstBuilder`] has a method for every node, named after it, plus one for every enum variant
+//! that boxes a struct node:
+//!
+//! ```ignore
+//! let ast = AstBuilder::new(&allocator);
+//! let one = ast.expression_number(SPAN, 1.0, Some(Atom::from("px")));
+//! let two = ast.expression_number(SPAN, 2.0, None);
+//! let sum = ast.expression_binary(SPAN, BinaryOperator::Plus, one, two, false);
+//! ```
+//!
+//! The builder gives every node it makes the next [`NodeId`]. Use [`SPAN`](crate::SPAN) for nodes
+//! that don't come from the source.
+
+#![allow(clippy::too_many_arguments, clippy::wildcard_imports)]
+
+use scr_allocator::{Allocator, Box, Vec};
+
+use crate::{node::*, Atom, NodeId, NodeIdGenerator, Span};
+
+pub struct AstBuilder<'a> {
+    pub allocator: &'a Allocator,
+    ids: NodeIdGenerator,
+}
+
+impl<'a> AstBuilder<'a> {
+    #[must_use]
+    pub fn new(allocator: &'a Allocator) -> Self {
+        Self { allocator, ids: NodeIdGenerator::default() }
+    }
+
+    /// The ids given out so far, for sizing a [`crate::SideTable`]
+    #[must_use]
+    pub const fn ids(&self) -> &NodeIdGenerator {
+        &self.ids
+    }
+
+    fn next_id(&self) -> NodeId {
+        self.ids.next_id()
+    }
+
+    pub fn alloc<T>(&self, value: T) -> Box<'a, T> {
+        Box(self.allocator.alloc(value))
+    }
+
+    #[must_use]
+    pub fn new_vec<T>(&self) -> Vec<'a, T> {
+        Vec::new_in(self.allocator)
+    }
+
+    pub fn vec_from_iter<T>(&self, iter: impl IntoIterator<Item = T>) -> Vec<'a, T> {
+        Vec::from_iter_in(iter, self.allocator)
+    }
+
+    /// Copies `text` into the arena, for text that isn't a slice of the source
+    pub fn str(&self, text: &str) -> &'a str {
+        self.allocator.alloc_str(text)
+    }
+
+    /// An interpolation holding only `text`
+    pub fn plain_interpolation(&self, span: Span, text: &'a str) -> Interpolation<'a> {
+        let text = self.interpolation_part_text(span, text);
+        self.interpolation(span, self.vec_from_iter([text]))
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/ast_builder.rs"));
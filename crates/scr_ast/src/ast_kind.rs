@@ -0,0 +1,8 @@
+//! A reference to any struct node, with access to the fields they all share, and `span()` on
+//! enum nodes
+
+#![allow(clippy::too_many_lines, clippy::wildcard_imports)]
+
+use crate::{node::*, NodeId, Span};
+
+include!(concat!(env!("OUT_DIR"), "/ast_kind.rs"));
@@ -0,0 +1,331 @@
+//! Interned strings for identifiers
+//!
+//! Every distinct string is stored once for the lifetime of the process, and an [`Atom`] is a
+//! thin pointer to it, so comparing and hashing atoms never looks at the text. The interner is
+//! shared by all threads. Strings are never freed, which suits identifiers: a compilation only
+//! sees a bounded set of names, and they are usually needed until the end. Atoms are only for
+//! identifiers and units: free text such as comments, string contents and URLs is unbounded, so
+//! nodes hold it as `&'a str` in their arena instead.
+
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    ptr,
+    sync::{LazyLock, RwLock},
+};
+
+use rustc_hash::FxHashMap;
+use scr_allocator::{Allocator, CloneIn};
+use serde::{Serialize, Serializer};
+
+#[derive(Clone, Copy)]
+pub struct Atom(&'static &'static str);
+
+static INTERNER: LazyLock<RwLock<FxHashMap<&'static str, Atom>>> = LazyLock::new(|| {
+    let atoms = COMMON.iter().map(|name| (*name, Atom(name)));
+    RwLock::new(atoms.collect())
+});
+
+impl Atom {
+    /// Returns the atom for `s`, interning it on first use.
+    ///
+    /// # Panics
+    ///
+    /// If another thread panicked while interning.
+    #[must_use]
+    pub fn new(s: &str) -> Self {
+        if let Some(&atom) = INTERNER.read().unwrap().get(s) {
+            return atom;
+        }
+        let mut interner = INTERNER.write().unwrap();
+        // Another thread may have interned it between the two locks.
+        if let Some(&atom) = interner.get(s) {
+            return atom;
+        }
+        let s: &'static str = Box::leak(s.into());
+        let atom = Self(Box::leak(Box::new(s)));
+        interner.insert(s, atom);
+        atom
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        self.0
+    }
+}
+
+/// Atoms are interned for the whole process, so the clone is the same atom.
+impl<'new> CloneIn<'new> for Atom {
+    type Cloned = Self;
+
+    fn clone_in(&self, _allocator: &'new Allocator) -> Self {
+        *self
+    }
+}
+
+impl PartialEq for Atom {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self.0, other.0)
+    }
+}
+
+impl Eq for Atom {}
+
+impl Hash for Atom {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ptr::hash(self.0, state);
+    }
+}
+
+/// Atoms are ordered by their text, so sorted output doesn't depend on interning order.
+impl PartialOrd for Atom {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Atom {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self == other {
+            Ordering::Equal
+        } else {
+            self.as_str().cmp(other.as_str())
+        }
+    }
+}
+
+impl Default for Atom {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl Deref for Atom {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.0
+    }
+}
+
+impl AsRef<str> for Atom {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+impl PartialEq<str> for Atom {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Atom {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl From<&str> for Atom {
+    fn from(s: &str) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<String> for Atom {
+    fn from(s: String) -> Self {
+        Self::new(&s)
+    }
+}
+
+impl From<Cow<'_, str>> for Atom {
+    fn from(s: Cow<'_, str>) -> Self {
+        Self::new(&s)
+    }
+}
+
+impl fmt::Debug for Atom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for Atom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Atom {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Names interned up front, stored in the binary instead of on the heap
+static COMMON: [&str; 134] = [
+    // Properties
+    "align-items",
+    "animation",
+    "background",
+    "background-color",
+    "background-image",
+    "background-position",
+    "background-repeat",
+    "background-size",
+    "border",
+    "border-bottom",
+    "border-color",
+    "border-left",
+    "border-radius",
+    "border-right",
+    "border-style",
+    "border-top",
+    "border-width",
+    "bottom",
+    "box-shadow",
+    "box-sizing",
+    "color",
+    "content",
+    "cursor",
+    "display",
+    "flex",
+    "flex-direction",
+    "flex-wrap",
+    "float",
+    "font",
+    "font-family",
+    "font-size",
+    "font-style",
+    "font-weight",
+    "gap",
+    "grid-template-columns",
+    "height",
+    "justify-content",
+    "left",
+    "letter-spacing",
+    "line-height",
+    "list-style",
+    "margin",
+    "margin-bottom",
+    "margin-left",
+    "margin-right",
+    "margin-top",
+    "max-height",
+    "max-width",
+    "min-height",
+    "min-width",
+    "opacity",
+    "outline",
+    "overflow",
+    "padding",
+    "padding-bottom",
+    "padding-left",
+    "padding-right",
+    "padding-top",
+    "pointer-events",
+    "position",
+    "right",
+    "text-align",
+    "text-decoration",
+    "text-overflow",
+    "text-transform",
+    "top",
+    "transform",
+    "transition",
+    "vertical-align",
+    "visibility",
+    "white-space",
+    "width",
+    "z-index",
+    // Keywords
+    "",
+    "absolute",
+    "and",
+    "auto",
+    "block",
+    "bold",
+    "center",
+    "currentColor",
+    "false",
+    "fixed",
+    "flex-end",
+    "flex-start",
+    "from",
+    "hidden",
+    "important",
+    "inherit",
+    "initial",
+    "inline",
+    "inline-block",
+    "none",
+    "normal",
+    "not",
+    "null",
+    "only",
+    "or",
+    "pointer",
+    "relative",
+    "revert",
+    "screen",
+    "solid",
+    "through",
+    "to",
+    "transparent",
+    "true",
+    "unset",
+    // Units
+    "%",
+    "deg",
+    "em",
+    "fr",
+    "ms",
+    "px",
+    "rem",
+    "s",
+    "vh",
+    "vw",
+    // Functions
+    "calc",
+    "clamp",
+    "hsl",
+    "hsla",
+    "if",
+    "map-get",
+    "max",
+    "min",
+    "rgb",
+    "rgba",
+    "url",
+    "var",
+    // Rules
+    "font-face",
+    "keyframes",
+    "media",
+    "supports",
+];
+
+#[cfg(test)]
+mod test {
+    use std::thread;
+
+    use super::{Atom, COMMON};
+
+    #[test]
+    fn interned() {
+        let color = Atom::from("color");
+        assert!(COMMON.iter().any(|name| std::ptr::eq(color.0, name)));
+
+        let name = Atom::from(String::from("my-mixin"));
+        let handles: Vec<_> = (0..4).map(|_| thread::spawn(|| Atom::from("my-mixin"))).collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), name);
+        }
+        assert_eq!(name, "my-mixin");
+        assert_ne!(name, color);
+        assert!(color < name);
+    }
+}
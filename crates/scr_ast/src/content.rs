@@ -0,0 +1,193 @@
+//! Comparing and hashing what nodes mean rather than where they are
+//!
+//! [`ContentEq`] and [`ContentHash`] ignore spans, node ids, and comments in statement lists, so two
+//! stylesheets that differ only in layout or comments are equal. Both can be derived on nodes.
+
+use std::hash::{Hash, Hasher};
+
+use scr_allocator::{Box, Vec};
+
+use crate::{node::DocComment, Atom, NodeId, Span};
+
+pub trait ContentEq {
+    fn content_eq(&self, other: &Self) -> bool;
+
+    /// Whether this value is left out when comparing or hashing lists, e.g. a comment.
+    fn is_trivia(&self) -> bool {
+        false
+    }
+}
+
+/// Hashing consistent with [`ContentEq`]: values that are `content_eq` hash the same.
+pub trait ContentHash {
+    fn content_hash<H: Hasher>(&self, state: &mut H);
+}
+
+impl ContentEq for Span {
+    fn content_eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl ContentHash for Span {
+    fn content_hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+impl ContentEq for NodeId {
+    fn content_eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl ContentHash for NodeId {
+    fn content_hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+/// Doc comments are comments, so they don't change what the member they document means.
+impl ContentEq for DocComment<'_> {
+    fn content_eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl ContentHash for DocComment<'_> {
+    fn content_hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+macro_rules! content_by_value {
+    ($($ty:ty),*) => {
+        $(
+            impl ContentEq for $ty {
+                fn content_eq(&self, other: &Self) -> bool {
+                    self == other
+                }
+            }
+
+            impl ContentHash for $ty {
+                fn content_hash<H: Hasher>(&self, state: &mut H) {
+                    self.hash(state);
+                }
+            }
+        )*
+    };
+}
+
+content_by_value!(Atom, bool, &str);
+
+/// Numbers are compared by bits, so that equal values hash the same and `NaN` equals itself.
+impl ContentEq for f64 {
+    fn content_eq(&self, other: &Self) -> bool {
+        self.to_bits() == other.to_bits()
+    }
+}
+
+impl ContentHash for f64 {
+    fn content_hash<H: Hasher>(&self, state: &mut H) {
+        self.to_bits().hash(state);
+    }
+}
+
+impl<T: ContentEq> ContentEq for Option<T> {
+    fn content_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Some(a), Some(b)) => a.content_eq(b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T: ContentHash> ContentHash for Option<T> {
+    fn content_hash<H: Hasher>(&self, state: &mut H) {
+        self.is_some().hash(state);
+        if let Some(it) = self {
+            it.content_hash(state);
+        }
+    }
+}
+
+impl<T: ContentEq> ContentEq for Box<'_, T> {
+    fn content_eq(&self, other: &Self) -> bool {
+        (**self).content_eq(&**other)
+    }
+
+    fn is_trivia(&self) -> bool {
+        (**self).is_trivia()
+    }
+}
+
+impl<T: ContentHash> ContentHash for Box<'_, T> {
+    fn content_hash<H: Hasher>(&self, state: &mut H) {
+        (**self).content_hash(state);
+    }
+}
+
+impl<T: ContentEq> ContentEq for Vec<'_, T> {
+    fn content_eq(&self, other: &Self) -> bool {
+        let a = self.iter().filter(|it| !it.is_trivia());
+        let mut b = other.iter().filter(|it| !it.is_trivia());
+        a.map(Some).chain([None]).all(|a| match (a, b.next()) {
+            (Some(a), Some(b)) => a.content_eq(b),
+            (None, None) => true,
+            _ => false,
+        })
+    }
+}
+
+impl<T: ContentEq + ContentHash> ContentHash for Vec<'_, T> {
+    fn content_hash<H: Hasher>(&self, state: &mut H) {
+        let mut len = 0_usize;
+        for it in self.iter().filter(|it| !it.is_trivia()) {
+            it.content_hash(state);
+            len += 1;
+        }
+        len.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::hash_map::DefaultHasher, hash::Hasher};
+
+    use super::{ContentEq, ContentHash};
+    use crate::{AstBuilder, Atom, Span, SPAN};
+
+    fn hash(it: &impl ContentHash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        it.content_hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn ignores_spans_and_comments() {
+        let allocator = scr_allocator::Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let declaration = |span: Span, value: f64| {
+            let value = ast.expression_number(span, value, Some(Atom::from("px")));
+            ast.statement_variable_declaration(
+                span,
+                None,
+                Atom::from("a"),
+                value,
+                false,
+                false,
+                None,
+            )
+        };
+
+        let a = ast.stylesheet(SPAN, ast.vec_from_iter([declaration(Span::new(0, 8), 1.0)]));
+        let b = ast.stylesheet(
+            Span::new(0, 20),
+            ast.vec_from_iter([
+                ast.statement_silent_comment(Span::new(0, 6), "// one"),
+                declaration(Span::new(7, 20), 1.0),
+            ]),
+        );
+        let c = ast.stylesheet(SPAN, ast.vec_from_iter([declaration(SPAN, 2.0)]));
+
+        assert!(a.content_eq(&b));
+        assert_eq!(hash(&a), hash(&b));
+        assert!(!a.content_eq(&c));
+        assert_ne!(hash(&a), hash(&c));
+    }
+}
@@ -0,0 +1,150 @@
+//! Where the parser is in the stylesheet
+//!
+//! Some statements are only valid in certain places, e.g. `@return` inside `@function` or
+//! declarations inside style rules. The parser keeps a [`Context`] of the blocks it is in and
+//! checks it before accepting such a statement, like Dart Sass's `_inMixin`, `_inStyleRule`, etc.
+
+use bitflags::bitflags;
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Context: u16 {
+        /// Inside a `@mixin` body
+        const MIXIN = 1 << 0;
+        /// Inside the content block of an `@include`
+        const CONTENT_BLOCK = 1 << 1;
+        /// Inside a `@function` body
+        const FUNCTION = 1 << 2;
+        /// Inside `@if`, `@each`, `@for` or `@while`
+        const CONTROL_DIRECTIVE = 1 << 3;
+        /// Inside a style rule or a keyframe block
+        const STYLE_RULE = 1 << 4;
+        /// Directly inside `@keyframes`, where children are keyframe blocks
+        const KEYFRAMES = 1 << 5;
+        /// Inside `@font-face`
+        const FONT_FACE = 1 << 6;
+        /// Inside an at-rule whose body may hold declarations, such as `@page`
+        const DECLARATION_AT_RULE = 1 << 7;
+        /// Parsing a plain CSS file, where Sass features are errors
+        const PLAIN_CSS = 1 << 8;
+        /// Directly inside `@page`, where margin at-rules such as `@top-left` are allowed
+        const PAGE = 1 << 9;
+    }
+}
+
+impl Context {
+    /// Flags of blocks that may hold declarations, directly or through an `@include`
+    const DECLARATION_PARENT: Self = Self::MIXIN
+        .union(Self::CONTENT_BLOCK)
+        .union(Self::STYLE_RULE)
+        .union(Self::FONT_FACE)
+        .union(Self::DECLARATION_AT_RULE);
+
+    #[must_use]
+    pub const fn in_mixin(self) -> bool {
+        self.intersects(Self::MIXIN.union(Self::CONTENT_BLOCK))
+    }
+
+    #[must_use]
+    pub const fn in_function(self) -> bool {
+        self.contains(Self::FUNCTION)
+    }
+
+    #[must_use]
+    pub const fn in_control_directive(self) -> bool {
+        self.contains(Self::CONTROL_DIRECTIVE)
+    }
+
+    #[must_use]
+    pub const fn in_style_rule(self) -> bool {
+        self.contains(Self::STYLE_RULE)
+    }
+
+    #[must_use]
+    pub const fn in_keyframes(self) -> bool {
+        self.contains(Self::KEYFRAMES)
+    }
+
+    #[must_use]
+    pub const fn in_font_face(self) -> bool {
+        self.contains(Self::FONT_FACE)
+    }
+
+    #[must_use]
+    pub const fn in_page(self) -> bool {
+        self.contains(Self::PAGE)
+    }
+
+    #[must_use]
+    pub const fn is_plain_css(self) -> bool {
+        self.contains(Self::PLAIN_CSS)
+    }
+
+    /// Not inside any block, only the syntax flag may be set.
+    #[must_use]
+    pub const fn is_top_level(self) -> bool {
+        self.difference(Self::PLAIN_CSS).is_empty()
+    }
+
+    #[must_use]
+    pub const fn allows_declarations(self) -> bool {
+        !self.in_function() && self.intersects(Self::DECLARATION_PARENT)
+    }
+}
+
+/// Scoped changes to a parser's [`Context`]
+pub trait WithContext {
+    fn context_mut(&mut self) -> &mut Context;
+
+    /// Runs `f` with `flags` added to the context, then restores the previous context.
+    fn with_context<T>(&mut self, flags: Context, f: impl FnOnce(&mut Self) -> T) -> T {
+        let previous = *self.context_mut();
+        self.context_mut().insert(flags);
+        let result = f(self);
+        *self.context_mut() = previous;
+        result
+    }
+
+    /// Runs `f` with `flags` removed from the context, then restores the previous context.
+    ///
+    /// Used where a block resets its surroundings, e.g. `@at-root` leaves the style rule.
+    fn without_context<T>(&mut self, flags: Context, f: impl FnOnce(&mut Self) -> T) -> T {
+        let previous = *self.context_mut();
+        self.context_mut().remove(flags);
+        let result = f(self);
+        *self.context_mut() = previous;
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Context, WithContext};
+
+    struct Parser(Context);
+
+    impl WithContext for Parser {
+        fn context_mut(&mut self) -> &mut Context {
+            &mut self.0
+        }
+    }
+
+    #[test]
+    fn scoped() {
+        let mut parser = Parser(Context::PLAIN_CSS);
+        assert!(parser.0.is_top_level());
+        assert!(!parser.0.allows_declarations());
+
+        parser.with_context(Context::STYLE_RULE, |parser| {
+            assert!(parser.0.allows_declarations());
+            parser.with_context(Context::FUNCTION, |parser| {
+                assert!(!parser.0.allows_declarations());
+            });
+            parser.without_context(Context::STYLE_RULE, |parser| {
+                assert!(parser.0.is_top_level());
+            });
+            assert_eq!(parser.0, Context::PLAIN_CSS | Context::STYLE_RULE);
+        });
+        assert_eq!(parser.0, Context::PLAIN_CSS);
+    }
+}
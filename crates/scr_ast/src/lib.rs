@@ -1,7 +1,31 @@
-mod source_type;
-
-pub use source_type::SourceType;
+extern crate self as scr_ast;
 
-pub type Atom = compact_str::CompactString;
+mod ast_builder;
+mod ast_kind;
+mod atom;
+mod content;
+pub mod context;
+pub mod line_index;
+pub mod node;
+mod node_id;
+mod parent_map;
+pub mod serialize;
+mod source_map;
+mod source_type;
+mod span;
+mod visit;
+mod visit_mut;
 
-pub type Span = std::ops::Range<usize>;
+pub use ast_builder::AstBuilder;
+pub use ast_kind::AstKind;
+pub use atom::Atom;
+pub use content::{ContentEq, ContentHash};
+pub use node_id::{NodeId, NodeIdGenerator, SideTable};
+pub use parent_map::ParentMap;
+pub use scr_allocator::CloneIn;
+pub use scr_ast_macros::{CloneIn, ContentEq, ContentHash};
+pub use source_map::{FileId, SourceFile, SourceMap};
+pub use source_type::{SourceType, Syntax};
+pub use span::{Span, SPAN};
+pub use visit::{walk, Visit};
+pub use visit_mut::{walk_mut, VisitMut};
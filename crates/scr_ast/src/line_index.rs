@@ -0,0 +1,156 @@
+//! Conversion between byte offsets and line/column positions
+//!
+//! Lines end at `\n`, `\r\n` or `\r`. Lines and columns are zero-based. Columns can be counted
+//! in UTF-8 bytes, UTF-16 code units (what the Language Server Protocol uses) or chars.
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    Utf8,
+    Utf16,
+    Char,
+}
+
+/// A zero-based line and column.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LineCol {
+    pub line: u32,
+    pub col: u32,
+}
+
+/// A non-ASCII char, the only chars whose width differs between encodings.
+#[derive(Debug, Clone, Copy)]
+struct WideChar {
+    /// Byte column of the char in its line
+    start: u32,
+    len_utf8: u32,
+}
+
+impl WideChar {
+    const fn len(self, encoding: Encoding) -> u32 {
+        match encoding {
+            Encoding::Utf8 => self.len_utf8,
+            Encoding::Utf16 if self.len_utf8 == 4 => 2,
+            Encoding::Utf16 | Encoding::Char => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    /// Byte offset of the first char of each line
+    line_starts: Vec<u32>,
+    /// Non-ASCII chars by line, lines with only ASCII are left out
+    wide_chars: HashMap<u32, Vec<WideChar>>,
+    len: u32,
+}
+
+impl LineIndex {
+    /// # Panics
+    ///
+    /// If `text` is longer than `u32::MAX` bytes.
+    #[must_use]
+    pub fn new(text: &str) -> Self {
+        let len = u32::try_from(text.len()).expect("source text is larger than 4 GiB");
+        let mut line_starts = vec![0];
+        let mut wide_chars: HashMap<u32, Vec<WideChar>> = HashMap::new();
+        let mut chars = text.char_indices().peekable();
+        while let Some((offset, c)) = chars.next() {
+            // `offset` fits in `u32`, it is less than `len`
+            #[allow(clippy::cast_possible_truncation)]
+            let offset = offset as u32;
+            match c {
+                '\r' if chars.peek().is_some_and(|&(_, next)| next == '\n') => {}
+                '\n' | '\r' => line_starts.push(offset + 1),
+                c if !c.is_ascii() => {
+                    let line = line_starts.len() - 1;
+                    let start = offset - line_starts[line];
+                    #[allow(clippy::cast_possible_truncation)]
+                    let (line, len_utf8) = (line as u32, c.len_utf8() as u32);
+                    wide_chars.entry(line).or_default().push(WideChar { start, len_utf8 });
+                }
+                _ => {}
+            }
+        }
+        Self { line_starts, wide_chars, len }
+    }
+
+    #[must_use]
+    pub const fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Byte offset of the start of `line`
+    #[must_use]
+    pub fn line_start(&self, line: u32) -> Option<u32> {
+        self.line_starts.get(line as usize).copied()
+    }
+
+    /// Line and column of the byte `offset`.
+    ///
+    /// Offsets past the end of the text are clamped to the end.
+    #[must_use]
+    pub fn line_col(&self, offset: u32, encoding: Encoding) -> LineCol {
+        let offset = offset.min(self.len);
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        #[allow(clippy::cast_possible_truncation)]
+        let line = line as u32;
+        let col = offset - self.line_starts[line as usize];
+        LineCol { line, col: self.utf8_to_col(encoding, line, col) }
+    }
+
+    /// Byte offset of a line and column, `None` if the position is outside the text.
+    #[must_use]
+    pub fn offset(&self, line_col: LineCol, encoding: Encoding) -> Option<u32> {
+        let start = self.line_start(line_col.line)?;
+        let end = self.line_start(line_col.line + 1).unwrap_or(self.len);
+        let offset = start + self.col_to_utf8(encoding, line_col.line, line_col.col);
+        (offset <= end).then_some(offset)
+    }
+
+    fn utf8_to_col(&self, encoding: Encoding, line: u32, col: u32) -> u32 {
+        let Some(wide_chars) = self.wide_chars.get(&line) else { return col };
+        wide_chars
+            .iter()
+            .take_while(|c| c.start < col)
+            .fold(col, |col, &c| col - c.len_utf8 + c.len(encoding))
+    }
+
+    fn col_to_utf8(&self, encoding: Encoding, line: u32, mut col: u32) -> u32 {
+        if let Some(wide_chars) = self.wide_chars.get(&line) {
+            for &c in wide_chars {
+                // `col` becomes a byte column as the chars before it are accounted for
+                if col <= c.start {
+                    break;
+                }
+                col = col + c.len_utf8 - c.len(encoding);
+            }
+        }
+        col
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Encoding, LineCol, LineIndex};
+
+    #[test]
+    fn line_col() {
+        // "é" is 2 bytes and 1 UTF-16 unit, "😀" is 4 bytes and 2 UTF-16 units
+        let text = "a {\r\n  b: \"é😀\";\r}\nc";
+        let index = LineIndex::new(text);
+        assert_eq!(index.line_count(), 4);
+
+        let offset = u32::try_from(text.find(';').unwrap()).unwrap();
+        for (encoding, col) in [(Encoding::Utf8, 13), (Encoding::Utf16, 10), (Encoding::Char, 9)] {
+            let line_col = LineCol { line: 1, col };
+            assert_eq!(index.line_col(offset, encoding), line_col);
+            assert_eq!(index.offset(line_col, encoding), Some(offset));
+        }
+
+        assert_eq!(index.line_col(4, Encoding::Utf8), LineCol { line: 0, col: 4 });
+        assert_eq!(index.line_col(100, Encoding::Utf8), LineCol { line: 3, col: 1 });
+        assert_eq!(index.offset(LineCol { line: 4, col: 0 }, Encoding::Utf8), None);
+    }
+}
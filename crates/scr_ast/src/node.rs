@@ -0,0 +1,24 @@
+//! Sass AST nodes
+//!
+//! The node shapes follow Dart Sass's `lib/src/ast/sass`, except that selectors, media queries and
+//! supports conditions are parsed into structured nodes instead of being kept as raw interpolation.
+//!
+//! Every struct node carries a `span` and an `id`, and every enum node wraps boxed struct nodes.
+//! The visitors in [`crate::Visit`] and [`crate::VisitMut`] and [`crate::AstKind`] are generated
+//! from the definitions in this module, so a new node or field only needs to be declared here.
+
+mod doc;
+mod expression;
+mod interpolation;
+mod media;
+mod selector;
+mod statement;
+mod supports;
+
+pub use doc::*;
+pub use expression::*;
+pub use interpolation::*;
+pub use media::*;
+pub use selector::*;
+pub use statement::*;
+pub use supports::*;
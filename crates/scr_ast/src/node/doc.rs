@@ -0,0 +1,125 @@
+//! `SassDoc` comments
+//! <http://sassdoc.com/annotations/>
+
+use scr_allocator::{Box, Vec};
+use serde::Serialize;
+
+use crate::{serialize::skip_span, Atom, CloneIn, ContentEq, ContentHash, NodeId, Span};
+
+/// Consecutive `///` comments documenting the mixin, function, variable or placeholder rule that
+/// follows them
+///
+/// Like other comments, it's ignored by [`ContentEq`](crate::ContentEq).
+#[derive(Debug, Serialize, CloneIn)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct DocComment<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    /// The text before the first annotation
+    pub description: &'a str,
+    pub annotations: Vec<'a, DocAnnotation<'a>>,
+}
+
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(untagged)]
+pub enum DocAnnotation<'a> {
+    Access(Box<'a, DocAccess>),
+    Deprecated(Box<'a, DocDeprecated<'a>>),
+    Example(Box<'a, DocExample<'a>>),
+    Param(Box<'a, DocParam<'a>>),
+    Return(Box<'a, DocReturn<'a>>),
+    See(Box<'a, DocSee<'a>>),
+}
+
+/// `@access public` or `@access private`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct DocAccess {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub level: DocAccessLevel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, CloneIn, ContentEq, ContentHash)]
+pub enum DocAccessLevel {
+    Public,
+    Private,
+}
+
+impl DocAccessLevel {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::Private => "private",
+        }
+    }
+}
+
+/// `@deprecated [message]`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct DocDeprecated<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub message: &'a str,
+}
+
+/// `@example [language] [- description]`, followed by the code on the next lines
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct DocExample<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub language: Option<&'a str>,
+    pub description: &'a str,
+    pub code: &'a str,
+}
+
+/// `@param {type} $name [default] - description`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct DocParam<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    /// e.g. `Number | Null`
+    pub types: Option<&'a str>,
+    /// Without the `$`
+    pub name: Atom,
+    pub default: Option<&'a str>,
+    pub description: &'a str,
+}
+
+/// `@return {type} [description]`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct DocReturn<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub types: Option<&'a str>,
+    pub description: &'a str,
+}
+
+/// `@see [{kind}] name`, where the kind is e.g. `mixin`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct DocSee<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub kind: Option<&'a str>,
+    pub name: &'a str,
+}
@@ -0,0 +1,356 @@
+use scr_allocator::{Box, Vec};
+use serde::Serialize;
+
+use super::Interpolation;
+use crate::{serialize::skip_span, Atom, CloneIn, ContentEq, ContentHash, NodeId, Span};
+
+/// A `SassScript` expression
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(untagged)]
+pub enum Expression<'a> {
+    Binary(Box<'a, BinaryExpression<'a>>),
+    Boolean(Box<'a, BooleanLiteral>),
+    Color(Box<'a, ColorLiteral<'a>>),
+    Function(Box<'a, FunctionExpression<'a>>),
+    InterpolatedFunction(Box<'a, InterpolatedFunctionExpression<'a>>),
+    If(Box<'a, IfExpression<'a>>),
+    List(Box<'a, ListExpression<'a>>),
+    Map(Box<'a, MapExpression<'a>>),
+    Null(Box<'a, NullLiteral>),
+    Number(Box<'a, NumberLiteral>),
+    Parenthesized(Box<'a, ParenthesizedExpression<'a>>),
+    ParentSelector(Box<'a, ParentSelectorExpression>),
+    String(Box<'a, StringExpression<'a>>),
+    Unary(Box<'a, UnaryExpression<'a>>),
+    Variable(Box<'a, VariableExpression>),
+}
+
+/// `left <operator> right`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct BinaryExpression<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub operator: BinaryOperator,
+    pub left: Expression<'a>,
+    pub right: Expression<'a>,
+    /// Whether a `/` operation may be emitted as a literal slash, e.g. `font: 12px/1.5`.
+    pub allows_slash: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, CloneIn, ContentEq, ContentHash)]
+pub enum BinaryOperator {
+    /// `=`, only allowed in the arguments of IE's `alpha()` filter
+    SingleEquals,
+    Or,
+    And,
+    Equals,
+    NotEquals,
+    GreaterThan,
+    GreaterThanOrEquals,
+    LessThan,
+    LessThanOrEquals,
+    Plus,
+    Minus,
+    Times,
+    DividedBy,
+    Modulo,
+}
+
+impl BinaryOperator {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::SingleEquals => "=",
+            Self::Or => "or",
+            Self::And => "and",
+            Self::Equals => "==",
+            Self::NotEquals => "!=",
+            Self::GreaterThan => ">",
+            Self::GreaterThanOrEquals => ">=",
+            Self::LessThan => "<",
+            Self::LessThanOrEquals => "<=",
+            Self::Plus => "+",
+            Self::Minus => "-",
+            Self::Times => "*",
+            Self::DividedBy => "/",
+            Self::Modulo => "%",
+        }
+    }
+
+    /// Higher binds tighter, matching Dart Sass's `BinaryOperator.precedence`.
+    #[must_use]
+    pub const fn precedence(self) -> u8 {
+        match self {
+            Self::SingleEquals => 0,
+            Self::Or => 1,
+            Self::And => 2,
+            Self::Equals | Self::NotEquals => 3,
+            Self::GreaterThan
+            | Self::GreaterThanOrEquals
+            | Self::LessThan
+            | Self::LessThanOrEquals => 4,
+            Self::Plus | Self::Minus => 5,
+            Self::Times | Self::DividedBy | Self::Modulo => 6,
+        }
+    }
+}
+
+/// `<operator>argument`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct UnaryExpression<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub operator: UnaryOperator,
+    pub argument: Expression<'a>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, CloneIn, ContentEq, ContentHash)]
+pub enum UnaryOperator {
+    Plus,
+    Minus,
+    Divide,
+    Not,
+}
+
+impl UnaryOperator {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Plus => "+",
+            Self::Minus => "-",
+            Self::Divide => "/",
+            Self::Not => "not",
+        }
+    }
+}
+
+/// `true` or `false`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct BooleanLiteral {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub value: bool,
+}
+
+/// `null`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct NullLiteral {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+}
+
+/// A number with an optional unit, e.g. `1.5`, `10px` or `50%`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct NumberLiteral {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub value: f64,
+    pub unit: Option<Atom>,
+}
+
+/// A hex color such as `#fff`, `value` holds the digits without the leading `#`.
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ColorLiteral<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub value: &'a str,
+}
+
+/// A quoted or unquoted string, possibly interpolated.
+///
+/// Identifiers such as `bold` and special functions such as `url(foo.png)` are unquoted strings.
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct StringExpression<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub text: Interpolation<'a>,
+    pub quoted: bool,
+}
+
+/// A space-, comma- or slash-separated list, optionally in square brackets.
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ListExpression<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub elements: Vec<'a, Expression<'a>>,
+    pub separator: ListSeparator,
+    pub bracketed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(rename_all = "lowercase")]
+pub enum ListSeparator {
+    Space,
+    Comma,
+    Slash,
+    /// A list with fewer than two elements, e.g. `()` or `[a]`
+    Undecided,
+}
+
+/// `(key: value, ...)`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct MapExpression<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub entries: Vec<'a, MapEntry<'a>>,
+}
+
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct MapEntry<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub key: Expression<'a>,
+    pub value: Expression<'a>,
+}
+
+/// A Sass or plain CSS function call, e.g. `darken($c, 10%)` or `math.div(1, 2)`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct FunctionExpression<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub namespace: Option<Atom>,
+    pub name: Atom,
+    pub arguments: ArgumentList<'a>,
+}
+
+/// A function call whose name is interpolated, always emitted as plain CSS, e.g. `foo-#{$x}(1)`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct InterpolatedFunctionExpression<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Interpolation<'a>,
+    pub arguments: ArgumentList<'a>,
+}
+
+/// `if($condition, $if-true, $if-false)`, whose arguments are evaluated lazily.
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct IfExpression<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub arguments: ArgumentList<'a>,
+}
+
+/// `(expression)`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ParenthesizedExpression<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub expression: Expression<'a>,
+}
+
+/// `&` used as a value in an expression.
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ParentSelectorExpression {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+}
+
+/// `$name` or `namespace.$name`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct VariableExpression {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub namespace: Option<Atom>,
+    pub name: Atom,
+}
+
+/// Arguments passed to a function, mixin or content block.
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ArgumentList<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub positional: Vec<'a, Expression<'a>>,
+    pub named: Vec<'a, NamedArgument<'a>>,
+    /// `$args...`
+    pub rest: Option<Expression<'a>>,
+    /// `$kwargs...`, passed after `rest`.
+    pub keyword_rest: Option<Expression<'a>>,
+}
+
+/// `$name: value` in an [`ArgumentList`].
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct NamedArgument<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Atom,
+    pub value: Expression<'a>,
+}
+
+/// Parameters declared by a `@mixin`, `@function` or content block.
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ParameterList<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub parameters: Vec<'a, Parameter<'a>>,
+    /// `$args...`
+    pub rest: Option<Atom>,
+}
+
+/// `$name` or `$name: default`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct Parameter<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Atom,
+    pub default: Option<Expression<'a>>,
+}
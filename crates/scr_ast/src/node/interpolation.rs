@@ -0,0 +1,60 @@
+use scr_allocator::{Box, Vec};
+use serde::Serialize;
+
+use super::Expression;
+use crate::{serialize::skip_span, CloneIn, ContentEq, ContentHash, NodeId, Span};
+
+/// Text that may contain `#{}` interpolated expressions.
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct Interpolation<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub parts: Vec<'a, InterpolationPart<'a>>,
+}
+
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(untagged)]
+pub enum InterpolationPart<'a> {
+    Text(Box<'a, InterpolationText<'a>>),
+    Expression(Expression<'a>),
+}
+
+/// A literal chunk of an [`Interpolation`], kept as written in the source.
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct InterpolationText<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub value: &'a str,
+}
+
+impl<'a> Interpolation<'a> {
+    /// Returns the text if this interpolation has no `#{}` expressions.
+    #[must_use]
+    pub fn as_plain(&self) -> Option<&'a str> {
+        match self.parts.as_slice() {
+            [] => Some(""),
+            [InterpolationPart::Text(text)] => Some(text.value),
+            _ => None,
+        }
+    }
+
+    /// Whether this names a custom property, i.e. starts with `--` before any `#{}`
+    #[must_use]
+    pub fn is_custom_property_name(&self) -> bool {
+        self.parts.first().is_some_and(|part| match part {
+            InterpolationPart::Text(text) => text.value.starts_with("--"),
+            InterpolationPart::Expression(_) => false,
+        })
+    }
+
+    #[must_use]
+    pub fn is_plain(&self) -> bool {
+        self.parts.iter().all(|part| matches!(part, InterpolationPart::Text(_)))
+    }
+}
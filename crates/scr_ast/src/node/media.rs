@@ -0,0 +1,145 @@
+//! Media queries
+//! <https://drafts.csswg.org/mediaqueries-4/#mq-syntax>
+
+use scr_allocator::{Box, Vec};
+use serde::Serialize;
+
+use super::{Expression, Interpolation};
+use crate::{serialize::skip_span, Atom, CloneIn, ContentEq, ContentHash, NodeId, Span};
+
+/// `screen and (min-width: 100px), print`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct MediaQueryList<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub queries: Vec<'a, MediaQuery<'a>>,
+}
+
+/// `[not | only] media-type [and condition]` or a bare condition
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct MediaQuery<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    /// `not` or `only`
+    pub modifier: Option<Atom>,
+    pub media_type: Option<Interpolation<'a>>,
+    pub condition: Option<MediaCondition<'a>>,
+}
+
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(untagged)]
+pub enum MediaCondition<'a> {
+    Feature(Box<'a, MediaFeature<'a>>),
+    Range(Box<'a, MediaRange<'a>>),
+    Not(Box<'a, MediaNot<'a>>),
+    Operation(Box<'a, MediaOperation<'a>>),
+    Style(Box<'a, StyleQuery<'a>>),
+    /// `#{$condition}`
+    Interpolation(Box<'a, Interpolation<'a>>),
+}
+
+/// `(name)` or `(name: value)`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct MediaFeature<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Interpolation<'a>,
+    pub value: Option<Expression<'a>>,
+}
+
+/// `(left < middle)` or `(left < middle <= right)`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct MediaRange<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub left: Expression<'a>,
+    pub left_comparison: MediaComparison,
+    pub middle: Expression<'a>,
+    pub right_comparison: Option<MediaComparison>,
+    pub right: Option<Expression<'a>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, CloneIn, ContentEq, ContentHash)]
+pub enum MediaComparison {
+    Equal,
+    GreaterThan,
+    GreaterThanOrEqual,
+    LessThan,
+    LessThanOrEqual,
+}
+
+impl MediaComparison {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Equal => "=",
+            Self::GreaterThan => ">",
+            Self::GreaterThanOrEqual => ">=",
+            Self::LessThan => "<",
+            Self::LessThanOrEqual => "<=",
+        }
+    }
+}
+
+/// `not (condition)`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct MediaNot<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub condition: MediaCondition<'a>,
+}
+
+/// `(a) and (b) and (c)` or `(a) or (b)`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct MediaOperation<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub operator: MediaOperator,
+    pub conditions: Vec<'a, MediaCondition<'a>>,
+}
+
+/// `style(--name: value)` in `@container`, whose features are custom properties with values
+/// kept as text
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct StyleQuery<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub condition: MediaCondition<'a>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, CloneIn, ContentEq, ContentHash)]
+pub enum MediaOperator {
+    And,
+    Or,
+}
+
+impl MediaOperator {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::And => "and",
+            Self::Or => "or",
+        }
+    }
+}
@@ -0,0 +1,223 @@
+//! Selectors
+//! Names may contain interpolation, which is resolved and re-parsed during evaluation.
+
+use scr_allocator::{Box, Vec};
+use serde::Serialize;
+
+use super::{Interpolation, StringExpression};
+use crate::{serialize::skip_span, Atom, CloneIn, ContentEq, ContentHash, NodeId, Span};
+
+/// `a, b`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct SelectorList<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub selectors: Vec<'a, ComplexSelector<'a>>,
+}
+
+/// Compound selectors joined by combinators, e.g. `a > .b ~ c`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ComplexSelector<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    /// A combinator before the first compound selector, e.g. `> a` in a relative selector.
+    pub leading_combinator: Option<Combinator>,
+    pub components: Vec<'a, ComplexSelectorComponent<'a>>,
+}
+
+/// A compound selector and the combinator that follows it.
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ComplexSelectorComponent<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub selector: CompoundSelector<'a>,
+    /// `None` only for the last component, unless it has a trailing combinator.
+    pub combinator: Option<Combinator>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, CloneIn, ContentEq, ContentHash)]
+pub enum Combinator {
+    /// whitespace
+    Descendant,
+    /// `>`
+    Child,
+    /// `+`
+    NextSibling,
+    /// `~`
+    SubsequentSibling,
+}
+
+impl Combinator {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Descendant => " ",
+            Self::Child => ">",
+            Self::NextSibling => "+",
+            Self::SubsequentSibling => "~",
+        }
+    }
+}
+
+/// Simple selectors without whitespace between them, e.g. `a.b#c:hover`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct CompoundSelector<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub components: Vec<'a, SimpleSelector<'a>>,
+}
+
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(untagged)]
+pub enum SimpleSelector<'a> {
+    Attribute(Box<'a, AttributeSelector<'a>>),
+    Class(Box<'a, ClassSelector<'a>>),
+    Id(Box<'a, IdSelector<'a>>),
+    Parent(Box<'a, ParentSelector<'a>>),
+    Placeholder(Box<'a, PlaceholderSelector<'a>>),
+    Pseudo(Box<'a, PseudoSelector<'a>>),
+    Type(Box<'a, TypeSelector<'a>>),
+    Universal(Box<'a, UniversalSelector<'a>>),
+}
+
+/// `[name]` or `[name <matcher> value modifier]`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct AttributeSelector<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub namespace: Option<Interpolation<'a>>,
+    pub name: Interpolation<'a>,
+    pub matcher: Option<AttributeMatcher>,
+    pub value: Option<StringExpression<'a>>,
+    /// `i` or `s`
+    pub modifier: Option<Atom>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, CloneIn, ContentEq, ContentHash)]
+pub enum AttributeMatcher {
+    /// `=`
+    Equal,
+    /// `~=`
+    Includes,
+    /// `|=`
+    DashMatch,
+    /// `^=`
+    Prefix,
+    /// `$=`
+    Suffix,
+    /// `*=`
+    Substring,
+}
+
+impl AttributeMatcher {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Equal => "=",
+            Self::Includes => "~=",
+            Self::DashMatch => "|=",
+            Self::Prefix => "^=",
+            Self::Suffix => "$=",
+            Self::Substring => "*=",
+        }
+    }
+}
+
+/// `.name`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ClassSelector<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Interpolation<'a>,
+}
+
+/// `#name`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct IdSelector<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Interpolation<'a>,
+}
+
+/// `&`, optionally followed by a suffix such as `&-active`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ParentSelector<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub suffix: Option<Interpolation<'a>>,
+}
+
+/// `%name`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct PlaceholderSelector<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Interpolation<'a>,
+}
+
+/// `:name`, `::name` or `:name(argument)`
+///
+/// Selector pseudo-classes such as `:not()`, `:is()` or `:has()` store their argument in
+/// `selector`, any other argument is kept as text in `argument`.
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct PseudoSelector<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Interpolation<'a>,
+    pub is_element: bool,
+    pub argument: Option<Interpolation<'a>>,
+    pub selector: Option<SelectorList<'a>>,
+}
+
+/// `name` or `namespace|name`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct TypeSelector<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub namespace: Option<Interpolation<'a>>,
+    pub name: Interpolation<'a>,
+}
+
+/// `*` or `namespace|*`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct UniversalSelector<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub namespace: Option<Interpolation<'a>>,
+}
@@ -0,0 +1,641 @@
+use scr_allocator::{Box, Vec};
+use serde::Serialize;
+
+use super::{
+    ArgumentList, DocComment, Expression, Interpolation, MediaCondition, MediaQueryList,
+    ParameterList, SelectorList, SupportsCondition,
+};
+use crate::{serialize::skip_span, Atom, CloneIn, ContentEq, ContentHash, NodeId, Span};
+
+/// The root of a parsed `.scss`, `.sass` or `.css` file.
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct Stylesheet<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(untagged)]
+pub enum Statement<'a> {
+    AtRoot(Box<'a, AtRootRule<'a>>),
+    AtRule(Box<'a, AtRule<'a>>),
+    Container(Box<'a, ContainerRule<'a>>),
+    Content(Box<'a, ContentRule<'a>>),
+    CounterStyle(Box<'a, CounterStyleRule<'a>>),
+    Debug(Box<'a, DebugRule<'a>>),
+    Declaration(Box<'a, Declaration<'a>>),
+    Each(Box<'a, EachRule<'a>>),
+    Error(Box<'a, ErrorRule<'a>>),
+    Extend(Box<'a, ExtendRule<'a>>),
+    FontFeatureValues(Box<'a, FontFeatureValuesRule<'a>>),
+    For(Box<'a, ForRule<'a>>),
+    Forward(Box<'a, ForwardRule<'a>>),
+    Function(Box<'a, FunctionRule<'a>>),
+    If(Box<'a, IfRule<'a>>),
+    Import(Box<'a, ImportRule<'a>>),
+    Include(Box<'a, IncludeRule<'a>>),
+    Invalid(Box<'a, InvalidStatement>),
+    KeyframeBlock(Box<'a, KeyframeBlock<'a>>),
+    Keyframes(Box<'a, KeyframesRule<'a>>),
+    Layer(Box<'a, LayerRule<'a>>),
+    #[content_eq(trivia)]
+    LoudComment(Box<'a, LoudComment<'a>>),
+    Media(Box<'a, MediaRule<'a>>),
+    Mixin(Box<'a, MixinRule<'a>>),
+    Page(Box<'a, PageRule<'a>>),
+    PageMargin(Box<'a, PageMarginRule<'a>>),
+    Property(Box<'a, PropertyRule<'a>>),
+    Return(Box<'a, ReturnRule<'a>>),
+    Scope(Box<'a, ScopeRule<'a>>),
+    #[content_eq(trivia)]
+    SilentComment(Box<'a, SilentComment<'a>>),
+    StartingStyle(Box<'a, StartingStyleRule<'a>>),
+    StyleRule(Box<'a, StyleRule<'a>>),
+    Supports(Box<'a, SupportsRule<'a>>),
+    Use(Box<'a, UseRule<'a>>),
+    VariableDeclaration(Box<'a, VariableDeclaration<'a>>),
+    Warn(Box<'a, WarnRule<'a>>),
+    While(Box<'a, WhileRule<'a>>),
+}
+
+/// `selector { ... }`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct StyleRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub selector: SelectorList<'a>,
+    pub body: Vec<'a, Statement<'a>>,
+    /// The `SassDoc` of a placeholder rule such as `%name { ... }`
+    pub doc: Option<DocComment<'a>>,
+}
+
+/// `name: value;`, or a nested property such as `font: { family: x; }`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct Declaration<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Interpolation<'a>,
+    /// Custom properties keep their value as an unquoted [`super::StringExpression`].
+    pub value: Option<Expression<'a>>,
+    pub body: Option<Vec<'a, Statement<'a>>>,
+}
+
+impl Declaration<'_> {
+    /// Whether this declares a custom property such as `--color`
+    #[must_use]
+    pub fn is_custom_property(&self) -> bool {
+        self.name.is_custom_property_name()
+    }
+}
+
+/// `$name: value [!default] [!global];`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct VariableDeclaration<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub namespace: Option<Atom>,
+    pub name: Atom,
+    pub value: Expression<'a>,
+    /// `!default`
+    pub guarded: bool,
+    /// `!global`
+    pub global: bool,
+    pub doc: Option<DocComment<'a>>,
+}
+
+/// `/* ... */`, which is emitted to CSS
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct LoudComment<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub text: Interpolation<'a>,
+}
+
+/// `// ...`, which is dropped from CSS
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct SilentComment<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub text: &'a str,
+}
+
+/// Source that couldn't be parsed, skipped so that parsing carries on with the next statement
+///
+/// The error itself is reported separately.
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct InvalidStatement {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+}
+
+/// `@at-root [query] { ... }`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct AtRootRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub query: Option<Interpolation<'a>>,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+/// An at-rule Sass doesn't know about, e.g. `@font-face` or `@vendor-rule foo;`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct AtRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Interpolation<'a>,
+    pub value: Option<Interpolation<'a>>,
+    pub body: Option<Vec<'a, Statement<'a>>>,
+}
+
+/// `@container [name] [condition] { ... }`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ContainerRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Option<Interpolation<'a>>,
+    /// Size queries, and `style()` queries
+    pub condition: Option<MediaCondition<'a>>,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+/// `@content [(arguments)];`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ContentRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub arguments: ArgumentList<'a>,
+}
+
+/// `@counter-style name { ... }`, whose body holds only declarations
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct CounterStyleRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Interpolation<'a>,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+/// `@debug expression;`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct DebugRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub expression: Expression<'a>,
+}
+
+/// `@each $a, $b in list { ... }`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct EachRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub variables: Vec<'a, Atom>,
+    pub list: Expression<'a>,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+/// `@error expression;`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ErrorRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub expression: Expression<'a>,
+}
+
+/// `@extend selector [!optional];`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ExtendRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub selector: SelectorList<'a>,
+    pub optional: bool,
+}
+
+/// `@font-feature-values family, ... { ... }`, whose body holds declarations and feature
+/// value blocks such as `@swash { ... }`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct FontFeatureValuesRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub families: Expression<'a>,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+/// `@for $i from 1 through 10 { ... }`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ForRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub variable: Atom,
+    pub from: Expression<'a>,
+    pub to: Expression<'a>,
+    /// `to` rather than `through`
+    pub exclusive: bool,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+/// `@forward "url" [as prefix-*] [show|hide members] [with (...)];`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ForwardRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub url: &'a str,
+    pub prefix: Option<Atom>,
+    pub shown: Option<Vec<'a, ForwardMember>>,
+    pub hidden: Option<Vec<'a, ForwardMember>>,
+    pub configuration: Vec<'a, ConfiguredVariable<'a>>,
+}
+
+/// A mixin, function or `$variable` name in `@forward ... show` or `hide`.
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ForwardMember {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Atom,
+    pub is_variable: bool,
+}
+
+/// `$name: value [!default]` in a `with (...)` clause.
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ConfiguredVariable<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Atom,
+    pub value: Expression<'a>,
+    pub guarded: bool,
+}
+
+/// `@function name(parameters) { ... }`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct FunctionRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Atom,
+    pub parameters: ParameterList<'a>,
+    pub body: Vec<'a, Statement<'a>>,
+    pub doc: Option<DocComment<'a>>,
+}
+
+/// `@if condition { ... } @else if condition { ... } @else { ... }`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct IfRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub clauses: Vec<'a, IfClause<'a>>,
+    pub else_clause: Option<ElseClause<'a>>,
+}
+
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct IfClause<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub condition: Expression<'a>,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ElseClause<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+/// `@import "a", "b";`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ImportRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub imports: Vec<'a, Import<'a>>,
+}
+
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(untagged)]
+pub enum Import<'a> {
+    Dynamic(Box<'a, DynamicImport<'a>>),
+    Static(Box<'a, StaticImport<'a>>),
+}
+
+/// An import of a Sass file, resolved at compile time.
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct DynamicImport<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub url: &'a str,
+}
+
+/// A plain CSS `@import`, e.g. `url(foo.css)` or `"foo.css" screen`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct StaticImport<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub url: Interpolation<'a>,
+    pub modifiers: Option<Interpolation<'a>>,
+}
+
+/// `@include [namespace.]name[(arguments)] [using (parameters)] [{ ... }]`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct IncludeRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub namespace: Option<Atom>,
+    pub name: Atom,
+    pub arguments: ArgumentList<'a>,
+    pub content: Option<ContentBlock<'a>>,
+}
+
+/// The block passed to an `@include`.
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ContentBlock<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub parameters: ParameterList<'a>,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+/// A block inside `@keyframes`, e.g. `from { ... }` or `50%, 75% { ... }`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct KeyframeBlock<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub selectors: Vec<'a, Interpolation<'a>>,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+/// `@keyframes name { ... }`, whose children are [`KeyframeBlock`]s
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct KeyframesRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    /// e.g. `-webkit-` in `@-webkit-keyframes`
+    pub vendor_prefix: Option<Atom>,
+    pub name: Interpolation<'a>,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+/// `@layer a, b.c;` or `@layer [name] { ... }`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct LayerRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub names: Vec<'a, Interpolation<'a>>,
+    pub body: Option<Vec<'a, Statement<'a>>>,
+}
+
+/// `@media query { ... }`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct MediaRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub query: MediaQueryList<'a>,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+/// `@mixin name[(parameters)] { ... }`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct MixinRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Atom,
+    pub parameters: ParameterList<'a>,
+    pub body: Vec<'a, Statement<'a>>,
+    /// Whether the body contains a `@content` rule
+    pub has_content: bool,
+    pub doc: Option<DocComment<'a>>,
+}
+
+/// `@page [selectors] { ... }`, whose body holds declarations and margin rules
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct PageRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub selectors: Vec<'a, PageSelector<'a>>,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+/// `name:first` in `@page`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct PageSelector<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Option<Interpolation<'a>>,
+    pub pseudo_classes: Vec<'a, Interpolation<'a>>,
+}
+
+/// A margin box in `@page`, e.g. `@top-left { ... }`, whose body holds only declarations
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct PageMarginRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Atom,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+/// `@property --name { ... }`, whose body holds only declarations
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct PropertyRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Interpolation<'a>,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+/// `@return expression;`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ReturnRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub expression: Expression<'a>,
+}
+
+/// `@scope [(root)] [to (limit)] { ... }`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct ScopeRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub root: Option<SelectorList<'a>>,
+    pub limit: Option<SelectorList<'a>>,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+/// `@starting-style { ... }`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct StartingStyleRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+/// `@supports condition { ... }`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct SupportsRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub condition: SupportsCondition<'a>,
+    pub body: Vec<'a, Statement<'a>>,
+}
+
+/// `@use "url" [as namespace] [with (...)];`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct UseRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub url: &'a str,
+    /// `None` for `as *`
+    pub namespace: Option<Atom>,
+    pub configuration: Vec<'a, ConfiguredVariable<'a>>,
+}
+
+/// `@warn expression;`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct WarnRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub expression: Expression<'a>,
+}
+
+/// `@while condition { ... }`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct WhileRule<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub condition: Expression<'a>,
+    pub body: Vec<'a, Statement<'a>>,
+}
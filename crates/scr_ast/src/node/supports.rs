@@ -0,0 +1,105 @@
+//! Supports conditions
+//! <https://drafts.csswg.org/css-conditional-3/#at-supports>
+
+use scr_allocator::Box;
+use serde::Serialize;
+
+use super::{Expression, Interpolation};
+use crate::{serialize::skip_span, CloneIn, ContentEq, ContentHash, NodeId, Span};
+
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(untagged)]
+pub enum SupportsCondition<'a> {
+    Anything(Box<'a, SupportsAnything<'a>>),
+    Declaration(Box<'a, SupportsDeclaration<'a>>),
+    Function(Box<'a, SupportsFunction<'a>>),
+    Interpolation(Box<'a, SupportsInterpolation<'a>>),
+    Negation(Box<'a, SupportsNegation<'a>>),
+    Operation(Box<'a, SupportsOperation<'a>>),
+}
+
+/// `(<any-value>)` that isn't a declaration, kept as text.
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct SupportsAnything<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub contents: Interpolation<'a>,
+}
+
+/// `(name: value)`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct SupportsDeclaration<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Expression<'a>,
+    pub value: Expression<'a>,
+}
+
+/// `name(arguments)`, e.g. `selector(:has(a))`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct SupportsFunction<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub name: Interpolation<'a>,
+    pub arguments: Interpolation<'a>,
+}
+
+/// `#{$condition}`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct SupportsInterpolation<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub expression: Expression<'a>,
+}
+
+/// `not condition`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct SupportsNegation<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub condition: SupportsCondition<'a>,
+}
+
+/// `left and right` or `left or right`
+#[derive(Debug, Serialize, CloneIn, ContentEq, ContentHash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct SupportsOperation<'a> {
+    #[serde(skip_serializing_if = "skip_span")]
+    pub span: Span,
+    #[serde(skip)]
+    pub id: NodeId,
+    pub left: SupportsCondition<'a>,
+    pub operator: SupportsOperator,
+    pub right: SupportsCondition<'a>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, CloneIn, ContentEq, ContentHash)]
+pub enum SupportsOperator {
+    And,
+    Or,
+}
+
+impl SupportsOperator {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::And => "and",
+            Self::Or => "or",
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Node ids and tables keyed by them
+//!
+//! Every struct node has a [`NodeId`], handed out by a [`NodeIdGenerator`] when the node is
+//! created. Ids are dense, so passes can store what they compute about nodes in a [`SideTable`]
+//! instead of mutating the AST.
+
+use std::{cell::Cell, ops::Index};
+
+use scr_allocator::{Allocator, CloneIn};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(u32);
+
+impl NodeId {
+    /// Placeholder for nodes that were not given an id.
+    pub const DUMMY: Self = Self(u32::MAX);
+
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    #[must_use]
+    pub const fn is_dummy(self) -> bool {
+        self.0 == Self::DUMMY.0
+    }
+}
+
+impl Default for NodeId {
+    fn default() -> Self {
+        Self::DUMMY
+    }
+}
+
+/// Clones keep their ids, so side tables of the original also apply to the clone.
+impl<'new> CloneIn<'new> for NodeId {
+    type Cloned = Self;
+
+    fn clone_in(&self, _allocator: &'new Allocator) -> Self {
+        *self
+    }
+}
+
+/// Hands out node ids in creation order, one generator per stylesheet.
+#[derive(Debug, Default)]
+pub struct NodeIdGenerator {
+    next: Cell<u32>,
+}
+
+impl NodeIdGenerator {
+    /// # Panics
+    ///
+    /// If all ids up to [`NodeId::DUMMY`] are used.
+    pub fn next_id(&self) -> NodeId {
+        let id = NodeId(self.next.get());
+        assert!(!id.is_dummy(), "too many AST nodes");
+        self.next.set(id.0 + 1);
+        id
+    }
+
+    /// Number of ids handed out so far
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.next.get() as usize
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.next.get() == 0
+    }
+}
+
+/// Values attached to nodes, stored densely by id.
+#[derive(Debug, Clone)]
+pub struct SideTable<T> {
+    values: Vec<Option<T>>,
+    len: usize,
+}
+
+impl<T> Default for SideTable<T> {
+    fn default() -> Self {
+        Self { values: Vec::new(), len: 0 }
+    }
+}
+
+impl<T> SideTable<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A table with room for every id of `ids`
+    #[must_use]
+    pub fn for_ids(ids: &NodeIdGenerator) -> Self {
+        Self { values: Vec::with_capacity(ids.len()), len: 0 }
+    }
+
+    /// Sets the value for `id`, returning the previous one.
+    ///
+    /// # Panics
+    ///
+    /// If `id` is [`NodeId::DUMMY`].
+    pub fn insert(&mut self, id: NodeId, value: T) -> Option<T> {
+        assert!(!id.is_dummy(), "cannot attach a value to a node without an id");
+        if id.index() >= self.values.len() {
+            self.values.resize_with(id.index() + 1, || None);
+        }
+        let previous = self.values[id.index()].replace(value);
+        if previous.is_none() {
+            self.len += 1;
+        }
+        previous
+    }
+
+    #[must_use]
+    pub fn get(&self, id: NodeId) -> Option<&T> {
+        self.values.get(id.index())?.as_ref()
+    }
+
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut T> {
+        self.values.get_mut(id.index())?.as_mut()
+    }
+
+    #[must_use]
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.get(id).is_some()
+    }
+
+    pub fn remove(&mut self, id: NodeId) -> Option<T> {
+        let removed = self.values.get_mut(id.index())?.take();
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    /// Number of nodes with a value
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Values in id order
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, &T)> {
+        self.values.iter().enumerate().filter_map(|(index, value)| {
+            // Indices come from `NodeId`s, so they fit in `u32`
+            #[allow(clippy::cast_possible_truncation)]
+            value.as_ref().map(|value| (NodeId(index as u32), value))
+        })
+    }
+}
+
+impl<T> Index<NodeId> for SideTable<T> {
+    type Output = T;
+
+    /// # Panics
+    ///
+    /// If `id` has no value.
+    fn index(&self, id: NodeId) -> &T {
+        self.get(id).expect("no value for node")
+    }
+}
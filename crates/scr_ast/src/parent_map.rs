@@ -0,0 +1,81 @@
+use crate::{node::Stylesheet, AstKind, NodeId, SideTable, Visit};
+
+/// The parent of every struct node in a stylesheet, for walking up from a node.
+#[derive(Debug, Default)]
+pub struct ParentMap {
+    parents: SideTable<NodeId>,
+}
+
+impl ParentMap {
+    #[must_use]
+    pub fn new(stylesheet: &Stylesheet) -> Self {
+        let mut builder = Builder { parents: SideTable::new(), stack: Vec::new() };
+        builder.visit_stylesheet(stylesheet);
+        Self { parents: builder.parents }
+    }
+
+    /// `None` for the stylesheet, and for nodes not in it
+    #[must_use]
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.parents.get(id).copied()
+    }
+
+    /// The parent of `id`, its parent, and so on up to the stylesheet
+    pub fn ancestors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        std::iter::successors(self.parent(id), |&id| self.parent(id))
+    }
+}
+
+struct Builder {
+    parents: SideTable<NodeId>,
+    stack: Vec<NodeId>,
+}
+
+impl<'a> Visit<'a> for Builder {
+    fn enter_node(&mut self, kind: AstKind<'a, '_>) {
+        let id = kind.id();
+        if let Some(&parent) = self.stack.last() {
+            if !id.is_dummy() {
+                self.parents.insert(id, parent);
+            }
+        }
+        self.stack.push(id);
+    }
+
+    fn leave_node(&mut self, _kind: AstKind<'a, '_>) {
+        self.stack.pop();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ParentMap;
+    use crate::{node::*, AstBuilder, Atom, SPAN};
+
+    #[test]
+    fn parents() {
+        let allocator = scr_allocator::Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let value = ast.expression_null(SPAN);
+        let Expression::Null(null) = &value else { unreachable!() };
+        let null_id = null.id;
+        let declaration = ast.statement_variable_declaration(
+            SPAN,
+            None,
+            Atom::from("a"),
+            value,
+            false,
+            false,
+            None,
+        );
+        let Statement::VariableDeclaration(it) = &declaration else { unreachable!() };
+        let declaration_id = it.id;
+        let stylesheet = ast.stylesheet(SPAN, ast.vec_from_iter([declaration]));
+        assert_eq!(ast.ids().len(), 3);
+
+        let parents = ParentMap::new(&stylesheet);
+        assert_eq!(parents.parent(null_id), Some(declaration_id));
+        assert!(parents.ancestors(null_id).eq([declaration_id, stylesheet.id]));
+        assert_eq!(parents.parent(stylesheet.id), None);
+    }
+}
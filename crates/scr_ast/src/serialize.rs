@@ -0,0 +1,188 @@
+//! JSON serialization of the AST
+//!
+//! [`to_json`] wraps a node in an envelope carrying the schema version:
+//!
+//! ```json
+//! { "version": 1, "ast": { "type": "Stylesheet", "span": { "start": 0, "end": 12 }, "body": [] } }
+//! ```
+//!
+//! # Schema
+//!
+//! * Every struct node is an object whose `type` is the node's name in [`crate::node`], e.g.
+//!   `"StyleRule"` or `"NumberLiteral"`.
+//! * `span` holds the UTF-8 byte offsets `start` (inclusive) and `end` (exclusive) into the
+//!   source. It is left out when [`SerializeOptions::spans`] is `false`.
+//! * Other fields use the camelCase form of the Rust field name, e.g. `allowsSlash`. Absent
+//!   optional fields are `null`, lists are arrays.
+//! * Enum nodes such as `Statement` or `Expression` are not wrapped, the `type` of the node they
+//!   hold tells them apart.
+//! * Operators, combinators and attribute matchers are strings of their source text, e.g. `"+"`,
+//!   `"and"` or `"~="`; the descendant combinator is `" "`. List separators are `"space"`,
+//!   `"comma"`, `"slash"` or `"undecided"`.
+//!
+//! # Versioning
+//!
+//! [`SCHEMA_VERSION`] is bumped whenever a node or field is renamed or removed, or a value changes
+//! representation. Adding a node type or field does not bump it, so consumers should ignore
+//! unknown fields.
+
+use std::cell::Cell;
+
+use serde::{Serialize, Serializer};
+
+use crate::{
+    node::{
+        AttributeMatcher, BinaryOperator, Combinator, DocAccessLevel, MediaComparison,
+        MediaOperator, SupportsOperator, UnaryOperator,
+    },
+    Span,
+};
+
+/// Version of the JSON schema described in this module.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy)]
+pub struct SerializeOptions {
+    /// Include the `span` of every node.
+    pub spans: bool,
+    /// Indent the output.
+    pub pretty: bool,
+}
+
+impl Default for SerializeOptions {
+    fn default() -> Self {
+        Self { spans: true, pretty: false }
+    }
+}
+
+thread_local! {
+    static OMIT_SPANS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Restores the previous span mode, even if serialization panics.
+struct SpanModeGuard(bool);
+
+impl Drop for SpanModeGuard {
+    fn drop(&mut self) {
+        OMIT_SPANS.with(|omit| omit.set(self.0));
+    }
+}
+
+#[derive(Serialize)]
+struct Envelope<'n, T> {
+    version: u32,
+    ast: &'n T,
+}
+
+/// Serializes `node` and its descendants as versioned JSON.
+///
+/// # Panics
+///
+/// Never in practice, AST nodes always serialize to valid JSON.
+pub fn to_json<T: Serialize>(node: &T, options: SerializeOptions) -> String {
+    let _guard = SpanModeGuard(OMIT_SPANS.with(|omit| omit.replace(!options.spans)));
+    let envelope = Envelope { version: SCHEMA_VERSION, ast: node };
+    if options.pretty {
+        serde_json::to_string_pretty(&envelope).unwrap()
+    } else {
+        serde_json::to_string(&envelope).unwrap()
+    }
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+pub(crate) fn skip_span(_span: &Span) -> bool {
+    OMIT_SPANS.with(Cell::get)
+}
+
+macro_rules! serialize_as_str {
+    ($($ty:ty),*) => {
+        $(
+            impl Serialize for $ty {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_str(self.as_str())
+                }
+            }
+        )*
+    };
+}
+
+serialize_as_str!(
+    AttributeMatcher,
+    BinaryOperator,
+    Combinator,
+    DocAccessLevel,
+    MediaComparison,
+    MediaOperator,
+    SupportsOperator,
+    UnaryOperator
+);
+
+#[cfg(test)]
+mod test {
+    use scr_allocator::{Allocator, Box, Vec};
+
+    use super::{to_json, SerializeOptions};
+    use crate::{node::*, Atom, NodeId, Span};
+
+    fn stylesheet(allocator: &Allocator) -> Stylesheet<'_> {
+        let left = Expression::Number(Box(allocator.alloc(NumberLiteral {
+            span: Span::new(4, 7),
+            id: NodeId::DUMMY,
+            value: 1.0,
+            unit: Some(Atom::from("px")),
+        })));
+        let right = Expression::Variable(Box(allocator.alloc(VariableExpression {
+            span: Span::new(10, 12),
+            id: NodeId::DUMMY,
+            namespace: None,
+            name: Atom::from("b"),
+        })));
+        let value = Expression::Binary(Box(allocator.alloc(BinaryExpression {
+            span: Span::new(4, 12),
+            id: NodeId::DUMMY,
+            operator: BinaryOperator::Plus,
+            left,
+            right,
+            allows_slash: false,
+        })));
+        let declaration = VariableDeclaration {
+            span: Span::new(0, 13),
+            id: NodeId::DUMMY,
+            namespace: None,
+            name: Atom::from("a"),
+            value,
+            guarded: true,
+            global: false,
+            doc: None,
+        };
+        Stylesheet {
+            span: Span::new(0, 13),
+            id: NodeId::DUMMY,
+            body: Vec::from_iter_in(
+                [Statement::VariableDeclaration(Box(allocator.alloc(declaration)))],
+                allocator,
+            ),
+        }
+    }
+
+    #[test]
+    fn with_spans() {
+        let allocator = Allocator::default();
+        let json = to_json(&stylesheet(&allocator), SerializeOptions::default());
+        assert_eq!(
+            json,
+            r#"{"version":1,"ast":{"type":"Stylesheet","span":{"start":0,"end":13},"body":[{"type":"VariableDeclaration","span":{"start":0,"end":13},"namespace":null,"name":"a","value":{"type":"BinaryExpression","span":{"start":4,"end":12},"operator":"+","left":{"type":"NumberLiteral","span":{"start":4,"end":7},"value":1.0,"unit":"px"},"right":{"type":"VariableExpression","span":{"start":10,"end":12},"namespace":null,"name":"b"},"allowsSlash":false},"guarded":true,"global":false,"doc":null}]}}"#
+        );
+    }
+
+    #[test]
+    fn without_spans() {
+        let allocator = Allocator::default();
+        let options = SerializeOptions { spans: false, ..SerializeOptions::default() };
+        let json = to_json(&stylesheet(&allocator), options);
+        assert_eq!(
+            json,
+            r#"{"version":1,"ast":{"type":"Stylesheet","body":[{"type":"VariableDeclaration","namespace":null,"name":"a","value":{"type":"BinaryExpression","operator":"+","left":{"type":"NumberLiteral","value":1.0,"unit":"px"},"right":{"type":"VariableExpression","namespace":null,"name":"b"},"allowsSlash":false},"guarded":true,"global":false,"doc":null}]}}"#
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! Files loaded during a compilation
+//!
+//! Every file added to a [`SourceMap`] is given its own range of offsets, the way rustc does. A
+//! file parsed with its [`SourceFile::start_pos`] as base offset produces spans that point into
+//! that range, so the map can tell which file any span came from without storing a file id in
+//! every node. The first file starts at 0, so a single file can be parsed without a map.
+
+use crate::{
+    line_index::{Encoding, LineCol, LineIndex},
+    Span,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FileId(u32);
+
+impl FileId {
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[derive(Debug)]
+pub struct SourceFile {
+    id: FileId,
+    /// Path or URL the file was loaded from
+    name: String,
+    source: String,
+    start_pos: u32,
+    line_index: LineIndex,
+}
+
+impl SourceFile {
+    #[must_use]
+    pub const fn id(&self) -> FileId {
+        self.id
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// First offset of this file in the source map
+    #[must_use]
+    pub const fn start_pos(&self) -> u32 {
+        self.start_pos
+    }
+
+    /// The offset just past the end of this file
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn end_pos(&self) -> u32 {
+        // `add_file` checked that the length fits
+        self.start_pos + self.source.len() as u32
+    }
+
+    /// The span covering the whole file
+    #[must_use]
+    pub const fn span(&self) -> Span {
+        Span::new(self.start_pos, self.end_pos())
+    }
+
+    #[must_use]
+    pub const fn line_index(&self) -> &LineIndex {
+        &self.line_index
+    }
+
+    /// Converts a span of this file to offsets into [`Self::source`].
+    #[must_use]
+    pub const fn to_local(&self, span: Span) -> Span {
+        Span::new(span.start - self.start_pos, span.end - self.start_pos)
+    }
+
+    /// Line and column of the start of `span`
+    #[must_use]
+    pub fn line_col(&self, span: Span, encoding: Encoding) -> LineCol {
+        self.line_index.line_col(span.start - self.start_pos, encoding)
+    }
+
+    #[must_use]
+    pub fn text(&self, span: Span) -> &str {
+        self.to_local(span).source_text(&self.source)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file and reserves its range of offsets.
+    ///
+    /// # Panics
+    ///
+    /// If the files added so far are larger than 4 GiB in total.
+    pub fn add_file(&mut self, name: impl Into<String>, source: impl Into<String>) -> FileId {
+        let source = source.into();
+        // Leave a gap of one offset, so the end of a file is not the start of the next.
+        let start_pos = self
+            .files
+            .last()
+            .map_or(Some(0), |file| file.end_pos().checked_add(1))
+            .filter(|start_pos| {
+                u32::try_from(source.len())
+                    .ok()
+                    .and_then(|len| start_pos.checked_add(len))
+                    .is_some()
+            })
+            .expect("source map is larger than 4 GiB");
+        let id = FileId(u32::try_from(self.files.len()).unwrap());
+        let line_index = LineIndex::new(&source);
+        self.files.push(SourceFile { id, name: name.into(), source, start_pos, line_index });
+        id
+    }
+
+    /// # Panics
+    ///
+    /// If `id` is from another source map.
+    #[must_use]
+    pub fn file(&self, id: FileId) -> &SourceFile {
+        &self.files[id.index()]
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = &SourceFile> {
+        self.files.iter()
+    }
+
+    /// The file that contains `offset`
+    #[must_use]
+    pub fn lookup_file(&self, offset: u32) -> Option<&SourceFile> {
+        let index = self.files.partition_point(|file| file.start_pos <= offset).checked_sub(1)?;
+        let file = &self.files[index];
+        (offset <= file.end_pos()).then_some(file)
+    }
+
+    /// The file `span` belongs to, with the span relative to that file's source.
+    #[must_use]
+    pub fn lookup(&self, span: Span) -> Option<(&SourceFile, Span)> {
+        let file = self.lookup_file(span.start)?;
+        (span.end <= file.end_pos()).then(|| (file, file.to_local(span)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SourceMap;
+    use crate::{
+        line_index::{Encoding, LineCol},
+        Span,
+    };
+
+    #[test]
+    fn lookup() {
+        let mut source_map = SourceMap::new();
+        let main = source_map.add_file("main.scss", "@use 'a';\n.b { c: d }");
+        let partial = source_map.add_file("_a.scss", "$x: 1;\n$y: 2;");
+
+        let base = source_map.file(partial).start_pos();
+        let span = Span::new(7, 13).shift(base);
+        let (file, local) = source_map.lookup(span).unwrap();
+        assert_eq!(file.id(), partial);
+        assert_eq!(file.name(), "_a.scss");
+        assert_eq!(local, Span::new(7, 13));
+        assert_eq!(file.text(span), "$y: 2;");
+        assert_eq!(file.line_col(span, Encoding::Utf8), LineCol { line: 1, col: 0 });
+
+        let (file, _) = source_map.lookup(Span::new(10, 12)).unwrap();
+        assert_eq!(file.id(), main);
+        assert_eq!(source_map.lookup_file(source_map.file(main).end_pos()).unwrap().id(), main);
+        assert!(source_map.lookup(Span::new(base + 10, base + 20)).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "source map is larger than 4 GiB")]
+    fn no_offsets_left() {
+        let mut source_map = SourceMap::new();
+        source_map.add_file("a.scss", "");
+        // A file ending at the last offset leaves no room for the gap before the next one.
+        source_map.files[0].start_pos = u32::MAX;
+        source_map.add_file("b.scss", "");
+    }
+}
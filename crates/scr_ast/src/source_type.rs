@@ -19,6 +19,33 @@ impl Default for SourceType {
 }
 
 impl SourceType {
+    #[must_use]
+    pub const fn new(syntax: Syntax) -> Self {
+        Self { syntax }
+    }
+
+    #[must_use]
+    pub const fn syntax(self) -> Syntax {
+        self.syntax
+    }
+
+    /// The indented syntax
+    #[must_use]
+    pub const fn is_sass(self) -> bool {
+        matches!(self.syntax, Syntax::Sass)
+    }
+
+    #[must_use]
+    pub const fn is_scss(self) -> bool {
+        matches!(self.syntax, Syntax::Scss)
+    }
+
+    #[must_use]
+    pub const fn is_css(self) -> bool {
+        matches!(self.syntax, Syntax::Css)
+    }
+
+    #[must_use]
     pub fn from_path<P: AsRef<Path>>(&self, path: P) -> Self {
         let syntax = match path.as_ref().extension() {
             Some(ext) if ext == "css" => Syntax::Css,
@@ -0,0 +1,90 @@
+use std::ops::Range;
+
+use miette::{SourceOffset, SourceSpan};
+use scr_allocator::{Allocator, CloneIn};
+use serde::Serialize;
+
+/// A byte range in the source.
+///
+/// Offsets are `u32` to keep nodes small. When files are registered in a [`crate::SourceMap`],
+/// each file occupies its own range of offsets, so a span also identifies the file it came from.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct Span {
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Span of nodes that don't come from the source, such as those made by [`crate::AstBuilder`]
+pub const SPAN: Span = Span::new(0, 0);
+
+impl Span {
+    #[must_use]
+    pub const fn new(start: u32, end: u32) -> Self {
+        Self { start, end }
+    }
+
+    /// An empty span at `offset`
+    #[must_use]
+    pub const fn empty(offset: u32) -> Self {
+        Self { start: offset, end: offset }
+    }
+
+    #[must_use]
+    pub const fn len(self) -> u32 {
+        self.end - self.start
+    }
+
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.start == self.end
+    }
+
+    #[must_use]
+    pub const fn contains(self, offset: u32) -> bool {
+        self.start <= offset && offset < self.end
+    }
+
+    /// The smallest span covering both `self` and `other`
+    #[must_use]
+    pub fn merge(self, other: Self) -> Self {
+        Self { start: self.start.min(other.start), end: self.end.max(other.end) }
+    }
+
+    /// Moves the span `offset` bytes forward.
+    #[must_use]
+    pub const fn shift(self, offset: u32) -> Self {
+        Self { start: self.start + offset, end: self.end + offset }
+    }
+
+    /// The text covered by this span, `source` must start at offset 0 of this span's space.
+    #[must_use]
+    pub fn source_text(self, source: &str) -> &str {
+        &source[self.start as usize..self.end as usize]
+    }
+}
+
+impl<'new> CloneIn<'new> for Span {
+    type Cloned = Self;
+
+    fn clone_in(&self, _allocator: &'new Allocator) -> Self {
+        *self
+    }
+}
+
+impl From<Range<u32>> for Span {
+    fn from(range: Range<u32>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+impl From<Span> for Range<usize> {
+    fn from(span: Span) -> Self {
+        span.start as usize..span.end as usize
+    }
+}
+
+impl From<Span> for SourceSpan {
+    fn from(span: Span) -> Self {
+        Self::new(SourceOffset::from(span.start as usize), SourceOffset::from(span.len() as usize))
+    }
+}
@@ -0,0 +1,28 @@
+//! Read-only AST traversal
+//!
+//! [`Visit`] has a `visit_*` method for every node type in [`crate::node`], plus one for every
+//! arena `Vec` of nodes, e.g. `visit_statements`. Each defaults to the matching function in
+//! [`walk`], which visits the node's children in source order. Override a method to act on a node,
+//! and call the walk function from it to keep descending.
+//!
+//! `enter_node` and `leave_node` are called around every struct node, for passes that care about
+//! the nesting of nodes rather than their types, like [`crate::ParentMap`].
+//!
+//! ```ignore
+//! struct CountVariables(usize);
+//!
+//! impl<'a> Visit<'a> for CountVariables {
+//!     fn visit_variable_expression(&mut self, it: &VariableExpression) {
+//!         self.0 += 1;
+//!         walk::walk_variable_expression(self, it);
+//!     }
+//! }
+//! ```
+
+#![allow(clippy::explicit_iter_loop, clippy::missing_const_for_fn, clippy::wildcard_imports)]
+
+use scr_allocator::Vec;
+
+use crate::{node::*, AstKind};
+
+include!(concat!(env!("OUT_DIR"), "/visit.rs"));
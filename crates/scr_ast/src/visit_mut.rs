@@ -0,0 +1,92 @@
+//! Mutable AST traversal
+//!
+//! The mutable counterpart of [`crate::Visit`]. A visitor can replace a node in place by assigning
+//! through the `&mut` it is given, and can remove, insert or replace children of an arena `Vec` by
+//! overriding the list method, e.g. `visit_statements`, before walking the remaining elements.
+//!
+//! ```ignore
+//! struct RemoveSilentComments;
+//!
+//! impl<'a> VisitMut<'a> for RemoveSilentComments {
+//!     fn visit_statements(&mut self, it: &mut Vec<'a, Statement<'a>>) {
+//!         it.retain(|stmt| !matches!(stmt, Statement::SilentComment(_)));
+//!         walk_mut::walk_statements(self, it);
+//!     }
+//! }
+//! ```
+
+#![allow(clippy::explicit_iter_loop, clippy::missing_const_for_fn, clippy::wildcard_imports)]
+
+use scr_allocator::Vec;
+
+use crate::node::*;
+
+include!(concat!(env!("OUT_DIR"), "/visit_mut.rs"));
+
+#[cfg(test)]
+mod test {
+    use scr_allocator::{Allocator, Box, Vec};
+
+    use crate::{node::*, walk_mut, Atom, NodeId, Span, VisitMut};
+
+    /// Removes silent comments and replaces `null` with `false`.
+    struct Rewrite<'a> {
+        allocator: &'a Allocator,
+    }
+
+    impl<'a> VisitMut<'a> for Rewrite<'a> {
+        fn visit_statements(&mut self, it: &mut Vec<'a, Statement<'a>>) {
+            it.retain(|stmt| !matches!(stmt, Statement::SilentComment(_)));
+            walk_mut::walk_statements(self, it);
+        }
+
+        fn visit_expression(&mut self, it: &mut Expression<'a>) {
+            if let Expression::Null(null) = it {
+                let span = null.span;
+                *it = Expression::Boolean(Box(self.allocator.alloc(BooleanLiteral {
+                    span,
+                    id: null.id,
+                    value: false,
+                })));
+            }
+            walk_mut::walk_expression(self, it);
+        }
+    }
+
+    fn variable<'a>(allocator: &'a Allocator, name: &str) -> Statement<'a> {
+        let value = Expression::Null(Box(
+            allocator.alloc(NullLiteral { span: Span::new(5, 9), id: NodeId::DUMMY })
+        ));
+        Statement::VariableDeclaration(Box(allocator.alloc(VariableDeclaration {
+            span: Span::new(0, 9),
+            id: NodeId::DUMMY,
+            namespace: None,
+            name: Atom::from(name),
+            value,
+            guarded: false,
+            global: false,
+            doc: None,
+        })))
+    }
+
+    #[test]
+    fn replace_and_remove() {
+        let allocator = Allocator::default();
+        let comment = Statement::SilentComment(Box(allocator.alloc(SilentComment {
+            span: Span::new(0, 5),
+            id: NodeId::DUMMY,
+            text: "// a",
+        })));
+        let mut stylesheet = Stylesheet {
+            span: Span::new(0, 20),
+            id: NodeId::DUMMY,
+            body: Vec::from_iter_in([comment, variable(&allocator, "a")], &allocator),
+        };
+
+        Rewrite { allocator: &allocator }.visit_stylesheet(&mut stylesheet);
+
+        assert_eq!(stylesheet.body.len(), 1);
+        let Statement::VariableDeclaration(decl) = &stylesheet.body[0] else { unreachable!() };
+        assert!(matches!(&decl.value, Expression::Boolean(b) if !b.value));
+    }
+}
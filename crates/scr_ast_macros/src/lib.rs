@@ -0,0 +1,235 @@
+//! Derive macros for `scr_ast`
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Variant};
+
+/// Derives `scr_ast::ContentEq`, comparing every field with `content_eq`.
+///
+/// Enum variants marked `#[content_eq(trivia)]` are skipped when comparing lists.
+///
+/// # Panics
+///
+/// On unions and on malformed `content_eq` attributes.
+#[proc_macro_derive(ContentEq, attributes(content_eq))]
+pub fn derive_content_eq(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let (body, trivia) = match &input.data {
+        Data::Struct(data) => {
+            let (this, bindings) = destructure(&quote!(Self), &data.fields, "a");
+            let (other, other_bindings) = destructure(&quote!(Self), &data.fields, "b");
+            (
+                quote! {
+                    let #this = self;
+                    let #other = other;
+                    true #(&& ::scr_ast::ContentEq::content_eq(#bindings, #other_bindings))*
+                },
+                quote!(false),
+            )
+        }
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let ident = &variant.ident;
+                let (this, bindings) = destructure(&quote!(Self::#ident), &variant.fields, "a");
+                let (other, other_bindings) =
+                    destructure(&quote!(Self::#ident), &variant.fields, "b");
+                quote! {
+                    (#this, #other) => true #(&& ::scr_ast::ContentEq::content_eq(#bindings, #other_bindings))*
+                }
+            });
+            let trivia = data.variants.iter().filter(|variant| is_trivia(variant)).map(|variant| {
+                let ident = &variant.ident;
+                quote!(Self::#ident { .. })
+            });
+            let trivia = trivia.collect::<Vec<_>>();
+            let trivia = if trivia.is_empty() {
+                quote!(false)
+            } else {
+                quote!(matches!(self, #(#trivia)|*))
+            };
+            (
+                quote! {
+                    #[allow(unreachable_patterns)]
+                    match (self, other) {
+                        #(#arms,)*
+                        _ => false,
+                    }
+                },
+                trivia,
+            )
+        }
+        Data::Union(_) => panic!("ContentEq cannot be derived for unions"),
+    };
+
+    quote! {
+        impl #impl_generics ::scr_ast::ContentEq for #name #ty_generics #where_clause {
+            fn content_eq(&self, other: &Self) -> bool {
+                #body
+            }
+
+            fn is_trivia(&self) -> bool {
+                #trivia
+            }
+        }
+    }
+    .into()
+}
+
+/// Derives `scr_ast::ContentHash`, hashing every field with `content_hash`.
+///
+/// # Panics
+///
+/// On unions.
+#[proc_macro_derive(ContentHash)]
+pub fn derive_content_hash(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let (this, bindings) = destructure(&quote!(Self), &data.fields, "a");
+            quote! {
+                let #this = self;
+                #(::scr_ast::ContentHash::content_hash(#bindings, state);)*
+            }
+        }
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let ident = &variant.ident;
+                let (this, bindings) = destructure(&quote!(Self::#ident), &variant.fields, "a");
+                quote! {
+                    #this => {
+                        #(::scr_ast::ContentHash::content_hash(#bindings, state);)*
+                    }
+                }
+            });
+            quote! {
+                ::std::hash::Hash::hash(&::std::mem::discriminant(self), state);
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => panic!("ContentHash cannot be derived for unions"),
+    };
+
+    quote! {
+        impl #impl_generics ::scr_ast::ContentHash for #name #ty_generics #where_clause {
+            fn content_hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+                #body
+            }
+        }
+    }
+    .into()
+}
+
+/// Derives `scr_allocator::CloneIn`, cloning every field into the new arena.
+///
+/// # Panics
+///
+/// On unions, and on types with type parameters or more than one lifetime.
+#[proc_macro_derive(CloneIn)]
+pub fn derive_clone_in(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let generics = &input.generics;
+    assert!(
+        generics.type_params().next().is_none() && generics.lifetimes().count() <= 1,
+        "CloneIn can only be derived for types with at most one lifetime"
+    );
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let (impl_generics, cloned) = generics.lifetimes().next().map_or_else(
+        || (quote!(<'new_alloc>), quote!(#name)),
+        |lifetime| {
+            let lifetime = &lifetime.lifetime;
+            (quote!(<#lifetime, 'new_alloc>), quote!(#name<'new_alloc>))
+        },
+    );
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let (this, bindings) = destructure(&quote!(Self), &data.fields, "a");
+            let construct = construct(&quote!(#name), &data.fields, &bindings);
+            quote! {
+                let #this = self;
+                #construct
+            }
+        }
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let ident = &variant.ident;
+                let (this, bindings) = destructure(&quote!(Self::#ident), &variant.fields, "a");
+                let construct = construct(&quote!(#name::#ident), &variant.fields, &bindings);
+                quote!(#this => #construct)
+            });
+            quote! {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+        Data::Union(_) => panic!("CloneIn cannot be derived for unions"),
+    };
+
+    quote! {
+        impl #impl_generics ::scr_allocator::CloneIn<'new_alloc> for #name #ty_generics {
+            type Cloned = #cloned;
+
+            fn clone_in(&self, allocator: &'new_alloc ::scr_allocator::Allocator) -> Self::Cloned {
+                #body
+            }
+        }
+    }
+    .into()
+}
+
+/// Builds `path` from the clones of `bindings` in the new arena.
+fn construct(path: &TokenStream2, fields: &Fields, bindings: &[Ident]) -> TokenStream2 {
+    let values = bindings
+        .iter()
+        .map(|binding| quote!(::scr_allocator::CloneIn::clone_in(#binding, allocator)));
+    match fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| field.ident.as_ref().unwrap());
+            quote!(#path { #(#names: #values),* })
+        }
+        Fields::Unnamed(_) => quote!(#path(#(#values),*)),
+        Fields::Unit => quote!(#path),
+    }
+}
+
+/// A pattern binding every field of `path` to `{prefix}_{index}`, and the bindings.
+fn destructure(path: &TokenStream2, fields: &Fields, prefix: &str) -> (TokenStream2, Vec<Ident>) {
+    let bindings =
+        (0..fields.len()).map(|index| format_ident!("{prefix}_{index}")).collect::<Vec<_>>();
+    let pattern = match fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| field.ident.as_ref().unwrap());
+            quote!(#path { #(#names: #bindings),* })
+        }
+        Fields::Unnamed(_) => quote!(#path(#(#bindings),*)),
+        Fields::Unit => quote!(#path),
+    };
+    (pattern, bindings)
+}
+
+fn is_trivia(variant: &Variant) -> bool {
+    variant.attrs.iter().filter(|attr| attr.path().is_ident("content_eq")).any(|attr| {
+        let mut trivia = false;
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("trivia") {
+                trivia = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `trivia`"))
+            }
+        })
+        .unwrap();
+        trivia
+    })
+}
@@ -35,6 +35,134 @@ pub enum Diagnostic {
     #[error("Expected escape sequence.")]
     ExpectedEscapeSequence(#[label("Expected escape sequence")] Span),
 
+    #[error("Mixins may not contain mixin declarations.")]
+    MixinInMixin(#[label("Mixin declared here")] Span),
+
+    #[error("Mixins may not contain function declarations.")]
+    FunctionInMixin(#[label("Function declared here")] Span),
+
+    #[error("Mixins may not be declared in control directives.")]
+    MixinInControlDirective(#[label("Mixin declared here")] Span),
+
+    #[error("Functions may not be declared in control directives.")]
+    FunctionInControlDirective(#[label("Function declared here")] Span),
+
+    #[error("@function rules may not contain {0}.")]
+    InvalidFunctionChild(&'static str, #[label("Not allowed in a function")] Span),
+
+    #[error("@return may only be used within a function.")]
+    ReturnOutsideFunction(#[label("Not inside a function")] Span),
+
+    #[error("@content is only allowed within mixin declarations.")]
+    ContentOutsideMixin(#[label("Not inside a mixin")] Span),
+
+    #[error("Declarations may only be used within style rules.")]
+    DeclarationOutsideStyleRule(#[label("Not inside a style rule")] Span),
+
+    #[error("Only declarations are allowed here.")]
+    OnlyDeclarations(#[label("Not a declaration")] Span),
+
+    #[error("Expected \"to\", \"from\" or a percentage.")]
+    InvalidKeyframeSelector(#[label("Not a keyframe selector")] Span),
+
+    #[error("This at-rule is not allowed here.")]
+    DisallowedAtRule(#[label("Not allowed here")] Span),
+
+    #[error("{0} aren't allowed in plain CSS.")]
+    NotInPlainCss(&'static str, #[label("Not allowed in plain CSS")] Span),
+
+    #[error("{0} isn't allowed in plain CSS.")]
+    IsNotInPlainCss(&'static str, #[label("Not allowed in plain CSS")] Span),
+
+    #[error("Parent selectors can't have suffixes in plain CSS.")]
+    ParentSuffixInPlainCss(#[label("Suffix")] Span),
+
+    #[error("expected \"{0}\".")]
+    ExpectedChar(char, #[label("Expected `{0}`")] Span),
+
+    #[error("expected \"{0}\".")]
+    ExpectedKeyword(&'static str, #[label("Expected `{0}`")] Span),
+
+    #[error("Expected {0}.")]
+    Expected(&'static str, #[label("Expected {0}")] Span),
+
+    #[error("unmatched \"}}\".")]
+    UnmatchedCurly(#[label("No block to close")] Span),
+
+    #[error("Invalid flag name.")]
+    InvalidFlag(#[label("Unknown flag")] Span),
+
+    #[error("!global isn't allowed for variables in other modules.")]
+    GlobalInOtherModule(#[label("Declared in another module")] Span),
+
+    #[error("Duplicate argument.")]
+    DuplicateArgument(#[label("Already passed")] Span),
+
+    #[error("Duplicate parameter.")]
+    DuplicateParameter(#[label("Already declared")] Span),
+
+    #[error("Positional arguments must come before keyword arguments.")]
+    PositionalAfterNamed(#[label("Positional argument")] Span),
+
+    #[error("Invalid function name.")]
+    InvalidFunctionName(#[label("Reserved name")] Span),
+
+    #[error("@use rules must be written before any other rules.")]
+    UseAfterRule(#[label("Too late for @use")] Span),
+
+    #[error("@extend may only be used within style rules.")]
+    ExtendOutsideStyleRule(#[label("Not inside a style rule")] Span),
+
+    #[error("The same variable may only be configured once.")]
+    DuplicateConfiguration(#[label("Already configured")] Span),
+
+    #[error("The default namespace \"{0}\" is not a valid Sass identifier.")]
+    #[diagnostic(help("Add an \"as\" clause to define an explicit namespace."))]
+    InvalidDefaultNamespace(String, #[label("Invalid namespace")] Span),
+
+    #[error("Declarations whose names begin with \"--\" may not be nested.")]
+    NestedCustomProperty(#[label("Custom property")] Span),
+
+    #[error("Nothing may be indented here.")]
+    NothingMayBeIndented(#[label("Unexpected indentation")] Span),
+
+    #[error("Inconsistent indentation, expected {0} spaces.")]
+    InconsistentIndentation(u32, #[label("Indented differently from the line above")] Span),
+
+    #[error("Tabs and spaces may not be mixed.")]
+    MixedIndentation(#[label("Both tabs and spaces")] Span),
+
+    #[error("Expected {0}, was {1}.")]
+    IndentationCharacter(&'static str, &'static str, #[label("Expected {0}")] Span),
+
+    #[error("Indenting at the beginning of the document is illegal.")]
+    IndentedDocument(#[label("Indented")] Span),
+
+    #[error("Multiple statements on one line are not supported in the indented syntax.")]
+    MultipleStatementsOnOneLine(#[label("Another statement")] Span),
+
+    #[error("Unexpected text after end of comment.")]
+    TextAfterComment(#[label("Expected a newline")] Span),
+
+    #[error("Malformed @{0} annotation: {1}.")]
+    #[diagnostic(severity(Warning))]
+    MalformedDocAnnotation(&'static str, &'static str, #[label("In this annotation")] Span),
+
+    #[error("{0} and {1} have incompatible units{2}.")]
+    IncompatibleUnits(String, String, &'static str, #[label("Can't be converted")] Span),
+
+    #[error("Expected {0} to have {1}.")]
+    ExpectedUnits(String, String, #[label("Has other units")] Span),
+
+    #[error("{0} is not an int.")]
+    NotAnInt(String, #[label("Not an int")] Span),
+
+    #[error("Nesting is too deep.")]
+    NestingTooDeep(#[label("Nested too deeply")] Span),
+
+    #[error("Too many errors, the rest of the file was skipped.")]
+    TooManyErrors,
+
     #[error("Syntax Error")]
     #[diagnostic()]
     ExceptedToken,
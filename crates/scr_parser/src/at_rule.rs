@@ -0,0 +1,350 @@
+//! CSS at-rules that Sass passes through, parsed into structured nodes
+//!
+//! Their preludes may still be interpolated. Each at-rule's body is either a list of rules, like
+//! `@media`, or holds only declarations, like `@font-face`.
+
+use scr_allocator::Vec;
+use scr_ast::{
+    context::{Context, WithContext},
+    node::{Interpolation, MediaCondition, PageSelector, SelectorList, Statement},
+    Atom,
+};
+use scr_diagnostics::{Diagnostic, Result};
+
+use crate::{interpolation::InterpolationBuffer, scss::ChildParser, statement::unvendor, Parser};
+
+/// The margin boxes of a page, which are at-rules inside `@page`
+const PAGE_MARGINS: [&str; 16] = [
+    "top-left-corner",
+    "top-left",
+    "top-center",
+    "top-right",
+    "top-right-corner",
+    "bottom-left-corner",
+    "bottom-left",
+    "bottom-center",
+    "bottom-right",
+    "bottom-right-corner",
+    "left-top",
+    "left-middle",
+    "left-bottom",
+    "right-top",
+    "right-middle",
+    "right-bottom",
+];
+
+impl<'a> Parser<'a> {
+    /// An at-rule that isn't Sass's, with `child` parsing the children of unknown at-rules
+    pub(crate) fn css_at_rule(
+        &mut self,
+        start: u32,
+        name: Interpolation<'a>,
+        child: ChildParser<'a>,
+    ) -> Result<Statement<'a>> {
+        let lowercase = name.as_plain().map(str::to_ascii_lowercase);
+        match lowercase.as_deref() {
+            Some("container") => self.container_rule(start),
+            Some("counter-style") => self.counter_style_rule(start),
+            Some("font-feature-values") => self.font_feature_values_rule(start),
+            Some(name) if unvendor(name) == "keyframes" => {
+                let prefix = &name[..name.len() - "keyframes".len()];
+                let vendor_prefix = (!prefix.is_empty()).then(|| Atom::from(prefix));
+                self.keyframes_rule(start, vendor_prefix, child)
+            }
+            Some("layer") => self.layer_rule(start),
+            Some("media") => self.media_rule(start),
+            Some("page") => self.page_rule(start),
+            Some(name) if self.context.in_page() && PAGE_MARGINS.contains(&name) => {
+                let name = Atom::from(name);
+                let body = self.without_context(Context::PAGE, Self::declaration_body)?;
+                Ok(self.ast.statement_page_margin(self.span_from(start), name, body))
+            }
+            Some("property") => self.property_rule(start),
+            Some("scope") => self.scope_rule(start),
+            Some("starting-style") => {
+                let body = self.children(Self::statement)?;
+                Ok(self.ast.statement_starting_style(self.span_from(start), body))
+            }
+            Some("supports") => self.supports_rule(start),
+            _ => self.unknown_at_rule(start, name, child),
+        }
+    }
+
+    /// `@container [name] [condition] { ... }`
+    fn container_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        let name = if self.looking_at_container_name() {
+            let name = self.interpolated_identifier()?;
+            self.whitespace()?;
+            Some(name)
+        } else {
+            None
+        };
+        let condition = if self.looking_at_children() {
+            None
+        } else {
+            let condition = self.media_condition(true, Self::container_in_parens)?;
+            self.whitespace()?;
+            Some(condition)
+        };
+        let body = self.children(Self::statement)?;
+        Ok(self.ast.statement_container(self.span_from(start), name, condition, body))
+    }
+
+    /// Whether a container name is next, as opposed to a condition such as `not (...)` or
+    /// `style(...)`
+    fn looking_at_container_name(&mut self) -> bool {
+        if !self.looking_at_interpolated_identifier() {
+            return false;
+        }
+        let checkpoint = self.checkpoint();
+        let found = self.interpolated_identifier().is_ok_and(|name| {
+            name.as_plain().is_none_or(|name| {
+                !["not", "and", "or"].iter().any(|keyword| name.eq_ignore_ascii_case(keyword))
+            })
+        }) && self.peek() != '(';
+        self.rewind(checkpoint);
+        found
+    }
+
+    /// A size feature, a style query or a nested condition in `@container`
+    fn container_in_parens(&mut self) -> Result<MediaCondition<'a>> {
+        let start = self.offset();
+        if self.scan_function_name("style") {
+            return self.style_query(start);
+        }
+        let checkpoint = self.checkpoint();
+        if self.next_eq('(') {
+            self.whitespace()?;
+            if self.looking_at_nested_container_condition() {
+                let condition = self.media_condition(true, Self::container_in_parens)?;
+                self.whitespace()?;
+                self.expect_char(')')?;
+                return Ok(condition);
+            }
+        }
+        self.rewind(checkpoint);
+        self.media_in_parens()
+    }
+
+    fn looking_at_nested_container_condition(&mut self) -> bool {
+        if self.peek() == '(' || self.looking_at_negated_condition() {
+            return true;
+        }
+        let checkpoint = self.checkpoint();
+        let found = if self.scan_identifier("not") {
+            self.whitespace().is_ok() && self.scan_function_name("style")
+        } else {
+            self.scan_function_name("style")
+        };
+        self.rewind(checkpoint);
+        found
+    }
+
+    /// Consumes `name(` if it's next.
+    fn scan_function_name(&mut self, name: &str) -> bool {
+        let checkpoint = self.checkpoint();
+        if self.scan_identifier(name) && self.next_eq('(') {
+            return true;
+        }
+        self.rewind(checkpoint);
+        false
+    }
+
+    /// The rest of `style(...)` once `style(` is consumed
+    fn style_query(&mut self, start: u32) -> Result<MediaCondition<'a>> {
+        self.whitespace()?;
+        let condition = if self.peek() == '(' || self.looking_at_negated_condition() {
+            self.media_condition(true, Self::style_in_parens)?
+        } else {
+            self.style_feature()?
+        };
+        self.whitespace()?;
+        self.expect_char(')')?;
+        Ok(self.ast.media_condition_style(self.span_from(start), condition))
+    }
+
+    /// `(--name: value)` or a nested condition in `style(...)`
+    fn style_in_parens(&mut self) -> Result<MediaCondition<'a>> {
+        self.expect_char('(')?;
+        self.whitespace()?;
+        let condition = if self.peek() == '(' || self.looking_at_negated_condition() {
+            self.media_condition(true, Self::style_in_parens)?
+        } else {
+            self.style_feature()?
+        };
+        self.whitespace()?;
+        self.expect_char(')')?;
+        Ok(condition)
+    }
+
+    /// `--name[: value]`, whose value is kept as text like a custom property's
+    fn style_feature(&mut self) -> Result<MediaCondition<'a>> {
+        let start = self.offset();
+        let name = self.interpolated_identifier()?;
+        self.whitespace()?;
+        let value = if self.next_eq(':') {
+            self.whitespace()?;
+            let value = self.interpolated_declaration_value(false)?;
+            Some(self.ast.expression_string(value.span, value, false))
+        } else {
+            None
+        };
+        Ok(self.ast.media_condition_feature(self.span_from(start), name, value))
+    }
+
+    /// `@counter-style name { ... }`
+    fn counter_style_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        let name = self.interpolated_identifier()?;
+        self.whitespace()?;
+        let body = self.declaration_body()?;
+        Ok(self.ast.statement_counter_style(self.span_from(start), name, body))
+    }
+
+    /// `@font-feature-values family, ... { ... }`
+    fn font_feature_values_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        let families = self.expression()?;
+        self.whitespace()?;
+        let body = self.declaration_body()?;
+        Ok(self.ast.statement_font_feature_values(self.span_from(start), families, body))
+    }
+
+    /// `@keyframes name { ... }`, optionally vendor-prefixed
+    fn keyframes_rule(
+        &mut self,
+        start: u32,
+        vendor_prefix: Option<Atom>,
+        child: ChildParser<'a>,
+    ) -> Result<Statement<'a>> {
+        let name = if matches!(self.peek(), '"' | '\'') {
+            let name_start = self.offset();
+            self.string()?;
+            self.ast.plain_interpolation(self.span_from(name_start), self.text_from(name_start))
+        } else {
+            self.interpolated_identifier()?
+        };
+        self.whitespace()?;
+        let body = self.with_context(Context::KEYFRAMES, |p| p.children(child))?;
+        Ok(self.ast.statement_keyframes(self.span_from(start), vendor_prefix, name, body))
+    }
+
+    /// `@layer a, b.c;` or `@layer [name] { ... }`
+    fn layer_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        let mut names = self.ast.new_vec();
+        if !self.looking_at_children() {
+            loop {
+                names.push(self.layer_name()?);
+                self.whitespace()?;
+                if !self.next_eq(',') {
+                    break;
+                }
+                self.whitespace()?;
+            }
+        }
+        if names.len() <= 1 && self.looking_at_children() {
+            let body = self.children(Self::statement)?;
+            return Ok(self.ast.statement_layer(self.span_from(start), names, Some(body)));
+        }
+        let span = self.span_from(start);
+        self.expect_statement_separator()?;
+        Ok(self.ast.statement_layer(span, names, None))
+    }
+
+    /// `a.b.c`
+    fn layer_name(&mut self) -> Result<Interpolation<'a>> {
+        let start = self.offset();
+        let mut buffer = InterpolationBuffer::new(&self.ast, start);
+        loop {
+            let segment = self.interpolated_identifier()?;
+            buffer.add_interpolation(&self.ast, segment);
+            if self.peek() != '.' || !self.looking_at_interpolated_identifier_at(1) {
+                break;
+            }
+            buffer.push(self.bump());
+        }
+        Ok(buffer.finish(&self.ast, self.offset()))
+    }
+
+    /// `@page [name][:pseudo], ... { ... }`
+    fn page_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        let mut selectors = self.ast.new_vec();
+        if !self.looking_at_children() {
+            loop {
+                selectors.push(self.page_selector()?);
+                self.whitespace()?;
+                if !self.next_eq(',') {
+                    break;
+                }
+                self.whitespace()?;
+            }
+        }
+        let body = self.with_context(Context::PAGE, Self::declaration_body)?;
+        Ok(self.ast.statement_page(self.span_from(start), selectors, body))
+    }
+
+    fn page_selector(&mut self) -> Result<PageSelector<'a>> {
+        let start = self.offset();
+        let name = if self.peek() == ':' { None } else { Some(self.interpolated_identifier()?) };
+        let mut pseudo_classes = self.ast.new_vec();
+        while self.next_eq(':') {
+            pseudo_classes.push(self.interpolated_identifier()?);
+        }
+        Ok(self.ast.page_selector(self.span_from(start), name, pseudo_classes))
+    }
+
+    /// `@property --name { ... }`
+    fn property_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        let name_start = self.offset();
+        let name = self.interpolated_identifier()?;
+        if name.as_plain().is_some_and(|name| !name.starts_with("--")) {
+            return Err(Diagnostic::Expected("custom property name", self.span_from(name_start)));
+        }
+        self.whitespace()?;
+        let body = self.declaration_body()?;
+        Ok(self.ast.statement_property(self.span_from(start), name, body))
+    }
+
+    /// `@scope [(root)] [to (limit)] { ... }`
+    fn scope_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        let root = if self.peek() == '(' { Some(self.scope_selector()?) } else { None };
+        let limit = if self.scan_identifier("to") {
+            self.whitespace()?;
+            Some(self.scope_selector()?)
+        } else {
+            None
+        };
+        let body = self.children(Self::statement)?;
+        Ok(self.ast.statement_scope(self.span_from(start), root, limit, body))
+    }
+
+    /// `(selector)`
+    fn scope_selector(&mut self) -> Result<SelectorList<'a>> {
+        self.expect_char('(')?;
+        self.whitespace()?;
+        let selector = self.selector_list()?;
+        self.whitespace()?;
+        self.expect_char(')')?;
+        self.whitespace()?;
+        Ok(selector)
+    }
+
+    /// The body of an at-rule that holds only declarations, and at-rules such as `@include`
+    fn declaration_body(&mut self) -> Result<Vec<'a, Statement<'a>>> {
+        self.with_context(Context::DECLARATION_AT_RULE, |p| {
+            p.children(Self::declaration_only_child)
+        })
+    }
+
+    fn declaration_only_child(&mut self) -> Result<Statement<'a>> {
+        if self.peek() == '@' {
+            return self.at_rule(Self::declaration_only_child);
+        }
+        let start = self.offset();
+        let checkpoint = self.checkpoint();
+        if let Some(declaration) = self.try_declaration()? {
+            return Ok(declaration);
+        }
+        self.rewind(checkpoint);
+        self.style_rule()?;
+        Err(Diagnostic::OnlyDeclarations(self.span_from(start)))
+    }
+}
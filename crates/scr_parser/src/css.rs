@@ -0,0 +1,221 @@
+//! Plain CSS, like Dart Sass's `CssParser`
+//!
+//! Plain CSS is parsed as SCSS with the Sass-only features rejected, and with function calls
+//! left for the browser unless Sass has a CSS-compatible version of them.
+
+use scr_ast::{
+    node::{ArgumentList, BinaryOperator, Expression, Interpolation, Statement},
+    Atom, Span,
+};
+use scr_diagnostics::{Diagnostic, Result};
+
+use crate::{scss::ChildParser, Parser};
+
+/// At-rules that only exist in Sass
+const SASS_AT_RULES: [&str; 14] = [
+    "at-root", "content", "debug", "each", "error", "extend", "for", "function", "if", "include",
+    "mixin", "return", "warn", "while",
+];
+
+/// Global Sass functions, except those that are also CSS functions such as `rgb()` and `min()`
+const SASS_FUNCTIONS: [&str; 71] = [
+    "adjust-color",
+    "adjust-hue",
+    "append",
+    "blue",
+    "call",
+    "ceil",
+    "change-color",
+    "comparable",
+    "complement",
+    "content-exists",
+    "darken",
+    "desaturate",
+    "fade-in",
+    "fade-out",
+    "feature-exists",
+    "floor",
+    "function-exists",
+    "get-function",
+    "global-variable-exists",
+    "green",
+    "hue",
+    "ie-hex-str",
+    "if",
+    "index",
+    "inspect",
+    "is-bracketed",
+    "is-superselector",
+    "join",
+    "keywords",
+    "length",
+    "lighten",
+    "lightness",
+    "list-separator",
+    "map-get",
+    "map-has-key",
+    "map-keys",
+    "map-merge",
+    "map-remove",
+    "map-values",
+    "mix",
+    "mixin-exists",
+    "nth",
+    "opacify",
+    "percentage",
+    "quote",
+    "random",
+    "red",
+    "saturation",
+    "scale-color",
+    "selector-append",
+    "selector-extend",
+    "selector-nest",
+    "selector-parse",
+    "selector-replace",
+    "selector-unify",
+    "set-nth",
+    "simple-selectors",
+    "str-index",
+    "str-insert",
+    "str-length",
+    "str-slice",
+    "to-lower-case",
+    "to-upper-case",
+    "transparentize",
+    "type-of",
+    "unique-id",
+    "unit",
+    "unitless",
+    "unquote",
+    "variable-exists",
+    "zip",
+];
+
+/// Sass functions that plain CSS may call, since CSS has functions of the same name
+const CSS_COMPATIBLE_FUNCTIONS: [&str; 13] = [
+    "rgb",
+    "rgba",
+    "hsl",
+    "hsla",
+    "grayscale",
+    "invert",
+    "alpha",
+    "opacity",
+    "saturate",
+    "min",
+    "max",
+    "round",
+    "abs",
+];
+
+impl<'a> Parser<'a> {
+    /// An at-rule in plain CSS, where Sass's at-rules are errors and `@import` is always a CSS
+    /// import
+    pub(crate) fn plain_css_at_rule(
+        &mut self,
+        start: u32,
+        name: Interpolation<'a>,
+        child: ChildParser<'a>,
+    ) -> Result<Statement<'a>> {
+        match name.as_plain() {
+            Some(name) if SASS_AT_RULES.contains(&name) => {
+                self.almost_any_value()?;
+                Err(Diagnostic::IsNotInPlainCss("This at-rule", self.span_from(start)))
+            }
+            Some(name) if name.eq_ignore_ascii_case("import") => self.css_import_rule(start),
+            _ => self.css_at_rule(start, name, child),
+        }
+    }
+
+    /// `@import url(...) modifiers;` or `@import "url" modifiers;`, which is left for the browser
+    fn css_import_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        let import = if matches!(self.peek(), 'u' | 'U') {
+            self.url_import()?
+        } else {
+            let url_start = self.offset();
+            self.string()?;
+            let url =
+                self.ast.plain_interpolation(self.span_from(url_start), self.text_from(url_start));
+            let modifiers = self.try_import_modifiers()?;
+            self.ast.import_static(self.span_from(url_start), url, modifiers)
+        };
+        let span = self.span_from(start);
+        self.whitespace()?;
+        self.expect_statement_separator()?;
+        Ok(self.ast.statement_import(span, self.ast.vec_from_iter([import])))
+    }
+
+    /// An identifier or a function call in plain CSS
+    ///
+    /// Keywords such as `null` and `not` are plain identifiers, and calls are left for the
+    /// browser unless they're to one of [`CSS_COMPATIBLE_FUNCTIONS`].
+    pub(crate) fn plain_css_identifier_like(&mut self) -> Result<Expression<'a>> {
+        let start = self.offset();
+        let identifier = self.interpolated_identifier()?;
+        let Some(plain) = identifier.as_plain() else {
+            return Ok(self.ast.expression_string(identifier.span, identifier, false));
+        };
+        if let Some(special) = self.try_special_function(plain, start)? {
+            return Ok(special);
+        }
+        if self.peek() == '.' && self.nth_char(1) != '.' {
+            let expression = self.namespaced_expression(Atom::from(plain), start)?;
+            return Err(Diagnostic::NotInPlainCss("Module namespaces", expression.span()));
+        }
+        if self.peek() != '(' {
+            return Ok(self.ast.expression_string(identifier.span, identifier, false));
+        }
+
+        let arguments = self.plain_css_arguments(plain.eq_ignore_ascii_case("var"))?;
+        let span = self.span_from(start);
+        if SASS_FUNCTIONS.contains(&plain) {
+            return Err(Diagnostic::IsNotInPlainCss("This function", span));
+        }
+        if CSS_COMPATIBLE_FUNCTIONS.contains(&plain) {
+            let name = Atom::from(plain);
+            return Ok(self.ast.expression_function(span, None, name, arguments));
+        }
+        Ok(self.ast.expression_interpolated_function(span, identifier, arguments))
+    }
+
+    /// `(a, b)`, with only positional arguments, where `var(--a,)` keeps its empty fallback
+    fn plain_css_arguments(&mut self, allow_empty_second: bool) -> Result<ArgumentList<'a>> {
+        let start = self.offset();
+        self.expect_char('(')?;
+        let mut positional = self.ast.new_vec();
+        if !self.next_eq(')') {
+            loop {
+                self.whitespace()?;
+                if allow_empty_second && positional.len() == 1 && self.peek() == ')' {
+                    let span = Span::empty(self.offset());
+                    let empty = self.ast.plain_interpolation(span, "");
+                    positional.push(self.ast.expression_string(span, empty, false));
+                    self.bump();
+                    break;
+                }
+                positional.push(self.function_argument()?);
+                self.whitespace()?;
+                if !self.next_eq(',') {
+                    self.expect_char(')')?;
+                    break;
+                }
+            }
+        }
+        let span = self.span_from(start);
+        Ok(self.ast.argument_list(span, positional, self.ast.new_vec(), None, None))
+    }
+}
+
+/// Whether plain CSS may use `operator`, which it can in calculations such as `calc(1px + 2%)`
+/// and in IE's `alpha(opacity=50)`
+pub const fn allowed_in_plain_css(operator: BinaryOperator) -> bool {
+    matches!(
+        operator,
+        BinaryOperator::SingleEquals
+            | BinaryOperator::Plus
+            | BinaryOperator::Minus
+            | BinaryOperator::Times
+            | BinaryOperator::DividedBy
+    )
+}
@@ -0,0 +1,365 @@
+//! Character-level helpers shared by every part of the parser, like Dart Sass's `Parser` class
+
+use scr_ast::Span;
+use scr_diagnostics::{Diagnostic, Result};
+
+use crate::{
+    lexer::{
+        definitions::{
+            is_dight, is_hex_digit, is_ident, is_ident_start, is_newline, is_whitespace, EOF_CHAR,
+        },
+        LexerCheckpoint,
+    },
+    Parser,
+};
+
+/// The largest code point, escapes beyond it are replaced
+const MAX_CODE_POINT: u32 = 0x0010_ffff;
+
+impl<'a> Parser<'a> {
+    #[inline]
+    pub(crate) fn peek(&self) -> char {
+        self.lexer.peek()
+    }
+
+    #[inline]
+    pub(crate) fn nth_char(&self, n: usize) -> char {
+        self.lexer.nth_char(n)
+    }
+
+    #[inline]
+    pub(crate) fn bump(&mut self) -> char {
+        self.lexer.bump().unwrap_or(EOF_CHAR)
+    }
+
+    /// Consumes `c` if it's next.
+    #[inline]
+    pub(crate) fn next_eq(&mut self, c: char) -> bool {
+        self.lexer.next_eq(c)
+    }
+
+    #[inline]
+    pub(crate) fn offset(&self) -> u32 {
+        self.base_offset + self.lexer.offset()
+    }
+
+    #[inline]
+    pub(crate) fn is_eof(&self) -> bool {
+        self.lexer.is_eof()
+    }
+
+    pub(crate) fn span_from(&self, start: u32) -> Span {
+        Span::new(start, self.offset())
+    }
+
+    /// The source text between `start` and the current position
+    pub(crate) fn text_from(&self, start: u32) -> &'a str {
+        self.source_text(self.span_from(start))
+    }
+
+    /// The source text covered by `span`
+    pub(crate) fn source_text(&self, span: Span) -> &'a str {
+        let span = Span::new(span.start - self.base_offset, span.end - self.base_offset);
+        span.source_text(self.lexer.source())
+    }
+
+    pub(crate) fn checkpoint(&self) -> LexerCheckpoint<'a> {
+        self.lexer.checkpoint()
+    }
+
+    pub(crate) fn rewind(&mut self, checkpoint: LexerCheckpoint<'a>) {
+        self.lexer.rewind(checkpoint);
+    }
+
+    /// A span covering the next character, or an empty span at the end of input
+    pub(crate) fn current_span(&self) -> Span {
+        let start = self.offset();
+        let len = if self.is_eof() { 0 } else { self.peek().len_utf8() };
+        #[allow(clippy::cast_possible_truncation)]
+        Span::new(start, start + len as u32)
+    }
+
+    pub(crate) fn expect_char(&mut self, c: char) -> Result<()> {
+        if self.next_eq(c) {
+            Ok(())
+        } else {
+            Err(Diagnostic::ExpectedChar(c, self.current_span()))
+        }
+    }
+
+    /// Consumes the identifier `keyword`, ignoring case.
+    pub(crate) fn expect_keyword(&mut self, keyword: &'static str) -> Result<()> {
+        if self.scan_identifier(keyword) {
+            Ok(())
+        } else {
+            Err(Diagnostic::ExpectedKeyword(keyword, self.current_span()))
+        }
+    }
+
+    /// Skips whitespace and comments.
+    pub(crate) fn whitespace(&mut self) -> Result<()> {
+        loop {
+            self.whitespace_without_comments();
+            if !self.scan_comment()? {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Skips whitespace, which doesn't include newlines in the indented syntax.
+    pub(crate) fn whitespace_without_comments(&mut self) {
+        if self.is_indented() {
+            self.lexer.eat_while(|c| c == ' ' || c == '\t');
+        } else {
+            self.lexer.eat_while(is_whitespace);
+        }
+    }
+
+    /// Skips a comment if one is next, returning whether it did.
+    pub(crate) fn scan_comment(&mut self) -> Result<bool> {
+        if self.peek() != '/' {
+            return Ok(false);
+        }
+        match self.nth_char(1) {
+            '/' => {
+                self.skip_silent_comment()?;
+                Ok(true)
+            }
+            '*' => {
+                self.skip_loud_comment()?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Skips a `//` comment, which is an error in plain CSS.
+    pub(crate) fn skip_silent_comment(&mut self) -> Result<()> {
+        let start = self.offset();
+        debug_assert!(self.lexer.remaining().starts_with("//"));
+        self.lexer.eat_while(|c| !is_newline(c));
+        if self.context.is_plain_css() {
+            return Err(Diagnostic::NotInPlainCss("Silent comments", self.span_from(start)));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn skip_loud_comment(&mut self) -> Result<()> {
+        let start = self.offset();
+        debug_assert!(self.lexer.remaining().starts_with("/*"));
+        self.bump();
+        self.bump();
+        loop {
+            match self.bump() {
+                '*' if self.next_eq('/') => return Ok(()),
+                EOF_CHAR if self.is_eof() => {
+                    return Err(Diagnostic::UnterminatedBlockComment(self.span_from(start)))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Whether an identifier starts `n` characters ahead
+    pub(crate) fn looking_at_identifier_at(&self, n: usize) -> bool {
+        let first = self.nth_char(n);
+        if is_ident_start(first) || first == '\\' {
+            return true;
+        }
+        if first != '-' {
+            return false;
+        }
+        let second = self.nth_char(n + 1);
+        is_ident_start(second) || second == '\\' || second == '-'
+    }
+
+    pub(crate) fn looking_at_identifier(&self) -> bool {
+        self.looking_at_identifier_at(0)
+    }
+
+    pub(crate) fn looking_at_identifier_body(&self) -> bool {
+        let c = self.peek();
+        is_ident(c) || c == '\\'
+    }
+
+    /// Whether `#{` is next
+    pub(crate) fn looking_at_interpolation(&self) -> bool {
+        self.peek() == '#' && self.nth_char(1) == '{'
+    }
+
+    /// Consumes a plain identifier, with escapes normalized.
+    ///
+    /// With `unit`, stops before a `-` followed by a digit or `.`, so `1px-2px` reads `px`.
+    pub(crate) fn identifier(&mut self, unit: bool) -> Result<String> {
+        let mut text = String::new();
+        if self.next_eq('-') {
+            text.push('-');
+            if self.next_eq('-') {
+                text.push('-');
+                self.identifier_body(&mut text, unit)?;
+                return Ok(text);
+            }
+        }
+        match self.peek() {
+            c if is_ident_start(c) => {
+                self.bump();
+                text.push(c);
+            }
+            '\\' => text.push_str(&self.escape(true)?),
+            _ => return Err(Diagnostic::ExpectedIdentifier(self.current_span())),
+        }
+        self.identifier_body(&mut text, unit)?;
+        Ok(text)
+    }
+
+    fn identifier_body(&mut self, text: &mut String, unit: bool) -> Result<()> {
+        loop {
+            match self.peek() {
+                '-' if unit && (self.nth_char(1) == '.' || is_dight(self.nth_char(1))) => break,
+                c if is_ident(c) => {
+                    self.bump();
+                    text.push(c);
+                }
+                '\\' => text.push_str(&self.escape(false)?),
+                _ => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// Consumes `$name` and returns `name`.
+    pub(crate) fn variable_name(&mut self) -> Result<String> {
+        self.expect_char('$')?;
+        self.identifier(false)
+    }
+
+    /// Whether the identifier `text` is next, ignoring ASCII case
+    pub(crate) fn looking_at_keyword(&mut self, text: &str) -> bool {
+        let checkpoint = self.checkpoint();
+        let found = self.scan_identifier(text);
+        self.rewind(checkpoint);
+        found
+    }
+
+    /// Consumes the identifier `text` if it's next and not followed by more identifier
+    /// characters, ignoring ASCII case.
+    pub(crate) fn scan_identifier(&mut self, text: &str) -> bool {
+        if !self.looking_at_identifier() {
+            return false;
+        }
+        let checkpoint = self.checkpoint();
+        for expected in text.chars() {
+            let c = self.peek();
+            if !c.eq_ignore_ascii_case(&expected) {
+                self.rewind(checkpoint);
+                return false;
+            }
+            self.bump();
+        }
+        if self.looking_at_identifier_body() {
+            self.rewind(checkpoint);
+            return false;
+        }
+        true
+    }
+
+    /// Consumes a `\` escape in an identifier and returns it normalized.
+    ///
+    /// Characters that are valid in the identifier at this position are unescaped, anything else
+    /// keeps a canonical escape, e.g. a leading digit becomes `\31 `.
+    pub(crate) fn escape(&mut self, identifier_start: bool) -> Result<String> {
+        let start = self.offset();
+        self.expect_char('\\')?;
+        let value = self.escaped_code_point(start)?;
+        let c = char::from_u32(value).unwrap_or(char::REPLACEMENT_CHARACTER);
+
+        let valid = if identifier_start { is_ident_start(c) } else { is_ident(c) };
+        Ok(if valid {
+            c.to_string()
+        } else if value <= 0x1f || value == 0x7f || (identifier_start && is_dight(c)) {
+            format!("\\{value:x} ")
+        } else {
+            format!("\\{c}")
+        })
+    }
+
+    /// Consumes a `\` escape in a string and returns the character it stands for.
+    pub(crate) fn escape_character(&mut self) -> Result<char> {
+        let start = self.offset();
+        self.expect_char('\\')?;
+        let value = self.escaped_code_point(start)?;
+        Ok(char::from_u32(value).unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    fn escaped_code_point(&mut self, start: u32) -> Result<u32> {
+        let first = self.peek();
+        if self.is_eof() || is_newline(first) {
+            return Err(Diagnostic::ExpectedEscapeSequence(self.span_from(start)));
+        }
+        if !is_hex_digit(first) {
+            return Ok(u32::from(self.bump()));
+        }
+        let mut value = 0;
+        for _ in 0..6 {
+            let Some(digit) = self.peek().to_digit(16) else { break };
+            self.bump();
+            value = value * 16 + digit;
+        }
+        if is_whitespace(self.peek()) {
+            self.bump();
+        }
+        if value == 0 || (0xd800..=0xdfff).contains(&value) || value > MAX_CODE_POINT {
+            return Ok(u32::from(char::REPLACEMENT_CHARACTER));
+        }
+        Ok(value)
+    }
+
+    /// Consumes a quoted string without interpolation and returns its contents.
+    pub(crate) fn string(&mut self) -> Result<String> {
+        let quote = self.peek();
+        if quote != '"' && quote != '\'' {
+            return Err(Diagnostic::Expected("string", self.current_span()));
+        }
+        self.bump();
+        let mut text = String::new();
+        loop {
+            match self.peek() {
+                c if c == quote => {
+                    self.bump();
+                    return Ok(text);
+                }
+                c if is_newline(c) || self.is_eof() => {
+                    return Err(Diagnostic::ExpectedChar(quote, self.current_span()));
+                }
+                '\\' => {
+                    if is_newline(self.nth_char(1)) {
+                        self.bump();
+                        self.skip_newline();
+                    } else {
+                        text.push(self.escape_character()?);
+                    }
+                }
+                c => {
+                    self.bump();
+                    text.push(c);
+                }
+            }
+        }
+    }
+
+    /// Consumes one newline, treating `\r\n` as one.
+    pub(crate) fn skip_newline(&mut self) {
+        if self.bump() == '\r' {
+            self.next_eq('\n');
+        }
+    }
+
+    /// Consumes a run of digits.
+    pub(crate) fn digits(&mut self) -> Result<()> {
+        if !is_dight(self.peek()) {
+            return Err(Diagnostic::ExpectedDigit(self.current_span()));
+        }
+        self.lexer.eat_while(is_dight);
+        Ok(())
+    }
+}
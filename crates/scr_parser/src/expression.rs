@@ -0,0 +1,836 @@
+//! `SassScript` expressions, like Dart Sass's `StylesheetParser.expression`
+
+use scr_allocator::Vec;
+use scr_ast::{
+    node::{
+        ArgumentList, BinaryOperator, Expression, Interpolation, ListSeparator, NamedArgument,
+        StringExpression, UnaryOperator,
+    },
+    Atom, Span,
+};
+use scr_diagnostics::{Diagnostic, Result};
+
+use crate::{
+    css::allowed_in_plain_css,
+    interpolation::InterpolationBuffer,
+    lexer::definitions::{is_dight, is_hex_digit, is_ident_start, is_newline, is_whitespace},
+    statement::unvendor,
+    Parser,
+};
+
+/// A check for where an expression must stop, e.g. before `to` in `@for $i from 1 to 10`.
+///
+/// It only looks ahead, the caller consumes whatever it found.
+pub type Until<'a> = Option<fn(&mut Parser<'a>) -> bool>;
+
+impl<'a> Parser<'a> {
+    /// A comma-separated list, or any narrower expression.
+    ///
+    /// Whitespace after the expression is left for the caller, so its span ends at the last
+    /// token.
+    pub(crate) fn expression(&mut self) -> Result<Expression<'a>> {
+        self.expression_until(None)
+    }
+
+    pub(crate) fn expression_until(&mut self, until: Until<'a>) -> Result<Expression<'a>> {
+        let start = self.offset();
+        let (mut elements, separator) = self.list_elements(until)?;
+        if separator == ListSeparator::Undecided {
+            return Ok(elements.pop().unwrap());
+        }
+        Ok(self.ast.expression_list(self.span_from(start), elements, separator, false))
+    }
+
+    /// An expression that may not be a comma-separated list, e.g. an argument
+    pub(crate) fn expression_until_comma(&mut self) -> Result<Expression<'a>> {
+        self.space_list(None, false)
+    }
+
+    /// An argument to a function call, where a single `=` is an operator as in IE's
+    /// `alpha(opacity=50)`
+    pub(crate) fn function_argument(&mut self) -> Result<Expression<'a>> {
+        self.space_list(None, true)
+    }
+
+    /// The elements of a comma- or space-separated list, `Undecided` for a single expression
+    fn list_elements(
+        &mut self,
+        until: Until<'a>,
+    ) -> Result<(Vec<'a, Expression<'a>>, ListSeparator)> {
+        self.restoring_parentheses(|parser| parser.comma_list_elements(until))
+    }
+
+    fn comma_list_elements(
+        &mut self,
+        until: Until<'a>,
+    ) -> Result<(Vec<'a, Expression<'a>>, ListSeparator)> {
+        let start = self.offset();
+        let first = self.space_list_elements(until, false)?;
+        let checkpoint = self.checkpoint();
+        self.whitespace()?;
+        if !self.next_eq(',') {
+            self.rewind(checkpoint);
+            let separator =
+                if first.len() > 1 { ListSeparator::Space } else { ListSeparator::Undecided };
+            return Ok((first, separator));
+        }
+        let mut elements = self.ast.new_vec();
+        elements.push(self.space_list_from(start, first));
+        loop {
+            let checkpoint = self.checkpoint();
+            self.whitespace()?;
+            if self.is_until(until) || !self.looking_at_expression() {
+                self.rewind(checkpoint);
+                break;
+            }
+            elements.push(self.space_list(until, false)?);
+            let checkpoint = self.checkpoint();
+            self.whitespace()?;
+            if !self.next_eq(',') {
+                self.rewind(checkpoint);
+                break;
+            }
+        }
+        Ok((elements, ListSeparator::Comma))
+    }
+
+    fn is_until(&mut self, until: Until<'a>) -> bool {
+        until.is_some_and(|until| until(self))
+    }
+
+    fn space_list(&mut self, until: Until<'a>, single_equals: bool) -> Result<Expression<'a>> {
+        self.restoring_parentheses(|parser| {
+            let start = parser.offset();
+            let elements = parser.space_list_elements(until, single_equals)?;
+            Ok(parser.space_list_from(start, elements))
+        })
+    }
+
+    fn space_list_from(&self, start: u32, mut elements: Vec<'a, Expression<'a>>) -> Expression<'a> {
+        if elements.len() == 1 {
+            return elements.pop().unwrap();
+        }
+        self.ast.expression_list(self.span_from(start), elements, ListSeparator::Space, false)
+    }
+
+    /// The elements of a space-separated list, each a chain of operations
+    fn space_list_elements(
+        &mut self,
+        until: Until<'a>,
+        single_equals: bool,
+    ) -> Result<Vec<'a, Expression<'a>>> {
+        let start = self.checkpoint();
+        let mut elements = self.ast.new_vec();
+        elements.push(self.operation_chain(until, single_equals)?);
+        loop {
+            let checkpoint = self.checkpoint();
+            self.whitespace()?;
+            if self.is_until(until) || !self.looking_at_expression() {
+                self.rewind(checkpoint);
+                return Ok(elements);
+            }
+            // A space-separated list in parentheses isn't a division, so `(1/2 3)` is read again
+            // with its slash kept.
+            if self.in_parentheses {
+                self.in_parentheses = false;
+                if is_division_chain(&elements[0]) {
+                    self.rewind(start.clone());
+                    elements.clear();
+                    elements.push(self.operation_chain(until, single_equals)?);
+                    continue;
+                }
+            }
+            elements.push(self.operation_chain(until, single_equals)?);
+        }
+    }
+
+    /// Runs `f`, then restores whether `/` is division because of enclosing parentheses, which a
+    /// space-separated list inside them turns off until the end of its expression.
+    fn restoring_parentheses<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        let in_parentheses = self.in_parentheses;
+        let result = f(self);
+        self.in_parentheses = in_parentheses;
+        result
+    }
+
+    /// Operands joined by binary operators, e.g. `$a + 1 == 2`
+    ///
+    /// A `/` is only kept as a slash, as in `font: 12px/1.5`, if every operator in the chain is
+    /// a `/` between numbers; `1/2 + 1` divides.
+    fn operation_chain(&mut self, until: Until<'a>, single_equals: bool) -> Result<Expression<'a>> {
+        let mut expression = self.binary(0, until, single_equals)?;
+        if !is_slash_operand(&expression) {
+            disallow_slash(&mut expression);
+        }
+        Ok(expression)
+    }
+
+    /// A Pratt parser for the operations whose operators bind at least as tightly as
+    /// `min_precedence`, with each operator's [`BinaryOperator::precedence`] as its binding power
+    ///
+    /// Operators are left-associative, and `not`, unary `+`, `-` and `/` bind tighter than any
+    /// of them, as part of a single expression.
+    fn binary(
+        &mut self,
+        min_precedence: u8,
+        until: Until<'a>,
+        single_equals: bool,
+    ) -> Result<Expression<'a>> {
+        let start = self.offset();
+        let mut left = self.single_expression()?;
+        loop {
+            let checkpoint = self.checkpoint();
+            self.whitespace()?;
+            if self.is_until(until) {
+                self.rewind(checkpoint);
+                break;
+            }
+            let whitespace_before = self.text_from(self.base_offset).ends_with(is_whitespace);
+            let operator = match self.peek_binary_operator(whitespace_before, single_equals) {
+                Some(operator) if operator.precedence() >= min_precedence => operator,
+                _ => {
+                    self.rewind(checkpoint);
+                    break;
+                }
+            };
+            let operator_start = self.offset();
+            for _ in 0..operator.as_str().len() {
+                self.bump();
+            }
+            if self.context.is_plain_css() && !allowed_in_plain_css(operator) {
+                return Err(Diagnostic::NotInPlainCss("Operators", self.span_from(operator_start)));
+            }
+            self.whitespace()?;
+            let right = self.binary(operator.precedence() + 1, until, single_equals)?;
+            let allows_slash = operator == BinaryOperator::DividedBy
+                && !self.in_parentheses
+                && is_slash_operand(&left)
+                && is_slash_operand(&right);
+            left = self.ast.expression_binary(
+                self.span_from(start),
+                operator,
+                left,
+                right,
+                allows_slash,
+            );
+        }
+        Ok(left)
+    }
+
+    /// The binary operator that's next after an operand, without consuming it.
+    ///
+    /// A `-` is an operator unless it starts an identifier, as in `a -b`, or a number after
+    /// whitespace, as in `1 -2`; `1-2`, `1 - 2` and `1 -$a` subtract. `+` always adds, so `1 +2`
+    /// is `1 + 2`.
+    fn peek_binary_operator(
+        &mut self,
+        whitespace_before: bool,
+        single_equals: bool,
+    ) -> Option<BinaryOperator> {
+        let next = self.nth_char(1);
+        Some(match self.peek() {
+            '=' if next == '=' => BinaryOperator::Equals,
+            '=' if single_equals => BinaryOperator::SingleEquals,
+            '!' if next == '=' => BinaryOperator::NotEquals,
+            '<' if next == '=' => BinaryOperator::LessThanOrEquals,
+            '<' => BinaryOperator::LessThan,
+            '>' if next == '=' => BinaryOperator::GreaterThanOrEquals,
+            '>' => BinaryOperator::GreaterThan,
+            '+' => BinaryOperator::Plus,
+            '-' => {
+                if (whitespace_before && self.looking_at_number())
+                    || self.looking_at_interpolated_identifier()
+                {
+                    return None;
+                }
+                BinaryOperator::Minus
+            }
+            '*' => BinaryOperator::Times,
+            '/' => BinaryOperator::DividedBy,
+            '%' => BinaryOperator::Modulo,
+            _ if self.context.is_plain_css() => return None,
+            'a' | 'A' if self.looking_at_keyword("and") => BinaryOperator::And,
+            'o' | 'O' if self.looking_at_keyword("or") => BinaryOperator::Or,
+            _ => return None,
+        })
+    }
+
+    /// Whether an expression can start here
+    pub(crate) fn looking_at_expression(&self) -> bool {
+        match self.peek() {
+            '.' => self.nth_char(1) != '.',
+            '!' => {
+                let next = self.nth_char(1);
+                matches!(next, 'i' | 'I') || is_whitespace(next)
+            }
+            '(' | '/' | '[' | '\'' | '"' | '#' | '+' | '-' | '\\' | '$' | '&' => true,
+            c => is_ident_start(c) || is_dight(c),
+        }
+    }
+
+    /// Whether a number, possibly signed, is next
+    fn looking_at_number(&self) -> bool {
+        let (first, second) = (self.peek(), self.nth_char(1));
+        match first {
+            c if is_dight(c) => true,
+            '.' => is_dight(second),
+            '+' | '-' => is_dight(second) || (second == '.' && is_dight(self.nth_char(2))),
+            _ => false,
+        }
+    }
+
+    /// An expression without binary operators or list separators
+    fn single_expression(&mut self) -> Result<Expression<'a>> {
+        self.nested(|parser| match parser.peek() {
+            '(' => parser.parentheses(),
+            '+' | '-' if parser.looking_at_number() => parser.number(),
+            '-' if parser.looking_at_interpolated_identifier() => parser.identifier_like(),
+            '/' | '+' | '-' => parser.unary_operation(),
+            '.' => parser.number(),
+            '[' => parser.bracketed_list(),
+            '$' => parser.variable(),
+            '&' => {
+                let start = parser.offset();
+                parser.bump();
+                if parser.context.is_plain_css() {
+                    let span = parser.span_from(start);
+                    return Err(Diagnostic::IsNotInPlainCss("The parent selector", span));
+                }
+                Ok(parser.ast.expression_parent_selector(parser.span_from(start)))
+            }
+            '"' | '\'' => parser.interpolated_string(),
+            '#' => parser.hash_expression(),
+            '!' => parser.important(),
+            'u' | 'U' if parser.nth_char(1) == '+' => parser.unicode_range(),
+            c if is_dight(c) => parser.number(),
+            c if is_ident_start(c) || c == '\\' => parser.identifier_like(),
+            _ => Err(Diagnostic::Expected("expression", parser.current_span())),
+        })
+    }
+
+    /// `-x`, `+x`, `/x`
+    fn unary_operation(&mut self) -> Result<Expression<'a>> {
+        let start = self.offset();
+        let operator = match self.bump() {
+            '+' => UnaryOperator::Plus,
+            '-' => UnaryOperator::Minus,
+            _ => UnaryOperator::Divide,
+        };
+        if self.context.is_plain_css() && operator != UnaryOperator::Minus {
+            return Err(Diagnostic::NotInPlainCss("Operators", self.span_from(start)));
+        }
+        self.whitespace()?;
+        let argument = self.single_expression()?;
+        Ok(self.ast.expression_unary(self.span_from(start), operator, argument))
+    }
+
+    /// `()`, `(expression)`, `(a, b)` or `(key: value, ...)`, in which `/` is division
+    fn parentheses(&mut self) -> Result<Expression<'a>> {
+        self.restoring_parentheses(|parser| {
+            parser.in_parentheses = true;
+            parser.parentheses_contents()
+        })
+    }
+
+    fn parentheses_contents(&mut self) -> Result<Expression<'a>> {
+        let start = self.offset();
+        self.expect_char('(')?;
+        self.whitespace()?;
+        if !self.looking_at_expression() {
+            self.expect_char(')')?;
+            let span = self.span_from(start);
+            return Ok(self.ast.expression_list(
+                span,
+                self.ast.new_vec(),
+                ListSeparator::Undecided,
+                false,
+            ));
+        }
+        let first = self.expression_until_comma()?;
+        self.whitespace()?;
+        if self.next_eq(':') {
+            self.whitespace()?;
+            return self.map(start, first);
+        }
+        if !self.next_eq(',') {
+            self.expect_char(')')?;
+            return Ok(self.ast.expression_parenthesized(self.span_from(start), first));
+        }
+        self.whitespace()?;
+        let mut elements = self.ast.new_vec();
+        elements.push(first);
+        while self.looking_at_expression() {
+            elements.push(self.expression_until_comma()?);
+            self.whitespace()?;
+            if !self.next_eq(',') {
+                break;
+            }
+            self.whitespace()?;
+        }
+        self.expect_char(')')?;
+        Ok(self.ast.expression_list(self.span_from(start), elements, ListSeparator::Comma, false))
+    }
+
+    /// The rest of a map after its first key and `:`
+    fn map(&mut self, start: u32, first_key: Expression<'a>) -> Result<Expression<'a>> {
+        let mut entries = self.ast.new_vec();
+        let mut key = first_key;
+        loop {
+            let value = self.expression_until_comma()?;
+            let span = Span::new(key.span().start, self.offset());
+            entries.push(self.ast.map_entry(span, key, value));
+            self.whitespace()?;
+            if !self.next_eq(',') {
+                break;
+            }
+            self.whitespace()?;
+            if !self.looking_at_expression() {
+                break;
+            }
+            key = self.expression_until_comma()?;
+            self.whitespace()?;
+            self.expect_char(':')?;
+            self.whitespace()?;
+        }
+        self.expect_char(')')?;
+        Ok(self.ast.expression_map(self.span_from(start), entries))
+    }
+
+    /// `[a, b]`, `[a b]` or `[]`
+    fn bracketed_list(&mut self) -> Result<Expression<'a>> {
+        let start = self.offset();
+        self.expect_char('[')?;
+        self.whitespace()?;
+        let (elements, separator) = if self.peek() == ']' {
+            (self.ast.new_vec(), ListSeparator::Undecided)
+        } else {
+            let elements = self.list_elements(None)?;
+            self.whitespace()?;
+            elements
+        };
+        self.expect_char(']')?;
+        Ok(self.ast.expression_list(self.span_from(start), elements, separator, true))
+    }
+
+    fn variable(&mut self) -> Result<Expression<'a>> {
+        let start = self.offset();
+        let name = Atom::from(self.variable_name()?);
+        if self.context.is_plain_css() {
+            return Err(Diagnostic::NotInPlainCss("Sass variables", self.span_from(start)));
+        }
+        Ok(self.ast.expression_variable(self.span_from(start), None, name))
+    }
+
+    /// A quoted string, which may contain interpolation
+    pub(crate) fn interpolated_string(&mut self) -> Result<Expression<'a>> {
+        let string = self.interpolated_string_expression()?;
+        Ok(Expression::String(self.ast.alloc(string)))
+    }
+
+    pub(crate) fn interpolated_string_expression(&mut self) -> Result<StringExpression<'a>> {
+        let start = self.offset();
+        let quote = self.bump();
+        let mut buffer = InterpolationBuffer::new(&self.ast, self.offset());
+        loop {
+            match self.peek() {
+                c if c == quote => break,
+                '\\' => {
+                    if is_newline(self.nth_char(1)) {
+                        self.bump();
+                        self.skip_newline();
+                    } else {
+                        buffer.push(self.escape_character()?);
+                    }
+                }
+                '#' if self.nth_char(1) == '{' => self.add_interpolation_expression(&mut buffer)?,
+                c if is_newline(c) || self.is_eof() => {
+                    return Err(Diagnostic::ExpectedChar(quote, self.current_span()));
+                }
+                c => {
+                    self.bump();
+                    buffer.push(c);
+                }
+            }
+        }
+        let text = buffer.finish(&self.ast, self.offset());
+        self.bump();
+        Ok(self.ast.string_expression(self.span_from(start), text, true))
+    }
+
+    /// A hex color, or an unquoted string starting with `#`
+    fn hash_expression(&mut self) -> Result<Expression<'a>> {
+        let start = self.offset();
+        if self.nth_char(1) == '{' {
+            return self.identifier_like();
+        }
+        self.expect_char('#')?;
+        if is_dight(self.peek()) {
+            return self.hex_color(start);
+        }
+        let after_hash = self.checkpoint();
+        let identifier = self.interpolated_identifier()?;
+        if identifier.as_plain().is_some_and(is_hex_color) {
+            self.rewind(after_hash);
+            return self.hex_color(start);
+        }
+        let mut buffer = InterpolationBuffer::new(&self.ast, start);
+        buffer.push('#');
+        buffer.add_interpolation(&self.ast, identifier);
+        let text = buffer.finish(&self.ast, self.offset());
+        Ok(self.ast.expression_string(self.span_from(start), text, false))
+    }
+
+    /// The digits of a hex color after its `#`
+    fn hex_color(&mut self, start: u32) -> Result<Expression<'a>> {
+        let digits_start = self.offset();
+        while is_hex_digit(self.peek()) {
+            self.bump();
+        }
+        let digits = self.text_from(digits_start);
+        if !matches!(digits.len(), 3 | 4 | 6 | 8) {
+            return Err(Diagnostic::Expected("hex digit", self.current_span()));
+        }
+        Ok(self.ast.expression_color(self.span_from(start), digits))
+    }
+
+    /// A number with an optional sign, exponent and unit
+    fn number(&mut self) -> Result<Expression<'a>> {
+        let start = self.offset();
+        if matches!(self.peek(), '+' | '-') {
+            self.bump();
+        }
+        if self.peek() != '.' {
+            self.digits()?;
+        }
+        if self.peek() == '.' && is_dight(self.nth_char(1)) {
+            self.bump();
+            self.digits()?;
+        }
+        if matches!(self.peek(), 'e' | 'E') {
+            let next = self.nth_char(1);
+            if is_dight(next) || (matches!(next, '+' | '-') && is_dight(self.nth_char(2))) {
+                self.bump();
+                if matches!(self.peek(), '+' | '-') {
+                    self.bump();
+                }
+                self.digits()?;
+            }
+        }
+        let value = self
+            .text_from(start)
+            .parse::<f64>()
+            .map_err(|_| Diagnostic::InvalidNumber("Invalid number", self.span_from(start)))?;
+        let unit = if self.next_eq('%') {
+            Some(Atom::from("%"))
+        } else if self.looking_at_identifier() && !(self.peek() == '-' && self.nth_char(1) == '-') {
+            Some(Atom::from(self.identifier(true)?))
+        } else {
+            None
+        };
+        Ok(self.ast.expression_number(self.span_from(start), value, unit))
+    }
+
+    /// `!important`
+    fn important(&mut self) -> Result<Expression<'a>> {
+        let start = self.offset();
+        self.expect_char('!')?;
+        self.whitespace()?;
+        self.expect_keyword("important")?;
+        let span = self.span_from(start);
+        let text = self.ast.plain_interpolation(span, "!important");
+        Ok(self.ast.expression_string(span, text, false))
+    }
+
+    /// `U+0025-00FF` or `u+4??`
+    fn unicode_range(&mut self) -> Result<Expression<'a>> {
+        let start = self.offset();
+        self.bump();
+        self.expect_char('+')?;
+        let mut first_length = 0;
+        while is_hex_digit(self.peek()) {
+            self.bump();
+            first_length += 1;
+        }
+        let mut has_question_mark = false;
+        while self.next_eq('?') {
+            has_question_mark = true;
+            first_length += 1;
+        }
+        if first_length == 0 {
+            return Err(Diagnostic::Expected("hex digit or \"?\"", self.current_span()));
+        }
+        if first_length > 6 {
+            return Err(Diagnostic::Expected("at most 6 digits", self.span_from(start)));
+        }
+        if !has_question_mark && self.next_eq('-') {
+            let second_start = self.offset();
+            while is_hex_digit(self.peek()) {
+                self.bump();
+            }
+            let second_length = self.offset() - second_start;
+            if second_length == 0 {
+                return Err(Diagnostic::Expected("hex digit", self.current_span()));
+            }
+            if second_length > 6 {
+                return Err(Diagnostic::Expected("at most 6 digits", self.span_from(start)));
+            }
+        }
+        if self.looking_at_interpolated_identifier_body() {
+            return Err(Diagnostic::Expected("end of identifier", self.current_span()));
+        }
+        let span = self.span_from(start);
+        let text = self.ast.plain_interpolation(span, self.text_from(start));
+        Ok(self.ast.expression_string(span, text, false))
+    }
+
+    /// An identifier, keyword, function call or special function
+    fn identifier_like(&mut self) -> Result<Expression<'a>> {
+        if self.context.is_plain_css() {
+            return self.plain_css_identifier_like();
+        }
+        let start = self.offset();
+        let identifier = self.interpolated_identifier()?;
+        let Some(plain) = identifier.as_plain() else {
+            if self.peek() == '(' {
+                let arguments = self.argument_list()?;
+                let span = self.span_from(start);
+                return Ok(self.ast.expression_interpolated_function(span, identifier, arguments));
+            }
+            return Ok(self.ast.expression_string(identifier.span, identifier, false));
+        };
+
+        if plain == "not" {
+            self.whitespace()?;
+            let argument = self.single_expression()?;
+            let span = self.span_from(start);
+            return Ok(self.ast.expression_unary(span, UnaryOperator::Not, argument));
+        }
+        if self.peek() != '(' {
+            match plain {
+                "true" => return Ok(self.ast.expression_boolean(identifier.span, true)),
+                "false" => return Ok(self.ast.expression_boolean(identifier.span, false)),
+                "null" => return Ok(self.ast.expression_null(identifier.span)),
+                _ => {}
+            }
+        }
+        if let Some(special) = self.try_special_function(plain, start)? {
+            return Ok(special);
+        }
+        match self.peek() {
+            '.' if self.nth_char(1) != '.' => self.namespaced_expression(Atom::from(plain), start),
+            '(' if plain == "if" => {
+                let arguments = self.argument_list()?;
+                Ok(self.ast.expression_if(self.span_from(start), arguments))
+            }
+            '(' => {
+                let arguments = self.argument_list()?;
+                let name = Atom::from(plain);
+                Ok(self.ast.expression_function(self.span_from(start), None, name, arguments))
+            }
+            _ => Ok(self.ast.expression_string(identifier.span, identifier, false)),
+        }
+    }
+
+    /// `namespace.$variable` or `namespace.function()`, after `namespace`
+    pub(crate) fn namespaced_expression(
+        &mut self,
+        namespace: Atom,
+        start: u32,
+    ) -> Result<Expression<'a>> {
+        self.expect_char('.')?;
+        if self.peek() == '$' {
+            let name = Atom::from(self.variable_name()?);
+            let span = self.span_from(start);
+            return Ok(self.ast.expression_variable(span, Some(namespace), name));
+        }
+        let name = Atom::from(self.identifier(false)?);
+        let arguments = self.argument_list()?;
+        Ok(self.ast.expression_function(self.span_from(start), Some(namespace), name, arguments))
+    }
+
+    /// Functions whose arguments aren't `SassScript`, e.g. `url(foo.png)` or
+    /// `progid:DXImageTransform.Microsoft.gradient(...)`, which become unquoted strings.
+    pub(crate) fn try_special_function(
+        &mut self,
+        name: &str,
+        start: u32,
+    ) -> Result<Option<Expression<'a>>> {
+        let normalized = unvendor(name);
+        let mut buffer = InterpolationBuffer::new(&self.ast, start);
+        match normalized {
+            "calc" if normalized == name => return Ok(None),
+            "calc" | "element" | "expression" => {
+                if !self.next_eq('(') {
+                    return Ok(None);
+                }
+                buffer.push_str(name);
+                buffer.push('(');
+            }
+            "progid" => {
+                if !self.next_eq(':') {
+                    return Ok(None);
+                }
+                buffer.push_str(name);
+                buffer.push(':');
+                while self.peek().is_ascii_alphabetic() || self.peek() == '.' {
+                    buffer.push(self.bump());
+                }
+                self.expect_char('(')?;
+                buffer.push('(');
+            }
+            "url" => {
+                let contents = self.try_url_contents(start, Some(name))?;
+                return Ok(contents.map(|contents| {
+                    self.ast.expression_string(self.span_from(start), contents, false)
+                }));
+            }
+            _ => return Ok(None),
+        }
+        let contents = self.interpolated_declaration_value(true)?;
+        buffer.add_interpolation(&self.ast, contents);
+        self.expect_char(')')?;
+        buffer.push(')');
+        let text = buffer.finish(&self.ast, self.offset());
+        Ok(Some(self.ast.expression_string(self.span_from(start), text, false)))
+    }
+
+    /// The contents of an unquoted `url(...)` after its name, or `None` if they aren't a plain
+    /// URL, in which case `url` is an ordinary function call.
+    pub(crate) fn try_url_contents(
+        &mut self,
+        start: u32,
+        name: Option<&str>,
+    ) -> Result<Option<Interpolation<'a>>> {
+        let before_contents = self.checkpoint();
+        if !self.next_eq('(') {
+            return Ok(None);
+        }
+        self.whitespace_without_comments();
+        let mut buffer = InterpolationBuffer::new(&self.ast, start);
+        buffer.push_str(name.unwrap_or("url"));
+        buffer.push('(');
+        loop {
+            match self.peek() {
+                '\\' => buffer.push_str(&self.escape(false)?),
+                '#' if self.nth_char(1) == '{' => self.add_interpolation_expression(&mut buffer)?,
+                ')' => {
+                    buffer.push(self.bump());
+                    return Ok(Some(buffer.finish(&self.ast, self.offset())));
+                }
+                c if is_whitespace(c) => {
+                    self.whitespace_without_comments();
+                    if self.peek() != ')' {
+                        break;
+                    }
+                }
+                c if matches!(c, '!' | '%' | '&' | '*'..='~') || c >= '\u{80}' => {
+                    buffer.push(self.bump());
+                }
+                _ => break,
+            }
+        }
+        self.rewind(before_contents);
+        Ok(None)
+    }
+
+    /// `(a, $b: c, $rest...)` after a function name
+    pub(crate) fn argument_list(&mut self) -> Result<ArgumentList<'a>> {
+        self.arguments(true)
+    }
+
+    /// `(a, $b: c, $rest...)` after `@include` or `@content`, where `=` isn't an operator
+    pub(crate) fn mixin_argument_list(&mut self) -> Result<ArgumentList<'a>> {
+        self.arguments(false)
+    }
+
+    fn arguments(&mut self, single_equals: bool) -> Result<ArgumentList<'a>> {
+        let start = self.offset();
+        self.expect_char('(')?;
+        self.whitespace()?;
+        let mut positional = self.ast.new_vec();
+        let mut named: Vec<'a, NamedArgument<'a>> = self.ast.new_vec();
+        let (mut rest, mut keyword_rest) = (None, None);
+        while self.looking_at_expression() {
+            let argument_start = self.offset();
+            let expression = if single_equals {
+                self.function_argument()?
+            } else {
+                self.expression_until_comma()?
+            };
+            self.whitespace()?;
+            match expression {
+                Expression::Variable(variable)
+                    if variable.namespace.is_none() && self.next_eq(':') =>
+                {
+                    self.whitespace()?;
+                    let name = variable.name;
+                    let value = self.expression_until_comma()?;
+                    let span = self.span_from(argument_start);
+                    if named.iter().any(|argument| argument.name == name) {
+                        return Err(Diagnostic::DuplicateArgument(span));
+                    }
+                    named.push(self.ast.named_argument(span, name, value));
+                }
+                expression if self.next_eq('.') => {
+                    self.expect_char('.')?;
+                    self.expect_char('.')?;
+                    if rest.is_none() {
+                        rest = Some(expression);
+                    } else {
+                        keyword_rest = Some(expression);
+                        self.whitespace()?;
+                        break;
+                    }
+                }
+                expression if !named.is_empty() => {
+                    return Err(Diagnostic::PositionalAfterNamed(expression.span()));
+                }
+                expression => positional.push(expression),
+            }
+            self.whitespace()?;
+            if !self.next_eq(',') {
+                break;
+            }
+            self.whitespace()?;
+        }
+        self.expect_char(')')?;
+        let span = self.span_from(start);
+        Ok(self.ast.argument_list(span, positional, named, rest, keyword_rest))
+    }
+}
+
+/// Whether `expression` may be half of a `/` that's emitted as a literal slash
+fn is_slash_operand(expression: &Expression) -> bool {
+    match expression {
+        Expression::Number(_) => true,
+        Expression::Binary(binary) => binary.allows_slash,
+        _ => false,
+    }
+}
+
+/// Makes every `/` in a chain of operations a division, once the chain turns out to have some
+/// other operator.
+fn disallow_slash(expression: &mut Expression) {
+    if let Expression::Binary(binary) = expression {
+        binary.allows_slash = false;
+        disallow_slash(&mut binary.left);
+        disallow_slash(&mut binary.right);
+    }
+}
+
+/// Whether every operator in `expression`'s chain is a `/`, which may then be a slash outside
+/// parentheses
+fn is_division_chain(expression: &Expression) -> bool {
+    match expression {
+        Expression::Binary(binary) => {
+            binary.operator == BinaryOperator::DividedBy
+                && is_division_chain(&binary.left)
+                && is_division_chain(&binary.right)
+        }
+        _ => true,
+    }
+}
+
+fn is_hex_color(text: &str) -> bool {
+    matches!(text.len(), 3 | 4 | 6 | 8) && text.chars().all(|c| c.is_ascii_hexdigit())
+}
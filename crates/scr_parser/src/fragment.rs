@@ -0,0 +1,79 @@
+//! Parsing a fragment of a stylesheet on its own, such as the argument of `selector.parse()` or
+//! a value from a design token
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use scr_ast::node::{Expression, Interpolation, MediaQueryList, SelectorList, SupportsCondition};
+use scr_diagnostics::{Diagnostic, Result};
+
+use crate::Parser;
+
+pub struct FragmentReturn<T> {
+    /// `None` if the fragment couldn't be parsed, the reason is in `errors`.
+    pub fragment: Option<T>,
+    pub errors: Vec<Diagnostic>,
+    /// Whether the parser panicked
+    pub panicked: bool,
+}
+
+impl<'a> Parser<'a> {
+    /// Parses the whole source as a selector list, e.g. `a > b, &:hover`.
+    #[must_use]
+    pub fn parse_selector(self) -> FragmentReturn<SelectorList<'a>> {
+        self.parse_fragment(Self::selector_list)
+    }
+
+    /// Parses the whole source as a `SassScript` expression, e.g. `$a + 1, 2`.
+    #[must_use]
+    pub fn parse_expression(self) -> FragmentReturn<Expression<'a>> {
+        self.parse_fragment(Self::expression)
+    }
+
+    /// Parses the whole source as a declaration value kept as text, as for a custom property.
+    #[must_use]
+    pub fn parse_declaration_value(self) -> FragmentReturn<Interpolation<'a>> {
+        self.parse_fragment(|parser| parser.interpolated_declaration_value(true))
+    }
+
+    /// Parses the whole source as the queries of `@media`, e.g. `screen and (min-width: 1px)`.
+    #[must_use]
+    pub fn parse_media_query_list(self) -> FragmentReturn<MediaQueryList<'a>> {
+        self.parse_fragment(Self::media_query_list)
+    }
+
+    /// Parses the whole source as the condition of `@supports`, e.g. `(a: b) and (c: d)`.
+    #[must_use]
+    pub fn parse_supports_condition(self) -> FragmentReturn<SupportsCondition<'a>> {
+        self.parse_fragment(Self::supports_condition)
+    }
+
+    /// Parses the whole source with `production`, allowing whitespace around it.
+    fn parse_fragment<T>(
+        mut self,
+        production: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> FragmentReturn<T> {
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            self.whitespace()?;
+            let fragment = production(&mut self)?;
+            self.whitespace()?;
+            if !self.is_eof() {
+                return Err(Diagnostic::Expected("no more input", self.current_span()));
+            }
+            Ok(fragment)
+        }));
+        let panicked = result.is_err();
+        let fragment = match result {
+            Ok(Ok(fragment)) => Some(fragment),
+            Ok(Err(error)) => {
+                self.error(error);
+                None
+            }
+            Err(_) => {
+                self.errors.borrow_mut().push(Diagnostic::Panic(self.source_span()));
+                None
+            }
+        };
+        let errors = std::mem::take(&mut *self.errors.borrow_mut());
+        FragmentReturn { fragment, errors, panicked }
+    }
+}
@@ -0,0 +1,349 @@
+//! Building [`Interpolation`]s from text and `#{}` expressions
+
+use scr_allocator::Vec;
+use scr_ast::{
+    node::{Expression, Interpolation, InterpolationPart},
+    AstBuilder, Span,
+};
+use scr_diagnostics::{Diagnostic, Result};
+
+use crate::{
+    lexer::definitions::{is_ident, is_ident_start, is_newline, is_whitespace},
+    Parser,
+};
+
+/// Collects text and expressions into an [`Interpolation`], like Dart Sass's
+/// `InterpolationBuffer`.
+///
+/// Text parts are spanned from the end of the previous expression to the start of the next one.
+pub struct InterpolationBuffer<'a> {
+    start: u32,
+    text: String,
+    text_start: u32,
+    parts: Vec<'a, InterpolationPart<'a>>,
+}
+
+impl<'a> InterpolationBuffer<'a> {
+    pub fn new(ast: &AstBuilder<'a>, start: u32) -> Self {
+        Self { start, text: String::new(), text_start: start, parts: ast.new_vec() }
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.text.push(c);
+    }
+
+    pub fn push_str(&mut self, text: &str) {
+        self.text.push_str(text);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty() && self.parts.is_empty()
+    }
+
+    fn flush(&mut self, ast: &AstBuilder<'a>, end: u32) {
+        if !self.text.is_empty() {
+            let span = Span::new(self.text_start, end.max(self.text_start));
+            let text = ast.interpolation_part_text(span, ast.str(&self.text));
+            self.parts.push(text);
+            self.text.clear();
+        }
+    }
+
+    /// Adds `#{expression}`, `span` covering the `#{` and `}`.
+    pub fn add(&mut self, ast: &AstBuilder<'a>, expression: Expression<'a>, span: Span) {
+        self.flush(ast, span.start);
+        self.parts.push(InterpolationPart::Expression(expression));
+        self.text_start = span.end;
+    }
+
+    /// Appends all of `interpolation`'s parts.
+    pub fn add_interpolation(&mut self, ast: &AstBuilder<'a>, interpolation: Interpolation<'a>) {
+        for part in interpolation.parts {
+            match part {
+                InterpolationPart::Text(text) => self.push_str(text.value),
+                InterpolationPart::Expression(expression) => {
+                    let span = expression.span();
+                    self.add(ast, expression, span);
+                }
+            }
+        }
+    }
+
+    /// Removes trailing whitespace from the text written so far.
+    pub fn trim_end(&mut self) {
+        let len = self.text.trim_end().len();
+        self.text.truncate(len);
+    }
+
+    pub fn finish(mut self, ast: &AstBuilder<'a>, end: u32) -> Interpolation<'a> {
+        self.flush(ast, end);
+        ast.interpolation(Span::new(self.start, end), self.parts)
+    }
+}
+
+impl<'a> Parser<'a> {
+    /// Consumes `#{expression}` and adds it to `buffer`.
+    pub(crate) fn add_interpolation_expression(
+        &mut self,
+        buffer: &mut InterpolationBuffer<'a>,
+    ) -> Result<()> {
+        let start = self.offset();
+        let expression = self.single_interpolation()?;
+        buffer.add(&self.ast, expression, self.span_from(start));
+        Ok(())
+    }
+
+    /// Consumes `#{expression}`.
+    pub(crate) fn single_interpolation(&mut self) -> Result<Expression<'a>> {
+        let start = self.offset();
+        self.expect_char('#')?;
+        self.expect_char('{')?;
+        self.whitespace()?;
+        let expression = self.expression()?;
+        self.expect_char('}')?;
+        if self.context.is_plain_css() {
+            return Err(Diagnostic::IsNotInPlainCss("Interpolation", self.span_from(start)));
+        }
+        Ok(expression)
+    }
+
+    /// Consumes an identifier that may contain interpolation, e.g. `foo-#{$bar}`.
+    pub(crate) fn interpolated_identifier(&mut self) -> Result<Interpolation<'a>> {
+        let start = self.offset();
+        let mut buffer = InterpolationBuffer::new(&self.ast, start);
+        if self.next_eq('-') {
+            buffer.push('-');
+            if self.next_eq('-') {
+                buffer.push('-');
+                self.interpolated_identifier_body(&mut buffer)?;
+                return Ok(buffer.finish(&self.ast, self.offset()));
+            }
+        }
+        match self.peek() {
+            c if is_ident_start(c) => {
+                self.bump();
+                buffer.push(c);
+            }
+            '\\' => {
+                let escape = self.escape(true)?;
+                buffer.push_str(&escape);
+            }
+            '#' if self.nth_char(1) == '{' => self.add_interpolation_expression(&mut buffer)?,
+            _ => return Err(Diagnostic::ExpectedIdentifier(self.current_span())),
+        }
+        self.interpolated_identifier_body(&mut buffer)?;
+        Ok(buffer.finish(&self.ast, self.offset()))
+    }
+
+    pub(crate) fn interpolated_identifier_body(
+        &mut self,
+        buffer: &mut InterpolationBuffer<'a>,
+    ) -> Result<()> {
+        loop {
+            match self.peek() {
+                c if is_ident(c) => {
+                    self.bump();
+                    buffer.push(c);
+                }
+                '\\' => {
+                    let escape = self.escape(false)?;
+                    buffer.push_str(&escape);
+                }
+                '#' if self.nth_char(1) == '{' => self.add_interpolation_expression(buffer)?,
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Whether an identifier, possibly starting with interpolation, is `n` characters ahead
+    pub(crate) fn looking_at_interpolated_identifier_at(&self, n: usize) -> bool {
+        let first = self.nth_char(n);
+        if is_ident_start(first) || first == '\\' {
+            return true;
+        }
+        if first == '#' {
+            return self.nth_char(n + 1) == '{';
+        }
+        if first != '-' {
+            return false;
+        }
+        let second = self.nth_char(n + 1);
+        if second == '#' {
+            return self.nth_char(n + 2) == '{';
+        }
+        is_ident_start(second) || second == '\\' || second == '-'
+    }
+
+    pub(crate) fn looking_at_interpolated_identifier(&self) -> bool {
+        self.looking_at_interpolated_identifier_at(0)
+    }
+
+    pub(crate) fn looking_at_interpolated_identifier_body(&self) -> bool {
+        self.looking_at_identifier_body() || self.looking_at_interpolation()
+    }
+
+    /// Adds `expression` to `buffer`, inlining the text of unquoted strings rather than wrapping
+    /// them in `#{}`.
+    pub(crate) fn add_or_inject(
+        &self,
+        buffer: &mut InterpolationBuffer<'a>,
+        expression: Expression<'a>,
+    ) {
+        match expression {
+            Expression::String(mut string) if !string.quoted => {
+                let empty = self.ast.interpolation(string.span, self.ast.new_vec());
+                let text = std::mem::replace(&mut string.text, empty);
+                buffer.add_interpolation(&self.ast, text);
+            }
+            expression => {
+                let span = expression.span();
+                buffer.add(&self.ast, expression, span);
+            }
+        }
+    }
+
+    /// A declaration value kept as text, as for custom properties, up to a `;` or closing
+    /// bracket that isn't nested.
+    pub(crate) fn interpolated_declaration_value(
+        &mut self,
+        allow_empty: bool,
+    ) -> Result<Interpolation<'a>> {
+        let start = self.offset();
+        let mut buffer = InterpolationBuffer::new(&self.ast, start);
+        let mut brackets = std::vec::Vec::new();
+        loop {
+            match self.peek() {
+                _ if self.is_eof() => break,
+                '\\' => {
+                    let escape = self.escape(false)?;
+                    buffer.push_str(&escape);
+                }
+                '"' | '\'' => self.add_quoted_text(&mut buffer)?,
+                '/' if self.nth_char(1) == '*' => {
+                    let comment_start = self.offset();
+                    self.skip_loud_comment()?;
+                    buffer.push_str(self.text_from(comment_start));
+                }
+                '#' if self.nth_char(1) == '{' => self.add_interpolation_expression(&mut buffer)?,
+                '(' | '[' | '{' => {
+                    let c = self.bump();
+                    buffer.push(c);
+                    brackets.push(match c {
+                        '(' => ')',
+                        '[' => ']',
+                        _ => '}',
+                    });
+                }
+                c @ (')' | ']' | '}') => {
+                    let Some(&closing) = brackets.last() else { break };
+                    if c != closing {
+                        return Err(Diagnostic::ExpectedChar(closing, self.current_span()));
+                    }
+                    brackets.pop();
+                    buffer.push(self.bump());
+                }
+                ';' if brackets.is_empty() => break,
+                c if is_newline(c) && self.is_indented() => break,
+                'u' | 'U' => self.add_url_or_char(&mut buffer)?,
+                c => {
+                    self.bump();
+                    buffer.push(c);
+                }
+            }
+        }
+        if let Some(&closing) = brackets.last() {
+            return Err(Diagnostic::ExpectedChar(closing, self.current_span()));
+        }
+        if !allow_empty && buffer.is_empty() {
+            return Err(Diagnostic::Expected("token", self.current_span()));
+        }
+        buffer.trim_end();
+        Ok(buffer.finish(&self.ast, self.offset()))
+    }
+
+    /// Anything up to the end of a statement, such as the prelude of an unknown at-rule
+    pub(crate) fn almost_any_value(&mut self) -> Result<Interpolation<'a>> {
+        let start = self.offset();
+        let mut buffer = InterpolationBuffer::new(&self.ast, start);
+        let mut end = start;
+        loop {
+            match self.peek() {
+                _ if self.is_eof() => break,
+                ';' | '{' | '}' => break,
+                c if is_newline(c) && self.is_indented() => break,
+                '\\' => {
+                    buffer.push(self.bump());
+                    if !self.is_eof() {
+                        buffer.push(self.bump());
+                    }
+                }
+                '"' | '\'' => self.add_quoted_text(&mut buffer)?,
+                '/' if self.nth_char(1) == '*' => {
+                    let comment_start = self.offset();
+                    self.skip_loud_comment()?;
+                    buffer.push_str(self.text_from(comment_start));
+                }
+                '/' if self.nth_char(1) == '/' => {
+                    self.skip_silent_comment()?;
+                    continue;
+                }
+                '#' if self.nth_char(1) == '{' => self.add_interpolation_expression(&mut buffer)?,
+                'u' | 'U' => self.add_url_or_char(&mut buffer)?,
+                c if is_whitespace(c) => {
+                    self.bump();
+                    buffer.push(c);
+                    continue;
+                }
+                c => {
+                    self.bump();
+                    buffer.push(c);
+                }
+            }
+            end = self.offset();
+        }
+        buffer.trim_end();
+        Ok(buffer.finish(&self.ast, end))
+    }
+
+    /// Adds a quoted string to `buffer` as written, with its quotes.
+    fn add_quoted_text(&mut self, buffer: &mut InterpolationBuffer<'a>) -> Result<()> {
+        let quote = self.bump();
+        buffer.push(quote);
+        loop {
+            match self.peek() {
+                c if c == quote => {
+                    buffer.push(self.bump());
+                    return Ok(());
+                }
+                c if is_newline(c) || self.is_eof() => {
+                    return Err(Diagnostic::ExpectedChar(quote, self.current_span()));
+                }
+                '\\' => {
+                    buffer.push(self.bump());
+                    buffer.push(self.bump());
+                }
+                '#' if self.nth_char(1) == '{' => self.add_interpolation_expression(buffer)?,
+                c => {
+                    self.bump();
+                    buffer.push(c);
+                }
+            }
+        }
+    }
+
+    /// Adds an unquoted `url(...)` if one is next, or else the next character.
+    fn add_url_or_char(&mut self, buffer: &mut InterpolationBuffer<'a>) -> Result<()> {
+        let start = self.offset();
+        let before_url = self.checkpoint();
+        if self.scan_identifier("url") {
+            let name = self.text_from(start);
+            if let Some(contents) = self.try_url_contents(start, Some(name))? {
+                buffer.add_interpolation(&self.ast, contents);
+                return Ok(());
+            }
+            self.rewind(before_url);
+        }
+        buffer.push(self.bump());
+        Ok(())
+    }
+}
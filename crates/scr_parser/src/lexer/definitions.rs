@@ -33,13 +33,6 @@ pub fn is_ident(c: char) -> bool {
     is_ident_start(c) || is_dight(c) || c == '-'
 }
 
-/// non-printable code point
-/// a code point between U+0000 NULL and U+0008 BACKSPACE inclusive, or U+000B LINE TABULATION,
-/// or a code point between U+000E SHIFT OUT and U+001F INFORMATION SEPARATOR ONE inclusive, or U+007F DELETE.
-pub fn is_non_printable(c: char) -> bool {
-    (c >= '\x00' && c <= '\x08') || c == '\x0b' || (c >= '\x0e' && c <= '\x1f') || c == '\x7f'
-}
-
 /// newline
 /// U+000A LINE FEED. Note that U+000D CARRIAGE RETURN and U+000C FORM FEED are not included in this definition,
 /// as they are converted to U+000A LINE FEED during preprocessing.
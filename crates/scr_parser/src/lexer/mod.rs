@@ -1,10 +1,10 @@
-mod definitions;
+pub(crate) mod definitions;
 mod kind;
 mod number;
 mod string_builder;
 mod token;
 
-use std::{ops::Range, str::Chars};
+use std::str::Chars;
 
 use definitions::{
     is_dight, is_hex_digit, is_ident, is_ident_start, is_newline, is_whitespace, EOF_CHAR,
@@ -17,6 +17,8 @@ use scr_diagnostics::{Diagnostic, Diagnostics};
 use string_builder::AutoCow;
 use token::{Token, TokenValue};
 
+use crate::recovery::report;
+
 #[derive(Debug, Clone)]
 pub struct LexerCheckpoint<'a> {
     chars: Chars<'a>,
@@ -57,6 +59,23 @@ impl<'a> Lexer<'a> {
         self.current.chars.as_str()
     }
 
+    pub const fn source(&self) -> &'a str {
+        self.source
+    }
+
+    pub const fn source_type(&self) -> SourceType {
+        self.source_type
+    }
+
+    /// Saves the position, so the parser can look ahead and [`Lexer::rewind`] if it guessed wrong.
+    pub fn checkpoint(&self) -> LexerCheckpoint<'a> {
+        self.current.clone()
+    }
+
+    pub fn rewind(&mut self, checkpoint: LexerCheckpoint<'a>) {
+        self.current = checkpoint;
+    }
+
     pub fn next_token(&mut self) -> Token {
         let kind = self.read_next_token();
         self.finish_next(kind)
@@ -80,7 +99,7 @@ impl<'a> Lexer<'a> {
         std::mem::take(&mut self.current.token)
     }
 
-    fn is_eof(&self) -> bool {
+    pub(crate) fn is_eof(&self) -> bool {
         self.current.chars.as_str().is_empty()
     }
 
@@ -91,20 +110,21 @@ impl<'a> Lexer<'a> {
     }
 
     #[inline]
-    fn offset(&self) -> usize {
-        self.source.len() - self.current.chars.as_str().len()
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn offset(&self) -> u32 {
+        (self.source.len() - self.current.chars.as_str().len()) as u32
     }
 
     #[inline]
-    fn peek(&self) -> char {
+    pub(crate) fn peek(&self) -> char {
         self.nth_char(0)
     }
 
-    fn nth_char(&self, n: usize) -> char {
+    pub(crate) fn nth_char(&self, n: usize) -> char {
         self.current.chars.clone().nth(n).unwrap_or(EOF_CHAR)
     }
 
-    fn bump(&mut self) -> Option<char> {
+    pub(crate) fn bump(&mut self) -> Option<char> {
         let c = self.current.chars.next()?;
 
         #[cfg(debug_assertions)]
@@ -116,7 +136,7 @@ impl<'a> Lexer<'a> {
     }
 
     #[inline]
-    fn next_eq(&mut self, c: char) -> bool {
+    pub(crate) fn next_eq(&mut self, c: char) -> bool {
         let eq = self.peek() == c;
         if eq {
             self.bump();
@@ -124,7 +144,7 @@ impl<'a> Lexer<'a> {
         eq
     }
 
-    fn eat_while(&mut self, mut predicate: impl FnMut(char) -> bool) {
+    pub(crate) fn eat_while(&mut self, mut predicate: impl FnMut(char) -> bool) {
         while predicate(self.peek()) && !self.is_eof() {
             self.bump();
         }
@@ -134,27 +154,16 @@ impl<'a> Lexer<'a> {
         TokenValue::String(Atom::from(s))
     }
 
-    fn unterminated_range(&self) -> Range<usize> {
-        self.current.token.start..self.offset()
+    fn unterminated_range(&self) -> Span {
+        Span::new(self.current.token.start, self.offset())
     }
 
     fn error(&mut self, error: Diagnostic) {
-        self.errors.borrow_mut().push(error)
+        report(&self.errors, error);
     }
 
     fn current_offset(&self) -> Span {
-        self.offset()..self.offset()
-    }
-
-    fn unexpected_err(&mut self) {
-        let c = self.peek();
-        let error = if c == EOF_CHAR {
-            Diagnostic::UnexpectedEnd(self.current_offset())
-        } else {
-            Diagnostic::InvalidCharacter(c, self.current_offset())
-        };
-
-        self.error(error)
+        Span::empty(self.offset())
     }
 }
 
@@ -314,7 +323,7 @@ impl<'a> Lexer<'a> {
         match parse(src).map(TokenValue::Number) {
             Ok(val) => self.current.token.value = val,
             Err(err) => {
-                self.error(Diagnostic::InvalidNumber(err, self.current.token.start..self.offset()));
+                self.error(Diagnostic::InvalidNumber(err, self.unterminated_range()));
                 self.current.token.value = TokenValue::Number(std::f64::NAN);
             }
         }
@@ -23,23 +23,6 @@ impl<'a> AutoCow<'a> {
         }
     }
 
-    // Push a different character than lexer.chars().next().
-    // force_allocation_without_current_ascii_char must be called before this.
-    pub fn push_different(&mut self, c: char) {
-        debug_assert!(self.value.is_some());
-        self.value.as_mut().unwrap().push(c);
-    }
-
-    // Force allocation of a String, excluding the current ASCII character,
-    // and return the reference to it
-    pub fn get_mut_string_without_current_ascii_char<'b>(
-        &'b mut self,
-        lexer: &'_ Lexer<'a>,
-    ) -> &'b mut String<'a> {
-        self.force_allocation_without_current_ascii_char(lexer);
-        self.value.as_mut().unwrap()
-    }
-
     // Force allocation of a String, excluding the current ASCII character.
     pub fn force_allocation_without_current_ascii_char(&mut self, lexer: &'_ Lexer<'a>) {
         if self.value.is_some() {
@@ -63,12 +46,4 @@ impl<'a> AutoCow<'a> {
             None => &self.start[..self.start.len() - lexer.remaining().len()],
         }
     }
-
-    // Just like finish, but without pushing current char.
-    pub fn finish_without_push(mut self, lexer: &Lexer<'a>) -> &'a str {
-        match self.value.take() {
-            Some(s) => s.into_bump_str(),
-            None => &self.start[..self.start.len() - lexer.remaining().len() - 1],
-        }
-    }
 }
@@ -1,6 +1,4 @@
-use std::ops::Range;
-
-use scr_ast::Atom;
+use scr_ast::{Atom, Span};
 
 use super::Kind;
 
@@ -9,9 +7,9 @@ pub struct Token {
     /// Token kind
     pub kind: Kind,
     /// start offset in source file
-    pub start: usize,
+    pub start: u32,
     /// end offset if source file
-    pub end: usize,
+    pub end: u32,
 
     /// Is the origin string escape?
     pub escaped: bool,
@@ -21,8 +19,8 @@ pub struct Token {
 
 impl Token {
     #[must_use]
-    pub fn range(&self) -> Range<usize> {
-        self.start..self.end
+    pub const fn span(&self) -> Span {
+        Span::new(self.start, self.end)
     }
 }
 
@@ -1,5 +1,692 @@
+//! Sass parser
+//!
+//! A recursive-descent parser ported from Dart Sass's `StylesheetParser`. Sass is whitespace- and
+//! interpolation-sensitive at the character level, so the parser reads characters through the
+//! [`Lexer`] rather than tokens, and uses its checkpoints to look ahead.
+//!
+//! ```ignore
+//! let allocator = Allocator::default();
+//! let ret = Parser::new(&allocator, "a { b: c }", SourceType::default()).parse();
+//! assert!(ret.errors.is_empty());
+//! ```
+
+mod at_rule;
+mod css;
+mod cursor;
+mod expression;
+mod fragment;
+mod interpolation;
 mod lexer;
+mod media;
+mod recovery;
+mod sass;
+mod sassdoc;
+mod scss;
+mod selector;
+mod statement;
+mod supports;
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use scr_allocator::Allocator;
+use scr_ast::{
+    context::{Context, WithContext},
+    node::Stylesheet,
+    AstBuilder, SourceType, Span,
+};
+use scr_diagnostics::{Diagnostic, Diagnostics};
+
+pub use crate::{
+    fragment::FragmentReturn,
+    lexer::{Kind, Lexer},
+    recovery::{MAX_DEPTH, MAX_ERRORS},
+};
+
+pub struct ParserReturn<'a> {
+    pub stylesheet: Stylesheet<'a>,
+    pub errors: Vec<Diagnostic>,
+    /// Problems that don't stop the source from compiling, such as a malformed `SassDoc`
+    /// annotation
+    pub warnings: Vec<Diagnostic>,
+    /// Whether the parser panicked, `stylesheet` is empty in that case.
+    pub panicked: bool,
+}
+
+pub struct Parser<'a> {
+    lexer: Lexer<'a>,
+
+    ast: AstBuilder<'a>,
+
+    errors: Diagnostics,
+
+    warnings: Diagnostics,
+
+    context: Context,
+
+    /// Whether `@use` may still appear, i.e. only `@use`, `@forward` and variables came before.
+    use_allowed: bool,
+
+    /// Whether the mixin being parsed contains `@content`
+    mixin_has_content: bool,
+
+    /// The indentation of the current line in the indented syntax
+    indentation: u32,
+
+    /// Whether the indented syntax is indented with spaces rather than tabs, decided by the first
+    /// indented line
+    indent_with_spaces: Option<bool>,
+
+    /// Whether the expression being parsed is in parentheses, where `/` is always division
+    in_parentheses: bool,
+
+    /// How many blocks and expressions enclose the one being parsed, up to [`MAX_DEPTH`]
+    depth: u32,
+
+    /// Where the source starts in the file it was taken from, which every span is relative to
+    base_offset: u32,
+
+    /// The silent comment right before the statement being parsed, which documents it if it's a
+    /// mixin, function, variable or placeholder rule
+    doc_comment: Option<Span>,
+}
+
+impl WithContext for Parser<'_> {
+    fn context_mut(&mut self) -> &mut Context {
+        &mut self.context
+    }
+}
+
+impl<'a> Parser<'a> {
+    #[must_use]
+    pub fn new(allocator: &'a Allocator, source: &'a str, source_type: SourceType) -> Self {
+        let errors = Diagnostics::default();
+        let lexer = Lexer::new(allocator, source, errors.clone(), source_type);
+        Self {
+            lexer,
+            ast: AstBuilder::new(allocator),
+            errors,
+            warnings: Diagnostics::default(),
+            context: if source_type.is_css() { Context::PLAIN_CSS } else { Context::default() },
+            use_allowed: true,
+            mixin_has_content: false,
+            indentation: 0,
+            indent_with_spaces: None,
+            in_parentheses: false,
+            depth: 0,
+            base_offset: 0,
+            doc_comment: None,
+        }
+    }
+
+    /// Offsets every span by `offset`, for a source that was taken from a larger file such as a
+    /// selector in a stylesheet.
+    #[must_use]
+    pub const fn with_base_offset(mut self, offset: u32) -> Self {
+        self.base_offset = offset;
+        self
+    }
+
+    /// Parses the whole source as a stylesheet.
+    ///
+    /// A panic inside the parser is caught and reported as [`Diagnostic::Panic`], so one bad file
+    /// doesn't take down a whole compilation.
+    #[must_use]
+    pub fn parse(mut self) -> ParserReturn<'a> {
+        let result = catch_unwind(AssertUnwindSafe(|| self.parse_stylesheet()));
+        let panicked = result.is_err();
+        let stylesheet = result.unwrap_or_else(|_| {
+            let span = self.source_span();
+            self.errors.borrow_mut().push(Diagnostic::Panic(span));
+            self.ast.stylesheet(span, self.ast.new_vec())
+        });
+        let errors = std::mem::take(&mut *self.errors.borrow_mut());
+        let warnings = std::mem::take(&mut *self.warnings.borrow_mut());
+        ParserReturn { stylesheet, errors, warnings, panicked }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    const fn source_span(&self) -> Span {
+        Span::new(self.base_offset, self.base_offset + self.lexer.source().len() as u32)
+    }
+
+    /// Reports `error`, up to [`MAX_ERRORS`] of them followed by [`Diagnostic::TooManyErrors`].
+    fn error(&self, error: Diagnostic) {
+        recovery::report(&self.errors, error);
+    }
+
+    /// Reports `warning`, which doesn't count towards [`MAX_ERRORS`].
+    fn warn(&self, warning: Diagnostic) {
+        self.warnings.borrow_mut().push(warning);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::thread;
+
+    use scr_allocator::{Allocator, AllocatorGuard};
+    use scr_ast::{
+        node::{BinaryOperator, DocAnnotation, Expression, MediaCondition, Statement},
+        SourceType, Span, Syntax,
+    };
+    use scr_diagnostics::Diagnostic;
+
+    use super::{Parser, MAX_ERRORS};
+
+    #[test]
+    fn smoke() {
+        let allocator = Allocator::default();
+        let source = "
+@use 'sass:math';
+$gap: math.div(10px, 2) !default;
+// comment
+.a, .b > c:hover {
+  color: red;
+  &-active { margin: $gap * 2 auto; }
+  font: { family: serif; }
+}
+@mixin m($a, $b: 1) { @content; }
+@include m(1, $b: 2) { d: e; }
+@media screen and (min-width: 100px) { a { b: c } }
+";
+        let ret = Parser::new(&allocator, source, SourceType::default()).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        assert!(!ret.panicked);
+        let kinds = ret.stylesheet.body.iter().map(|it| match it {
+            Statement::Use(_) => "use",
+            Statement::VariableDeclaration(_) => "variable",
+            Statement::SilentComment(_) => "comment",
+            Statement::StyleRule(_) => "rule",
+            Statement::Mixin(_) => "mixin",
+            Statement::Include(_) => "include",
+            Statement::Media(_) => "media",
+            _ => "other",
+        });
+        assert_eq!(
+            kinds.collect::<Vec<_>>(),
+            ["use", "variable", "comment", "rule", "mixin", "include", "media"]
+        );
+    }
+
+    #[test]
+    fn parses_in_parallel() {
+        let sources = ["a { b: c }", "@use 'x';\n$d: 1;", "e { f: g; h { i: j } }\nk {}"];
+        // Each thread parses into its own arena, and only the results leave it.
+        let counts = thread::scope(|scope| {
+            sources
+                .map(|source| {
+                    scope.spawn(move || {
+                        let allocator = AllocatorGuard::new();
+                        let ret = Parser::new(&allocator, source, SourceType::default()).parse();
+                        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+                        ret.stylesheet.body.len()
+                    })
+                })
+                .map(|handle| handle.join().unwrap())
+        });
+        assert_eq!(counts, [1, 2, 2]);
+    }
+
+    #[test]
+    fn reports_errors() {
+        let allocator = Allocator::default();
+        for source in [
+            "a { b: c",
+            "a { b: (c; }",
+            "@return 1;",
+            "}",
+            "$a: 1 !bogus;",
+            "a|=b",
+            "@function f() { a { b: c } }",
+        ] {
+            let ret = Parser::new(&allocator, source, SourceType::default()).parse();
+            assert_eq!(ret.errors.len(), 1, "{source}");
+        }
+    }
+
+    #[test]
+    fn recovers_from_errors() {
+        let allocator = Allocator::default();
+        let source = "
+a { b: ; c: d }
+@include ;
+e { f: g(; }
+}
+@if $x {
+  h { i: j }
+";
+        let ret = Parser::new(&allocator, source, SourceType::default()).parse();
+        assert_eq!(ret.errors.len(), 5, "{:?}", ret.errors);
+        let kinds = ret.stylesheet.body.iter().map(|it| match it {
+            Statement::StyleRule(rule) => match rule.body.last() {
+                Some(Statement::Invalid(_)) => "rule with invalid",
+                _ => "rule",
+            },
+            Statement::Invalid(_) => "invalid",
+            Statement::If(_) => "if",
+            _ => "other",
+        });
+        assert_eq!(kinds.collect::<Vec<_>>(), ["rule", "invalid", "rule with invalid", "if"]);
+
+        for source_type in [SourceType::default(), SourceType::new(Syntax::Css)] {
+            let ret = Parser::new(&allocator, "a { b|=c }\nd { e: f }", source_type).parse();
+            assert_eq!(ret.errors.len(), 1, "{:?}", ret.errors);
+            assert_eq!(ret.stylesheet.body.len(), 2);
+        }
+
+        let source = "a { b: ; }\n".repeat(MAX_ERRORS * 2);
+        let ret = Parser::new(&allocator, &source, SourceType::default()).parse();
+        assert_eq!(ret.errors.len(), MAX_ERRORS + 1);
+    }
+
+    #[test]
+    fn limits_nesting() {
+        let allocator = Allocator::default();
+        for source in [
+            format!("$a: {}1{};", "(".repeat(1000), ")".repeat(1000)),
+            format!("{}{}", "a {".repeat(1000), "}".repeat(1000)),
+        ] {
+            let ret = Parser::new(&allocator, &source, SourceType::default()).parse();
+            assert!(
+                matches!(ret.errors.as_slice(), [Diagnostic::NestingTooDeep(_)]),
+                "{:?}",
+                ret.errors
+            );
+        }
+    }
+
+    #[test]
+    fn indented_syntax() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::new(Syntax::Sass);
+        let source = "
+@use 'sass:math'
+$gap: math.div(10px, 2) !default
+// comment
+  continued
+.a, .b > c:hover,
+.d
+  color: red
+  &-active
+    margin: $gap * 2 auto
+  font:
+    family: serif
+=m($a, $b: 1)
+  @content
++m(1, $b: 2)
+  d: e
+@import foo, bar.css
+";
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let kinds = ret.stylesheet.body.iter().map(|it| match it {
+            Statement::Use(_) => "use",
+            Statement::VariableDeclaration(_) => "variable",
+            Statement::SilentComment(_) => "comment",
+            Statement::StyleRule(rule) => {
+                assert_eq!(rule.selector.selectors.len(), 3);
+                assert_eq!(rule.body.len(), 3);
+                "rule"
+            }
+            Statement::Mixin(_) => "mixin",
+            Statement::Include(_) => "include",
+            Statement::Import(_) => "import",
+            _ => "other",
+        });
+        assert_eq!(
+            kinds.collect::<Vec<_>>(),
+            ["use", "variable", "comment", "rule", "mixin", "include", "import"]
+        );
+
+        for source in
+            ["  a", "a\n  b: c\n d: e", "$a: 1\n  b", "$a: 1; $b: 2", "a\n\tb: c\nd\n  e: f"]
+        {
+            let ret = Parser::new(&allocator, source, source_type).parse();
+            assert_eq!(ret.errors.len(), 1, "{source}");
+        }
+    }
+
+    #[test]
+    fn declarations_or_rules() {
+        let allocator = Allocator::default();
+        let source = "a {
+  b:hover { c: d }
+  font: { family: x; }
+  font: 12px/1.5 { weight: bold }
+  e:f;
+  --g:h i;
+  j:k l {}
+  *zoom: 1;
+  :m {}
+  n:not(.o) {}
+  p:nth-child(2n+1) {}
+  lib.$q: 1;
+}";
+        let ret = Parser::new(&allocator, source, SourceType::default()).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let Statement::StyleRule(rule) = &ret.stylesheet.body[0] else { unreachable!() };
+        let kinds = rule.body.iter().map(|it| match it {
+            Statement::StyleRule(_) => "rule",
+            Statement::Declaration(declaration) if declaration.body.is_some() => "nested",
+            Statement::Declaration(_) => "declaration",
+            Statement::VariableDeclaration(_) => "variable",
+            _ => "other",
+        });
+        assert_eq!(
+            kinds.collect::<Vec<_>>(),
+            [
+                "rule",
+                "nested",
+                "nested",
+                "declaration",
+                "declaration",
+                "rule",
+                "declaration",
+                "rule",
+                "rule",
+                "rule",
+                "variable"
+            ]
+        );
+
+        for source in ["a { b:c(; }", "a:b;", "a { b: c; d }"] {
+            let ret = Parser::new(&allocator, source, SourceType::default()).parse();
+            assert_eq!(ret.errors.len(), 1, "{source}");
+        }
+    }
+
+    /// `expression` as a Lisp-like string, with `/` written as `slash` where it may be one
+    fn shape(expression: &Expression) -> String {
+        match expression {
+            Expression::Binary(binary) => {
+                let operator = match binary.operator {
+                    BinaryOperator::DividedBy if binary.allows_slash => "slash",
+                    operator => operator.as_str(),
+                };
+                format!("({operator} {} {})", shape(&binary.left), shape(&binary.right))
+            }
+            Expression::Unary(unary) => {
+                format!("({}{})", unary.operator.as_str(), shape(&unary.argument))
+            }
+            Expression::List(list) => {
+                let elements = list.elements.iter().map(shape).collect::<Vec<_>>();
+                format!("[{}]", elements.join(" "))
+            }
+            Expression::Number(number) => number.value.to_string(),
+            Expression::Parenthesized(parenthesized) => shape(&parenthesized.expression),
+            Expression::String(string) => string.text.as_plain().unwrap().to_owned(),
+            Expression::Variable(variable) => format!("${}", variable.name),
+            Expression::Function(function) => {
+                let arguments = function.arguments.positional.iter().map(shape);
+                format!("{}({})", function.name, arguments.collect::<Vec<_>>().join(", "))
+            }
+            _ => "?".to_owned(),
+        }
+    }
+
+    #[test]
+    fn expressions() {
+        let allocator = Allocator::default();
+        for (source, expected) in [
+            ("a -b", "[a -b]"),
+            ("a - b", "(- a b)"),
+            ("a-b", "a-b"),
+            ("1-2", "(- 1 2)"),
+            ("1 -2", "[1 -2]"),
+            ("1 - 2", "(- 1 2)"),
+            ("1 -$a", "(- 1 $a)"),
+            ("1 +2", "(+ 1 2)"),
+            ("- $a", "(-$a)"),
+            ("1/2", "(slash 1 2)"),
+            ("1/2/3", "(slash (slash 1 2) 3)"),
+            ("1/2 + 3", "(+ (/ 1 2) 3)"),
+            ("$a/2", "(/ $a 2)"),
+            ("(1/2)", "(/ 1 2)"),
+            ("(1/2 3)", "[(slash 1 2) 3]"),
+            ("(1 2/3)", "[1 (slash 2 3)]"),
+            ("f(1/2)", "f((slash 1 2))"),
+            ("(f(1/2))", "f((/ 1 2))"),
+            ("alpha(opacity=50)", "alpha((= opacity 50))"),
+            ("1 + 2 * 3", "(+ 1 (* 2 3))"),
+            ("not a == b", "(== (nota) b)"),
+            ("a or b and c == d", "(or a (and b (== c d)))"),
+        ] {
+            let source = format!("$x: {source};");
+            let ret = Parser::new(&allocator, &source, SourceType::default()).parse();
+            assert!(ret.errors.is_empty(), "{source}: {:?}", ret.errors);
+            let Statement::VariableDeclaration(declaration) = &ret.stylesheet.body[0] else {
+                unreachable!()
+            };
+            assert_eq!(shape(&declaration.value), expected, "{source}");
+        }
+
+        let ret = Parser::new(&allocator, "@include m(a=b);", SourceType::default()).parse();
+        assert_eq!(ret.errors.len(), 1);
+    }
+
+    #[test]
+    fn fragments() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default();
+
+        let ret = Parser::new(&allocator, " a > b, c:hover ", source_type).parse_selector();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let selector = ret.fragment.unwrap();
+        assert_eq!((selector.selectors.len(), selector.span), (2, Span::new(1, 15)));
+
+        let ret = Parser::new(&allocator, "1px solid", source_type)
+            .with_base_offset(10)
+            .parse_expression();
+        assert_eq!(ret.fragment.unwrap().span(), Span::new(10, 19));
+
+        let ret = Parser::new(&allocator, "a, b {", source_type)
+            .with_base_offset(10)
+            .parse_media_query_list();
+        assert!(ret.fragment.is_none());
+        assert!(matches!(ret.errors[..], [Diagnostic::Expected(_, span)] if span.start == 15));
+
+        let ret = Parser::new(&allocator, "a; b", source_type).parse_declaration_value();
+        assert_eq!(ret.errors.len(), 1);
+        let ret =
+            Parser::new(&allocator, "(a: b) and (c: d)", source_type).parse_supports_condition();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+    }
+
+    #[test]
+    fn plain_css() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::new(Syntax::Css);
+        let source = "
+@import 'vendor.css';
+:root { --gap: 1px; }
+a:hover, b > c { color: var(--x,); width: calc(100% - 10px); e: env(a, 0); f: rgb(0 0 0 / 50%); }
+@media (min-width: 100px) { a { b: c } }
+nav { color: red; > a:hover { b: c } & + & {} .x & {} @media (width > 1px) { d: e } }
+";
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        assert_eq!(ret.stylesheet.body.len(), 5);
+        let Some(Statement::StyleRule(nav)) = ret.stylesheet.body.last() else { unreachable!() };
+        let kinds = nav.body.iter().map(|it| match it {
+            Statement::Declaration(_) => "declaration",
+            Statement::StyleRule(_) => "rule",
+            Statement::Media(_) => "media",
+            _ => "other",
+        });
+        assert_eq!(kinds.collect::<Vec<_>>(), ["declaration", "rule", "rule", "rule", "media"]);
+
+        for source in [
+            "$a: 1;",
+            "a { b: $c }",
+            "@mixin m {}",
+            "// comment",
+            "a { &-b { c: d } }",
+            "a { b: c == d }",
+            "a { b: #{c} }",
+            "a { b: map-get(c, d) }",
+            "a { b: math.div(1, 2) }",
+        ] {
+            let ret = Parser::new(&allocator, source, source_type).parse();
+            assert_eq!(ret.errors.len(), 1, "{source}");
+        }
+    }
+
+    #[test]
+    fn css_at_rules() {
+        let allocator = Allocator::default();
+        let source = "
+@layer reset, theme.dark;
+@layer #{$name} { a { b: c } }
+@container card (min-width: 400px) and style(--dark: true) { a { b: c } }
+@container not style((--a) or (--b: 1 2)) { a { b: c } }
+@scope (.card) to (.content) { a { b: c } }
+@property --gap { syntax: '<length>'; inherits: false; }
+@starting-style { a { b: c } }
+@counter-style thumbs { system: cyclic; @include symbols; }
+@font-feature-values Font One, 'Two' { @swash { fancy: 1 } }
+@page wide:first, :left { margin: 1in; @top-left { content: 'a' } }
+@-webkit-keyframes spin { from { a: b } 50%, to { a: c } }
+";
+        let ret = Parser::new(&allocator, source, SourceType::default()).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let kinds = ret.stylesheet.body.iter().map(|it| match it {
+            Statement::Layer(rule) => match &rule.body {
+                Some(_) => "layer block",
+                None if rule.names.len() == 2 => "layer statement",
+                None => "other",
+            },
+            Statement::Container(rule) => match rule.condition {
+                Some(MediaCondition::Operation(_)) if rule.name.is_some() => "named container",
+                Some(MediaCondition::Not(_)) => "container",
+                _ => "other",
+            },
+            Statement::Scope(rule) if rule.root.is_some() && rule.limit.is_some() => "scope",
+            Statement::Property(_) => "property",
+            Statement::StartingStyle(_) => "starting-style",
+            Statement::CounterStyle(_) => "counter-style",
+            Statement::FontFeatureValues(rule) => match rule.body[..] {
+                [Statement::AtRule(_)] => "font-feature-values",
+                _ => "other",
+            },
+            Statement::Page(rule) => match rule.body[..] {
+                [Statement::Declaration(_), Statement::PageMargin(_)]
+                    if rule.selectors.len() == 2 =>
+                {
+                    "page"
+                }
+                _ => "other",
+            },
+            Statement::Keyframes(rule) if rule.vendor_prefix.as_deref() == Some("-webkit-") => {
+                "keyframes"
+            }
+            _ => "other",
+        });
+        assert_eq!(
+            kinds.collect::<Vec<_>>(),
+            [
+                "layer statement",
+                "layer block",
+                "named container",
+                "container",
+                "scope",
+                "property",
+                "starting-style",
+                "counter-style",
+                "font-feature-values",
+                "page",
+                "keyframes"
+            ]
+        );
+
+        let ret =
+            Parser::new(&allocator, "@property --a { b: c }", SourceType::new(Syntax::Css)).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+
+        for source in [
+            "@layer a, b { c { d: e } }",
+            "@property gap { syntax: '*' }",
+            "@counter-style a { b { c: d } }",
+            "@keyframes a { b { c: d } }",
+            "@page { a { b: c } }",
+        ] {
+            let ret = Parser::new(&allocator, source, SourceType::default()).parse();
+            assert_eq!(ret.errors.len(), 1, "{source}");
+        }
+    }
+
+    #[test]
+    fn sassdoc() {
+        let allocator = Allocator::default();
+        let source = "
+/// Spacing between items
+/// @type Number
+$gap: 1px;
+
+/// Centers its content.
+/// @access private
+/// @param {Length} $width [100%] - How wide
+///   the box is
+/// @return {Null}
+/// @example scss - Usage
+///   .a { @include center(10px); }
+/// @deprecated Use `flex` instead
+/// @see {function} size
+@mixin center($width: 100%) {}
+
+/// Not documentation
+a { b: c }
+@function size() { @return 1; }
 
-pub struct Parser;
+/// A placeholder
+%box { d: e }
+";
+        let ret = Parser::new(&allocator, source, SourceType::default()).parse();
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        let docs = ret
+            .stylesheet
+            .body
+            .iter()
+            .filter_map(|it| match it {
+                Statement::VariableDeclaration(it) => Some(&it.doc),
+                Statement::Mixin(it) => Some(&it.doc),
+                Statement::Function(it) => Some(&it.doc),
+                Statement::StyleRule(it) => Some(&it.doc),
+                _ => None,
+            })
+            .map(|doc| doc.as_ref().map(|doc| (doc.description, doc.annotations.len())))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            docs,
+            [
+                Some(("Spacing between items", 0)),
+                Some(("Centers its content.", 6)),
+                None,
+                None,
+                Some(("A placeholder", 0))
+            ]
+        );
+        let Some(Statement::Mixin(mixin)) = ret.stylesheet.body.get(3) else { unreachable!() };
+        let annotations = &mixin.doc.as_ref().unwrap().annotations;
+        let DocAnnotation::Param(param) = &annotations[1] else { unreachable!() };
+        assert_eq!(
+            (param.types, param.name.as_str(), param.default),
+            (Some("Length"), "width", Some("100%"))
+        );
+        assert_eq!(param.description, "How wide\n  the box is");
+        let DocAnnotation::Example(example) = &annotations[3] else { unreachable!() };
+        assert_eq!(example.code, "  .a { @include center(10px); }");
 
-pub use lexer::Lexer;
+        for source in [
+            "/// @param {Number\n@mixin a {}",
+            "/// @param - no name\n@mixin a {}",
+            "/// @access everyone\n$a: 1;",
+            "/// @example\n$a: 1;",
+            "/// @see\n$a: 1;",
+        ] {
+            let ret = Parser::new(&allocator, source, SourceType::default()).parse();
+            assert!(ret.errors.is_empty(), "{source}: {:?}", ret.errors);
+            assert_eq!(ret.warnings.len(), 1, "{source}");
+            assert_eq!(ret.stylesheet.body.len(), 2, "{source}");
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! Media queries
+//!
+//! <https://drafts.csswg.org/mediaqueries-4/#mq-syntax>
+
+use scr_ast::{
+    node::{
+        Expression, MediaComparison, MediaCondition, MediaOperator, MediaQuery, MediaQueryList,
+    },
+    Atom,
+};
+use scr_diagnostics::{Diagnostic, Result};
+
+use crate::{interpolation::InterpolationBuffer, Parser};
+
+impl<'a> Parser<'a> {
+    /// `screen and (min-width: 100px), print`
+    pub(crate) fn media_query_list(&mut self) -> Result<MediaQueryList<'a>> {
+        let start = self.offset();
+        let mut queries = self.ast.new_vec();
+        loop {
+            self.whitespace()?;
+            queries.push(self.media_query()?);
+            let checkpoint = self.checkpoint();
+            self.whitespace()?;
+            if !self.next_eq(',') {
+                self.rewind(checkpoint);
+                break;
+            }
+        }
+        Ok(self.ast.media_query_list(self.span_from(start), queries))
+    }
+
+    /// `[not | only] type [and condition]`, or a bare condition
+    fn media_query(&mut self) -> Result<MediaQuery<'a>> {
+        let start = self.offset();
+        if self.peek() == '(' || self.looking_at_negated_condition() {
+            let condition = self.media_condition(true, Self::media_in_parens)?;
+            return Ok(self.ast.media_query(self.span_from(start), None, None, Some(condition)));
+        }
+
+        let first = self.interpolated_identifier()?;
+        let modifier = first
+            .as_plain()
+            .filter(|name| name.eq_ignore_ascii_case("not") || name.eq_ignore_ascii_case("only"))
+            .map(Atom::from);
+        let media_type = if modifier.is_some() {
+            self.whitespace()?;
+            self.interpolated_identifier()?
+        } else {
+            first
+        };
+
+        let checkpoint = self.checkpoint();
+        self.whitespace()?;
+        let condition = if self.scan_identifier("and") {
+            self.whitespace()?;
+            Some(self.media_condition(false, Self::media_in_parens)?)
+        } else {
+            self.rewind(checkpoint);
+            None
+        };
+        Ok(self.ast.media_query(self.span_from(start), modifier, Some(media_type), condition))
+    }
+
+    /// Whether `not (` is next, as opposed to `not screen`
+    pub(crate) fn looking_at_negated_condition(&mut self) -> bool {
+        let checkpoint = self.checkpoint();
+        let found = self.scan_identifier("not") && self.whitespace().is_ok() && self.peek() == '(';
+        self.rewind(checkpoint);
+        found
+    }
+
+    /// `not (a)`, `(a) and (b)`, or `(a) or (b)` if `allow_or`, with `in_parens` parsing each
+    /// `(a)`
+    pub(crate) fn media_condition(
+        &mut self,
+        allow_or: bool,
+        in_parens: fn(&mut Self) -> Result<MediaCondition<'a>>,
+    ) -> Result<MediaCondition<'a>> {
+        let start = self.offset();
+        if self.scan_identifier("not") {
+            self.whitespace()?;
+            let condition = in_parens(self)?;
+            return Ok(self.ast.media_condition_not(self.span_from(start), condition));
+        }
+        let first = in_parens(self)?;
+        let checkpoint = self.checkpoint();
+        self.whitespace()?;
+        let operator = if self.scan_identifier("and") {
+            MediaOperator::And
+        } else if allow_or && self.scan_identifier("or") {
+            MediaOperator::Or
+        } else {
+            self.rewind(checkpoint);
+            return Ok(first);
+        };
+        let mut conditions = self.ast.new_vec();
+        conditions.push(first);
+        loop {
+            self.whitespace()?;
+            conditions.push(in_parens(self)?);
+            let checkpoint = self.checkpoint();
+            self.whitespace()?;
+            if !self.scan_identifier(operator.as_str()) {
+                self.rewind(checkpoint);
+                break;
+            }
+        }
+        Ok(self.ast.media_condition_operation(self.span_from(start), operator, conditions))
+    }
+
+    /// `(feature)`, `(feature: value)`, a range, a nested condition or `#{...}`
+    pub(crate) fn media_in_parens(&mut self) -> Result<MediaCondition<'a>> {
+        let start = self.offset();
+        if self.looking_at_interpolation() {
+            let mut buffer = InterpolationBuffer::new(&self.ast, start);
+            self.add_interpolation_expression(&mut buffer)?;
+            let interpolation = buffer.finish(&self.ast, self.offset());
+            return Ok(MediaCondition::Interpolation(self.ast.alloc(interpolation)));
+        }
+        self.expect_char('(')?;
+        self.whitespace()?;
+        if self.peek() == '(' || self.looking_at_negated_condition() {
+            let condition = self.media_condition(true, Self::media_in_parens)?;
+            self.whitespace()?;
+            self.expect_char(')')?;
+            return Ok(condition);
+        }
+
+        let left = if self.looking_at_interpolated_identifier() {
+            let name = self.interpolated_identifier()?;
+            self.whitespace()?;
+            if self.next_eq(')') {
+                return Ok(self.ast.media_condition_feature(self.span_from(start), name, None));
+            }
+            if self.next_eq(':') {
+                self.whitespace()?;
+                let value = self.expression()?;
+                self.whitespace()?;
+                self.expect_char(')')?;
+                let span = self.span_from(start);
+                return Ok(self.ast.media_condition_feature(span, name, Some(value)));
+            }
+            self.ast.expression_string(name.span, name, false)
+        } else {
+            self.media_range_operand()?
+        };
+
+        let left_comparison = self
+            .scan_media_comparison()
+            .ok_or_else(|| Diagnostic::Expected("\":\" or a comparison", self.current_span()))?;
+        self.whitespace()?;
+        let middle = self.media_range_operand()?;
+        let right_comparison = self.scan_media_comparison();
+        let right = if right_comparison.is_some() {
+            self.whitespace()?;
+            Some(self.media_range_operand()?)
+        } else {
+            None
+        };
+        self.expect_char(')')?;
+        let span = self.span_from(start);
+        Ok(self.ast.media_condition_range(
+            span,
+            left,
+            left_comparison,
+            middle,
+            right_comparison,
+            right,
+        ))
+    }
+
+    /// An expression in a range, which stops before comparisons rather than comparing
+    fn media_range_operand(&mut self) -> Result<Expression<'a>> {
+        let expression = self.expression_until(Some(|p| matches!(p.peek(), '<' | '>' | '=')))?;
+        self.whitespace()?;
+        Ok(expression)
+    }
+
+    fn scan_media_comparison(&mut self) -> Option<MediaComparison> {
+        let comparison = match (self.peek(), self.nth_char(1)) {
+            ('<', '=') => MediaComparison::LessThanOrEqual,
+            ('<', _) => MediaComparison::LessThan,
+            ('>', '=') => MediaComparison::GreaterThanOrEqual,
+            ('>', _) => MediaComparison::GreaterThan,
+            ('=', _) => MediaComparison::Equal,
+            _ => return None,
+        };
+        for _ in 0..comparison.as_str().len() {
+            self.bump();
+        }
+        Some(comparison)
+    }
+}
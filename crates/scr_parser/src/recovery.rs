@@ -0,0 +1,162 @@
+//! Recovering from syntax errors, so that a half-typed file still has an AST and all its errors
+//!
+//! When a statement fails to parse, its error is reported and the parser skips to where the next
+//! statement probably starts: past a `;` or a block, before the `}` closing the enclosing block,
+//! or before an at-rule at the start of a line. In the indented syntax, it skips the rest of the
+//! line and the lines indented beneath it. The skipped source becomes an
+//! [`InvalidStatement`](scr_ast::node::InvalidStatement).
+//!
+//! Recovery only runs once a statement's parser returns, so the loops inside statements stop with
+//! an error when an iteration consumes nothing, rather than repeating it forever.
+
+use std::cmp::Ordering;
+
+use scr_ast::node::Statement;
+use scr_diagnostics::{Diagnostic, Diagnostics, Result};
+
+use crate::{
+    lexer::definitions::{is_newline, is_whitespace},
+    Parser,
+};
+
+/// How many errors are reported before the rest of the source is skipped
+pub const MAX_ERRORS: usize = 100;
+
+/// How deeply blocks and expressions may nest, low enough that deeply nested source can't
+/// overflow the 2 MiB stack of a spawned thread
+pub const MAX_DEPTH: u32 = 128;
+
+/// Adds `error` to `errors`, up to [`MAX_ERRORS`] of them followed by
+/// [`Diagnostic::TooManyErrors`], for both the parser and the lexer.
+pub fn report(errors: &Diagnostics, error: Diagnostic) {
+    let mut errors = errors.borrow_mut();
+    match errors.len().cmp(&MAX_ERRORS) {
+        Ordering::Less => errors.push(error),
+        Ordering::Equal => errors.push(Diagnostic::TooManyErrors),
+        Ordering::Greater => {}
+    }
+}
+
+impl<'a> Parser<'a> {
+    /// Reports `error` in the statement that started at `start` on a line indented by
+    /// `indentation`, and skips past the statement.
+    ///
+    /// At least one character is skipped, so a statement that keeps failing can't loop.
+    pub(crate) fn recover(
+        &mut self,
+        start: u32,
+        indentation: u32,
+        error: Diagnostic,
+    ) -> Statement<'a> {
+        self.error(error);
+        if self.has_too_many_errors() {
+            self.lexer.eat_while(|_| true);
+        } else if self.is_indented() {
+            self.indentation = indentation;
+            self.indented_synchronize();
+        } else {
+            self.synchronize();
+        }
+        if self.offset() == start && !self.is_eof() {
+            self.bump();
+        }
+        self.ast.statement_invalid(self.span_from(start))
+    }
+
+    /// Runs `f` one level of nesting deeper, or fails with [`Diagnostic::NestingTooDeep`] at
+    /// [`MAX_DEPTH`].
+    pub(crate) fn nested<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        if self.depth == MAX_DEPTH {
+            return Err(Diagnostic::NestingTooDeep(self.current_span()));
+        }
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Whether the error cap was reached, after which nothing more is parsed or reported
+    pub(crate) fn has_too_many_errors(&self) -> bool {
+        self.errors.borrow().len() > MAX_ERRORS
+    }
+
+    fn synchronize(&mut self) {
+        let (mut braces, mut parentheses) = (0_usize, 0_usize);
+        let mut line_start = false;
+        while !self.is_eof() {
+            match self.peek() {
+                ';' if braces == 0 && parentheses == 0 => {
+                    self.bump();
+                    return;
+                }
+                '}' if braces == 0 => return,
+                '}' => {
+                    self.bump();
+                    braces -= 1;
+                    if braces == 0 {
+                        return;
+                    }
+                    continue;
+                }
+                '@' if braces == 0 && line_start => return,
+                '{' => braces += 1,
+                '(' | '[' => parentheses += 1,
+                ')' | ']' => parentheses = parentheses.saturating_sub(1),
+                '"' | '\'' => {
+                    self.skip_string_for_recovery();
+                    line_start = false;
+                    continue;
+                }
+                '/' if self.nth_char(1) == '/' && !self.context.is_plain_css() => {
+                    self.lexer.eat_while(|c| !is_newline(c));
+                    continue;
+                }
+                '/' if self.nth_char(1) == '*' => {
+                    self.bump();
+                    self.bump();
+                    while !self.is_eof() {
+                        if self.bump() == '*' && self.next_eq('/') {
+                            break;
+                        }
+                    }
+                    continue;
+                }
+                '\\' => {
+                    self.bump();
+                }
+                _ => {}
+            }
+            let c = self.bump();
+            if is_newline(c) {
+                line_start = true;
+            } else if !is_whitespace(c) {
+                line_start = false;
+            }
+        }
+    }
+
+    /// Skips a quoted string, which ends at its closing quote or the end of the line.
+    fn skip_string_for_recovery(&mut self) {
+        let quote = self.bump();
+        while !self.is_eof() {
+            match self.bump() {
+                c if c == quote => return,
+                '\\' => {
+                    self.bump();
+                }
+                c if is_newline(c) => return,
+                _ => {}
+            }
+        }
+    }
+
+    fn indented_synchronize(&mut self) {
+        loop {
+            self.lexer.eat_while(|c| !is_newline(c));
+            if self.is_eof() || !self.indented_looking_at_children() {
+                return;
+            }
+            self.skip_newline();
+        }
+    }
+}
@@ -0,0 +1,296 @@
+//! How statements and blocks are delimited in the indented syntax, like Dart Sass's `SassParser`
+//!
+//! Statements end at newlines and a block is the lines indented beneath its statement, so the
+//! parser remembers the indentation of the current line and peeks at the next one.
+
+use scr_allocator::Vec;
+use scr_ast::{
+    node::{Import, Statement},
+    Span,
+};
+use scr_diagnostics::{Diagnostic, Result};
+
+use crate::{
+    interpolation::InterpolationBuffer, lexer::definitions::is_newline, scss::ChildParser,
+    statement::is_plain_import_url, Parser,
+};
+
+/// The whitespace at the start of a line
+pub struct Indentation {
+    width: u32,
+    span: Span,
+    tabs: bool,
+    spaces: bool,
+}
+
+impl Indentation {
+    const fn empty(offset: u32) -> Self {
+        Self { width: 0, span: Span::empty(offset), tabs: false, spaces: false }
+    }
+}
+
+impl<'a> Parser<'a> {
+    /// Whether the source is in the indented syntax
+    pub(crate) const fn is_indented(&self) -> bool {
+        self.lexer.source_type().is_sass()
+    }
+
+    pub(crate) fn indented_expect_statement_separator(&mut self) -> Result<()> {
+        self.whitespace_without_comments();
+        let semicolon = self.next_eq(';');
+        if semicolon {
+            self.whitespace_without_comments();
+        }
+        if !self.indented_at_end_of_statement() {
+            let span = self.current_span();
+            return Err(if semicolon {
+                Diagnostic::MultipleStatementsOnOneLine(span)
+            } else {
+                Diagnostic::Expected("newline", span)
+            });
+        }
+        let next = self.peek_indentation()?;
+        if next.width > self.indentation {
+            return Err(Diagnostic::NothingMayBeIndented(next.span));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn indented_at_end_of_statement(&self) -> bool {
+        self.is_eof() || is_newline(self.peek())
+    }
+
+    pub(crate) fn indented_looking_at_children(&mut self) -> bool {
+        self.indented_at_end_of_statement()
+            && self.peek_indentation().is_ok_and(|next| next.width > self.indentation)
+    }
+
+    /// Consumes `@else` if it starts the next line at the same indentation as its `@if`.
+    pub(crate) fn indented_scan_else(&mut self, if_indentation: u32) -> Result<Option<u32>> {
+        if self.peek_indentation()?.width != if_indentation {
+            return Ok(None);
+        }
+        let checkpoint = self.checkpoint();
+        let indentation = self.indentation;
+        self.read_indentation()?;
+        let start = self.offset();
+        if self.scan_else_keyword() {
+            return Ok(Some(start));
+        }
+        self.rewind(checkpoint);
+        self.indentation = indentation;
+        Ok(None)
+    }
+
+    /// Consumes the lines indented beneath the current one, which must all be indented the same.
+    pub(crate) fn indented_children(
+        &mut self,
+        child: ChildParser<'a>,
+    ) -> Result<Vec<'a, Statement<'a>>> {
+        let parent = self.indentation;
+        let mut children = self.ast.new_vec();
+        let mut child_indentation = None;
+        while self.peek_indentation()?.width > parent {
+            let line = self.read_indentation()?;
+            let expected = *child_indentation.get_or_insert(line.width);
+            if line.width != expected {
+                return Err(Diagnostic::InconsistentIndentation(expected, line.span));
+            }
+            self.block_item(&mut children, child);
+        }
+        self.indentation = parent;
+        Ok(children)
+    }
+
+    /// Consumes every line of the source as a statement parsed by `child`.
+    pub(crate) fn indented_statements_into(
+        &mut self,
+        statements: &mut Vec<'a, Statement<'a>>,
+        child: ChildParser<'a>,
+    ) -> Result<()> {
+        if matches!(self.peek(), ' ' | '\t') {
+            let start = self.offset();
+            self.whitespace_without_comments();
+            return Err(Diagnostic::IndentedDocument(self.span_from(start)));
+        }
+        while !self.is_eof() {
+            if !is_newline(self.peek()) {
+                self.block_item(statements, child);
+            }
+            self.read_indentation()?;
+        }
+        Ok(())
+    }
+
+    /// Consumes a `//` comment along with the lines indented beneath it and any `//` lines that
+    /// directly follow it.
+    pub(crate) fn indented_silent_comment(&mut self) -> Result<Statement<'a>> {
+        let start = self.offset();
+        let parent = self.indentation;
+        loop {
+            self.lexer.eat_while(|c| !is_newline(c));
+            let next = self.peek_indentation()?;
+            let continues = next.width > parent
+                || (next.width == parent
+                    && self.lexer.source()[(next.span.end - self.base_offset) as usize..]
+                        .starts_with("//"));
+            if !continues {
+                break;
+            }
+            self.read_indentation()?;
+        }
+        self.indentation = parent;
+        Ok(self.ast.statement_silent_comment(self.span_from(start), self.text_from(start)))
+    }
+
+    /// Consumes a `/*` comment, which runs until `*/` or the end of the lines indented beneath
+    /// it. Each continuation line is written with a leading ` * `, and the closing `*/` is added
+    /// if it's missing.
+    pub(crate) fn indented_loud_comment(&mut self) -> Result<Statement<'a>> {
+        let start = self.offset();
+        let parent = self.indentation;
+        let mut buffer = InterpolationBuffer::new(&self.ast, start);
+        self.expect_char('/')?;
+        self.expect_char('*')?;
+        buffer.push_str("/*");
+        let mut first = true;
+        loop {
+            if first {
+                let line_start = self.offset();
+                self.whitespace_without_comments();
+                if is_newline(self.peek()) && self.peek_indentation()?.width > parent {
+                    self.read_indentation()?;
+                    buffer.push(' ');
+                } else {
+                    buffer.push_str(self.text_from(line_start));
+                }
+            } else {
+                buffer.push_str("\n * ");
+            }
+            first = false;
+            for _ in parent + 3..self.indentation {
+                buffer.push(' ');
+            }
+
+            while !self.indented_at_end_of_statement() {
+                match self.peek() {
+                    '#' if self.nth_char(1) == '{' => {
+                        self.add_interpolation_expression(&mut buffer)?;
+                    }
+                    '*' if self.nth_char(1) == '/' => {
+                        self.bump();
+                        self.bump();
+                        buffer.push_str("*/");
+                        let span = self.span_from(start);
+                        self.whitespace()?;
+                        if !self.indented_at_end_of_statement() {
+                            return Err(Diagnostic::TextAfterComment(self.current_span()));
+                        }
+                        self.indentation = parent;
+                        let text = buffer.finish(&self.ast, span.end);
+                        return Ok(self.ast.statement_loud_comment(span, text));
+                    }
+                    c => {
+                        self.bump();
+                        buffer.push(c);
+                    }
+                }
+            }
+            if self.peek_indentation()?.width <= parent {
+                break;
+            }
+            self.read_indentation()?;
+        }
+        buffer.push_str(" */");
+        self.indentation = parent;
+        let text = buffer.finish(&self.ast, self.offset());
+        Ok(self.ast.statement_loud_comment(self.span_from(start), text))
+    }
+
+    /// An `@import` URL without quotes, which runs to the next `,` or the end of the line
+    ///
+    /// Returns `None` for quoted URLs and `url(...)`, which are parsed as in SCSS.
+    pub(crate) fn try_unquoted_import_argument(&mut self) -> Option<Import<'a>> {
+        if matches!(self.peek(), '"' | '\'') {
+            return None;
+        }
+        let checkpoint = self.checkpoint();
+        let is_url = self.scan_identifier("url") && self.peek() == '(';
+        self.rewind(checkpoint);
+        if is_url {
+            return None;
+        }
+
+        let start = self.offset();
+        self.lexer.eat_while(|c| c != ',' && c != ';' && !is_newline(c));
+        let url = self.text_from(start).trim_end();
+        #[allow(clippy::cast_possible_truncation)]
+        let span = Span::new(start, start + url.len() as u32);
+        if is_plain_import_url(url) {
+            let url = self.ast.plain_interpolation(span, self.ast.str(&format!("\"{url}\"")));
+            return Some(self.ast.import_static(span, url, None));
+        }
+        Some(self.ast.import_dynamic(span, url))
+    }
+
+    /// The indentation of the next non-empty line, without consuming anything
+    fn peek_indentation(&mut self) -> Result<Indentation> {
+        let checkpoint = self.checkpoint();
+        let line = self.scan_indentation();
+        self.rewind(checkpoint);
+        line
+    }
+
+    /// Consumes the newlines and indentation before the next non-empty line, and makes it the
+    /// current line.
+    fn read_indentation(&mut self) -> Result<Indentation> {
+        let line = self.scan_indentation()?;
+        if line.tabs && line.spaces {
+            return Err(Diagnostic::MixedIndentation(line.span));
+        }
+        if line.width > 0 {
+            let spaces = *self.indent_with_spaces.get_or_insert(line.spaces);
+            if spaces && line.tabs {
+                return Err(Diagnostic::IndentationCharacter("spaces", "tabs", line.span));
+            }
+            if !spaces && line.spaces {
+                return Err(Diagnostic::IndentationCharacter("tabs", "spaces", line.span));
+            }
+        }
+        self.indentation = line.width;
+        Ok(line)
+    }
+
+    /// Trailing whitespace and blank lines are skipped, and the end of the source counts as a
+    /// line with no indentation.
+    fn scan_indentation(&mut self) -> Result<Indentation> {
+        self.whitespace_without_comments();
+        if self.is_eof() {
+            return Ok(Indentation::empty(self.offset()));
+        }
+        if !is_newline(self.peek()) {
+            return Err(Diagnostic::Expected("newline", self.current_span()));
+        }
+        loop {
+            self.skip_newline();
+            let start = self.offset();
+            let (mut tabs, mut spaces) = (false, false);
+            loop {
+                match self.peek() {
+                    ' ' => spaces = true,
+                    '\t' => tabs = true,
+                    _ => break,
+                }
+                self.bump();
+            }
+            if self.is_eof() {
+                return Ok(Indentation::empty(self.offset()));
+            }
+            if !is_newline(self.peek()) {
+                let span = self.span_from(start);
+                let width = span.end - span.start;
+                return Ok(Indentation { width, span, tabs, spaces });
+            }
+        }
+    }
+}
@@ -0,0 +1,206 @@
+//! `SassDoc` comments, attached to the mixin, function, variable or placeholder rule that follows
+//! them
+//! <http://sassdoc.com/annotations/>
+//!
+//! Only the `///` lines of a silent comment are documentation. A line starting with `@` begins an
+//! annotation, which runs until the next one, so the code of an `@example` goes on the lines
+//! beneath it. Annotations `SassDoc` knows but the AST doesn't model, like `@author`, are skipped.
+//! Malformed ones are skipped too, with a warning rather than an error, since documentation never
+//! stops a stylesheet from compiling.
+
+use scr_ast::{
+    node::{DocAccessLevel, DocAnnotation, DocComment, SelectorList, SimpleSelector},
+    AstBuilder, Atom, Span,
+};
+use scr_diagnostics::Diagnostic;
+
+use crate::Parser;
+
+/// A `///` line without the `///` and the space after it
+struct DocLine<'s> {
+    start: u32,
+    text: &'s str,
+}
+
+impl DocLine<'_> {
+    #[allow(clippy::cast_possible_truncation)]
+    const fn end(&self) -> u32 {
+        self.start + self.text.len() as u32
+    }
+}
+
+impl<'a> Parser<'a> {
+    /// The `SassDoc` of the statement being parsed, from the `///` comment right before it
+    pub(crate) fn take_doc_comment(&mut self) -> Option<DocComment<'a>> {
+        self.doc_comment.take().and_then(|span| self.parse_doc_comment(span))
+    }
+
+    /// Parses the `///` lines of the silent comment at `span`, if it has any.
+    pub(crate) fn parse_doc_comment(&self, span: Span) -> Option<DocComment<'a>> {
+        let lines = doc_lines(self.source_text(span), span.start);
+        if lines.is_empty() {
+            return None;
+        }
+        let is_annotation = |line: &DocLine| line.text.starts_with('@');
+        let description_end = lines.iter().position(is_annotation).unwrap_or(lines.len());
+        let (description, mut rest) = lines.split_at(description_end);
+        let mut annotations = self.ast.new_vec();
+        while let Some((line, tail)) = rest.split_first() {
+            let (body, next) =
+                tail.split_at(tail.iter().position(is_annotation).unwrap_or(tail.len()));
+            let end = body.last().map_or_else(|| line.end(), DocLine::end);
+            annotations.extend(self.doc_annotation(line, body, Span::new(line.start, end)));
+            rest = next;
+        }
+        let description = join_lines(&self.ast, "", description);
+        Some(self.ast.doc_comment(span, description, annotations))
+    }
+
+    /// The annotation starting on `line`, with `body` holding the lines after it, or `None` if
+    /// it's malformed or unknown
+    fn doc_annotation(
+        &self,
+        line: &DocLine<'a>,
+        body: &[DocLine<'a>],
+        span: Span,
+    ) -> Option<DocAnnotation<'a>> {
+        let name_end = line.text[1..]
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+            .map_or(line.text.len(), |index| index + 1);
+        let rest = line.text[name_end..].trim();
+        let malformed = |annotation, reason| {
+            self.warn(Diagnostic::MalformedDocAnnotation(annotation, reason, span));
+            None
+        };
+        match &line.text[1..name_end] {
+            "access" => {
+                let level = match rest {
+                    "public" => DocAccessLevel::Public,
+                    "private" => DocAccessLevel::Private,
+                    _ => return malformed("access", "expected \"public\" or \"private\""),
+                };
+                Some(self.ast.doc_annotation_access(span, level))
+            }
+            "deprecated" => {
+                Some(self.ast.doc_annotation_deprecated(span, join_lines(&self.ast, rest, body)))
+            }
+            "example" => {
+                let (language, description) = rest.strip_prefix('-').map_or_else(
+                    || {
+                        let (language, description) = rest.split_once(' ').unwrap_or((rest, ""));
+                        let language = (!language.is_empty()).then_some(language);
+                        (language, strip_dash(description.trim_start()))
+                    },
+                    |description| (None, description),
+                );
+                let code = body.iter().map(|line| line.text).collect::<Vec<_>>().join("\n");
+                let code = self.ast.str(code.trim_matches('\n'));
+                if code.is_empty() {
+                    return malformed("example", "expected code on the lines beneath it");
+                }
+                let description = description.trim();
+                Some(self.ast.doc_annotation_example(span, language, description, code))
+            }
+            "param" | "parameter" | "arg" | "argument" => {
+                let Some((types, rest)) = split_types(rest) else {
+                    return malformed("param", "expected \"}\"");
+                };
+                let rest = rest.strip_prefix('$').unwrap_or(rest);
+                let name_end =
+                    rest.find(|c: char| c.is_whitespace() || c == '[').unwrap_or(rest.len());
+                if name_end == 0 || &rest[..name_end] == "-" {
+                    return malformed("param", "expected a parameter name");
+                }
+                let (name, rest) = (Atom::from(&rest[..name_end]), rest[name_end..].trim_start());
+                let (default, rest) = match rest.strip_prefix('[') {
+                    Some(rest) => {
+                        let Some((default, rest)) = rest.split_once(']') else {
+                            return malformed("param", "expected \"]\"");
+                        };
+                        (Some(default.trim()), rest.trim_start())
+                    }
+                    None => (None, rest),
+                };
+                let description = join_lines(&self.ast, strip_dash(rest), body);
+                Some(self.ast.doc_annotation_param(span, types, name, default, description))
+            }
+            "return" | "returns" => {
+                let Some((types, rest)) = split_types(rest) else {
+                    return malformed("return", "expected \"}\"");
+                };
+                let description = join_lines(&self.ast, strip_dash(rest), body);
+                Some(self.ast.doc_annotation_return(span, types, description))
+            }
+            "see" => {
+                let Some((kind, rest)) = split_types(rest) else {
+                    return malformed("see", "expected \"}\"");
+                };
+                let Some(name) = rest.split_whitespace().next() else {
+                    return malformed("see", "expected the name of an item");
+                };
+                Some(self.ast.doc_annotation_see(span, kind, name))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Whether `selector` is a single placeholder such as `%name`, which `SassDoc` documents
+pub fn is_placeholder(selector: &SelectorList) -> bool {
+    match &selector.selectors[..] {
+        [complex] => match &complex.components[..] {
+            [component] => {
+                matches!(&component.selector.components[..], [SimpleSelector::Placeholder(_)])
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// The `///` lines of the comment `text` that starts at `start`
+///
+/// `////` lines are skipped, since `SassDoc` reads them as documenting the whole file.
+#[allow(clippy::cast_possible_truncation)]
+fn doc_lines(text: &str, start: u32) -> Vec<DocLine<'_>> {
+    let mut lines = vec![];
+    let mut line_start = start;
+    for line in text.split('\n') {
+        let offset = line_start;
+        line_start += line.len() as u32 + 1;
+        let Some(content) = line.trim_start().strip_prefix("///") else { continue };
+        if content.starts_with('/') {
+            continue;
+        }
+        let content = content.strip_prefix(' ').unwrap_or(content);
+        let start = offset + (line.len() - content.len()) as u32;
+        lines.push(DocLine { start, text: content.trim_end() });
+    }
+    lines
+}
+
+/// `{type} rest` split into the type and the rest, or `None` if the `}` is missing
+fn split_types(text: &str) -> Option<(Option<&str>, &str)> {
+    let Some(text) = text.strip_prefix('{') else {
+        return Some((None, text));
+    };
+    let (types, rest) = text.split_once('}')?;
+    Some((Some(types.trim()), rest.trim_start()))
+}
+
+/// The description after an optional `-`, as in `$name - description`
+fn strip_dash(text: &str) -> &str {
+    text.strip_prefix('-').map_or(text, str::trim_start)
+}
+
+/// `first` followed by the text of `lines`, one per line
+fn join_lines<'a>(ast: &AstBuilder<'a>, first: &str, lines: &[DocLine]) -> &'a str {
+    let mut text = first.to_string();
+    for line in lines {
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        text.push_str(line.text);
+    }
+    ast.str(text.trim())
+}
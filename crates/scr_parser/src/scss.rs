@@ -0,0 +1,223 @@
+//! How statements and blocks are delimited in SCSS, like Dart Sass's `ScssParser`
+//!
+//! Each of these defers to its counterpart in [`crate::sass`] for the indented syntax.
+
+use scr_allocator::Vec;
+use scr_ast::node::Statement;
+use scr_diagnostics::{Diagnostic, Result};
+
+use crate::{interpolation::InterpolationBuffer, lexer::definitions::is_newline, Parser};
+
+/// Parses one child of a block, e.g. any statement in a style rule or only declarations in a
+/// nested property
+pub type ChildParser<'a> = fn(&mut Parser<'a>) -> Result<Statement<'a>>;
+
+impl<'a> Parser<'a> {
+    /// Errors unless a statement ends here. The `;` itself is consumed by the enclosing block.
+    pub(crate) fn expect_statement_separator(&mut self) -> Result<()> {
+        if self.is_indented() {
+            return self.indented_expect_statement_separator();
+        }
+        self.whitespace_without_comments();
+        if self.is_eof() || matches!(self.peek(), ';' | '}') {
+            return Ok(());
+        }
+        self.expect_char(';')
+    }
+
+    pub(crate) fn at_end_of_statement(&self) -> bool {
+        if self.is_indented() {
+            return self.indented_at_end_of_statement();
+        }
+        self.is_eof() || matches!(self.peek(), ';' | '}' | '{')
+    }
+
+    pub(crate) fn looking_at_children(&mut self) -> bool {
+        if self.is_indented() {
+            return self.indented_looking_at_children();
+        }
+        self.peek() == '{'
+    }
+
+    /// Consumes `@else` if it follows the whitespace after an `@if` block, returning where it
+    /// starts.
+    ///
+    /// The deprecated `@elseif` is read as `@else if`.
+    pub(crate) fn scan_else(&mut self, if_indentation: u32) -> Result<Option<u32>> {
+        if self.is_indented() {
+            return self.indented_scan_else(if_indentation);
+        }
+        let checkpoint = self.checkpoint();
+        self.whitespace()?;
+        let start = self.offset();
+        if self.scan_else_keyword() {
+            return Ok(Some(start));
+        }
+        self.rewind(checkpoint);
+        Ok(None)
+    }
+
+    /// Consumes `@else`, or the `@else` of `@elseif`.
+    pub(crate) fn scan_else_keyword(&mut self) -> bool {
+        let checkpoint = self.checkpoint();
+        if self.next_eq('@') {
+            if self.scan_identifier("else") {
+                return true;
+            }
+            let before_name = self.checkpoint();
+            if self.scan_identifier("elseif") {
+                self.rewind(before_name);
+                "else".chars().for_each(|_| {
+                    self.bump();
+                });
+                return true;
+            }
+        }
+        self.rewind(checkpoint);
+        false
+    }
+
+    /// Consumes a `{ ... }` block whose children are parsed by `child`.
+    pub(crate) fn children(&mut self, child: ChildParser<'a>) -> Result<Vec<'a, Statement<'a>>> {
+        self.doc_comment = None;
+        if self.is_indented() {
+            return self.nested(|parser| parser.indented_children(child));
+        }
+        self.nested(|parser| {
+            parser.expect_char('{')?;
+            parser.whitespace_without_comments();
+            let mut children = parser.ast.new_vec();
+            loop {
+                match parser.peek() {
+                    '}' => {
+                        parser.bump();
+                        return Ok(children);
+                    }
+                    _ if parser.is_eof() => {
+                        parser.error(Diagnostic::ExpectedChar('}', parser.current_span()));
+                        return Ok(children);
+                    }
+                    _ => parser.block_item(&mut children, child),
+                }
+            }
+        })
+    }
+
+    /// Consumes statements parsed by `child` until the end of the source.
+    pub(crate) fn statements_into(
+        &mut self,
+        statements: &mut Vec<'a, Statement<'a>>,
+        child: ChildParser<'a>,
+    ) -> Result<()> {
+        if self.is_indented() {
+            return self.indented_statements_into(statements, child);
+        }
+        self.whitespace_without_comments();
+        while !self.is_eof() {
+            if self.peek() == '}' {
+                self.error(Diagnostic::UnmatchedCurly(self.current_span()));
+                self.bump();
+                self.whitespace_without_comments();
+                continue;
+            }
+            self.block_item(statements, child);
+        }
+        Ok(())
+    }
+
+    /// Consumes one item of a block: a variable declaration, a comment, a `;`, or a `child`.
+    ///
+    /// An item that fails to parse, or that consumes nothing, is reported and replaced by an
+    /// invalid statement.
+    pub(crate) fn block_item(
+        &mut self,
+        statements: &mut Vec<'a, Statement<'a>>,
+        child: ChildParser<'a>,
+    ) {
+        let start = self.offset();
+        let indentation = self.indentation;
+        let statement = match self.peek() {
+            '$' => self.variable_declaration_without_namespace().map(Some),
+            '/' if self.nth_char(1) == '/' => self.silent_comment().map(Some),
+            '/' if self.nth_char(1) == '*' => self.loud_comment().map(Some),
+            ';' => {
+                self.bump();
+                Ok(None)
+            }
+            _ => child(self).map(Some),
+        };
+        let statement = match statement {
+            // An item that consumed nothing would be parsed again at the same place forever.
+            Ok(_) if self.offset() == start && !self.is_eof() => {
+                Err(Diagnostic::Expected("statement", self.current_span()))
+            }
+            statement => statement,
+        };
+        self.doc_comment = match &statement {
+            Ok(Some(Statement::SilentComment(comment))) => Some(comment.span),
+            _ => None,
+        };
+        match statement {
+            Ok(statement) => statements.extend(statement),
+            Err(error) => statements.push(self.recover(start, indentation, error)),
+        }
+        self.whitespace_without_comments();
+    }
+
+    /// Consumes consecutive `//` comment lines as one comment.
+    pub(crate) fn silent_comment(&mut self) -> Result<Statement<'a>> {
+        if self.is_indented() {
+            return self.indented_silent_comment();
+        }
+        let start = self.offset();
+        loop {
+            self.skip_silent_comment()?;
+            let end = self.checkpoint();
+            self.whitespace_without_comments();
+            if !(self.peek() == '/' && self.nth_char(1) == '/') {
+                self.rewind(end);
+                break;
+            }
+        }
+        Ok(self.ast.statement_silent_comment(self.span_from(start), self.text_from(start)))
+    }
+
+    /// Consumes a `/* */` comment, which may contain interpolation.
+    pub(crate) fn loud_comment(&mut self) -> Result<Statement<'a>> {
+        if self.is_indented() {
+            return self.indented_loud_comment();
+        }
+        let start = self.offset();
+        let mut buffer = InterpolationBuffer::new(&self.ast, start);
+        self.expect_char('/')?;
+        self.expect_char('*')?;
+        buffer.push_str("/*");
+        loop {
+            match self.peek() {
+                '#' if self.nth_char(1) == '{' => self.add_interpolation_expression(&mut buffer)?,
+                '*' if self.nth_char(1) == '/' => {
+                    self.bump();
+                    self.bump();
+                    buffer.push_str("*/");
+                    let text = buffer.finish(&self.ast, self.offset());
+                    return Ok(self.ast.statement_loud_comment(self.span_from(start), text));
+                }
+                '\r' => {
+                    self.skip_newline();
+                    buffer.push('\n');
+                }
+                c if is_newline(c) => {
+                    self.bump();
+                    buffer.push('\n');
+                }
+                _ if self.is_eof() => {
+                    return Err(Diagnostic::UnterminatedBlockComment(self.span_from(start)));
+                }
+                c => {
+                    self.bump();
+                    buffer.push(c);
+                }
+            }
+        }
+    }
+}
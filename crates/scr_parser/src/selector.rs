@@ -0,0 +1,351 @@
+//! Selectors, like Dart Sass's `SelectorParser`, but parsed before interpolation is resolved
+
+use scr_ast::{
+    node::{
+        AttributeMatcher, Combinator, ComplexSelector, CompoundSelector, Interpolation,
+        SelectorList, SimpleSelector, StringExpression,
+    },
+    Atom, Span,
+};
+use scr_diagnostics::{Diagnostic, Result};
+
+use crate::{
+    interpolation::InterpolationBuffer,
+    lexer::definitions::{is_ident_start, is_newline, is_whitespace},
+    statement::unvendor,
+    Parser,
+};
+
+/// Pseudo-classes whose argument is a selector list
+const SELECTOR_PSEUDO_CLASSES: [&str; 9] =
+    ["not", "is", "matches", "where", "current", "any", "has", "host", "host-context"];
+
+/// Pseudo-elements whose argument is a selector list
+const SELECTOR_PSEUDO_ELEMENTS: [&str; 1] = ["slotted"];
+
+impl<'a> Parser<'a> {
+    /// `a, b > c`, up to the `{` of a style rule
+    pub(crate) fn selector_list(&mut self) -> Result<SelectorList<'a>> {
+        let start = self.offset();
+        let mut selectors = self.ast.new_vec();
+        loop {
+            self.whitespace()?;
+            selectors.push(self.complex_selector()?);
+            let checkpoint = self.checkpoint();
+            self.whitespace()?;
+            if !self.next_eq(',') {
+                self.rewind(checkpoint);
+                break;
+            }
+            // The indented syntax continues a selector list on the next line after a comma.
+            if self.is_indented() {
+                self.whitespace()?;
+                if is_newline(self.peek()) {
+                    self.skip_newline();
+                }
+            }
+        }
+        Ok(self.ast.selector_list(self.span_from(start), selectors))
+    }
+
+    /// Compound selectors joined by combinators, with an optional leading combinator as in
+    /// `> a`, and an optional trailing one as in `a >`.
+    fn complex_selector(&mut self) -> Result<ComplexSelector<'a>> {
+        let start = self.offset();
+        let leading_combinator = self.scan_combinator();
+        if leading_combinator.is_some() {
+            self.whitespace()?;
+        }
+        let mut components = self.ast.new_vec();
+        let mut end;
+        loop {
+            let compound_start = self.offset();
+            let selector = self.compound_selector()?;
+            end = self.offset();
+            let checkpoint = self.checkpoint();
+            self.whitespace()?;
+            let had_whitespace = self.offset() != end;
+            let combinator = if let Some(combinator) = self.scan_combinator() {
+                end = self.offset();
+                self.whitespace()?;
+                Some(combinator)
+            } else if had_whitespace && self.looking_at_compound_selector() {
+                Some(Combinator::Descendant)
+            } else {
+                None
+            };
+            let span = Span::new(compound_start, end);
+            components.push(self.ast.complex_selector_component(span, selector, combinator));
+            if combinator.is_none() {
+                self.rewind(checkpoint);
+                break;
+            }
+            if !self.looking_at_compound_selector() {
+                break;
+            }
+        }
+        let span = Span::new(start, end);
+        Ok(self.ast.complex_selector(span, leading_combinator, components))
+    }
+
+    fn scan_combinator(&mut self) -> Option<Combinator> {
+        let combinator = match self.peek() {
+            '>' => Combinator::Child,
+            '+' => Combinator::NextSibling,
+            '~' => Combinator::SubsequentSibling,
+            _ => return None,
+        };
+        self.bump();
+        Some(combinator)
+    }
+
+    fn looking_at_compound_selector(&self) -> bool {
+        match self.peek() {
+            '*' | '.' | '#' | '%' | '&' | '[' | ':' | '|' | '\\' | '-' => true,
+            c => is_ident_start(c),
+        }
+    }
+
+    /// Simple selectors without whitespace between them, e.g. `a.b:hover`
+    fn compound_selector(&mut self) -> Result<CompoundSelector<'a>> {
+        let start = self.offset();
+        let mut components = self.ast.new_vec();
+        loop {
+            let component_start = self.offset();
+            let component = match self.peek() {
+                '[' => self.attribute_selector()?,
+                '.' => {
+                    self.bump();
+                    let name = self.interpolated_identifier()?;
+                    self.ast.simple_selector_class(self.span_from(start_of(&name, 1)), name)
+                }
+                '#' if self.nth_char(1) != '{' => {
+                    self.bump();
+                    let name = self.interpolated_identifier()?;
+                    self.ast.simple_selector_id(self.span_from(start_of(&name, 1)), name)
+                }
+                '%' => {
+                    self.bump();
+                    let name = self.interpolated_identifier()?;
+                    let span = self.span_from(start_of(&name, 1));
+                    if self.context.is_plain_css() {
+                        return Err(Diagnostic::NotInPlainCss("Placeholder selectors", span));
+                    }
+                    self.ast.simple_selector_placeholder(span, name)
+                }
+                ':' => self.pseudo_selector()?,
+                '&' => self.parent_selector()?,
+                '*' => self.type_or_universal_selector()?,
+                // `|=` only follows a name inside an attribute selector.
+                '|' if self.nth_char(1) != '=' => self.type_or_universal_selector()?,
+                _ if self.looking_at_interpolated_identifier() => {
+                    self.type_or_universal_selector()?
+                }
+                _ => break,
+            };
+            // A component that consumed nothing would be parsed again at the same place forever.
+            if self.offset() == component_start {
+                return Err(Diagnostic::Expected("selector", self.current_span()));
+            }
+            components.push(component);
+        }
+        if components.is_empty() {
+            return Err(Diagnostic::Expected("selector", self.current_span()));
+        }
+        Ok(self.ast.compound_selector(self.span_from(start), components))
+    }
+
+    /// `&`, or `&-suffix`
+    fn parent_selector(&mut self) -> Result<SimpleSelector<'a>> {
+        let start = self.offset();
+        self.expect_char('&')?;
+        let suffix = if self.looking_at_interpolated_identifier_body() {
+            let mut buffer = InterpolationBuffer::new(&self.ast, self.offset());
+            self.interpolated_identifier_body(&mut buffer)?;
+            let suffix = buffer.finish(&self.ast, self.offset());
+            if self.context.is_plain_css() {
+                return Err(Diagnostic::ParentSuffixInPlainCss(suffix.span));
+            }
+            Some(suffix)
+        } else {
+            None
+        };
+        Ok(self.ast.simple_selector_parent(self.span_from(start), suffix))
+    }
+
+    /// `name`, `*`, `ns|name`, `ns|*`, `*|name` or `|name`
+    fn type_or_universal_selector(&mut self) -> Result<SimpleSelector<'a>> {
+        let start = self.offset();
+        let first = if self.next_eq('*') {
+            None
+        } else if self.peek() == '|' {
+            Some(self.ast.plain_interpolation(Span::empty(start), ""))
+        } else {
+            Some(self.interpolated_identifier()?)
+        };
+        if self.peek() != '|' || self.nth_char(1) == '=' {
+            let span = self.span_from(start);
+            return Ok(match first {
+                Some(name) => self.ast.simple_selector_type(span, None, name),
+                None => self.ast.simple_selector_universal(span, None),
+            });
+        }
+        self.bump();
+        let namespace =
+            first.unwrap_or_else(|| self.ast.plain_interpolation(Span::new(start, start + 1), "*"));
+        if self.next_eq('*') {
+            return Ok(self.ast.simple_selector_universal(self.span_from(start), Some(namespace)));
+        }
+        let name = self.interpolated_identifier()?;
+        Ok(self.ast.simple_selector_type(self.span_from(start), Some(namespace), name))
+    }
+
+    /// `[name]` or `[ns|name <matcher> value modifier]`
+    fn attribute_selector(&mut self) -> Result<SimpleSelector<'a>> {
+        let start = self.offset();
+        self.expect_char('[')?;
+        self.whitespace()?;
+
+        let name_start = self.offset();
+        let mut namespace = None;
+        let mut name = if self.peek() == '*' && self.nth_char(1) == '|' {
+            self.bump();
+            Some(self.ast.plain_interpolation(self.span_from(name_start), "*"))
+        } else if self.peek() == '|' {
+            None
+        } else {
+            Some(self.interpolated_identifier()?)
+        };
+        if self.peek() == '|' && self.nth_char(1) != '=' {
+            self.bump();
+            namespace = Some(
+                name.take()
+                    .unwrap_or_else(|| self.ast.plain_interpolation(Span::empty(name_start), "")),
+            );
+            name = Some(self.interpolated_identifier()?);
+        }
+        let Some(name) = name else {
+            return Err(Diagnostic::ExpectedIdentifier(self.current_span()));
+        };
+        self.whitespace()?;
+
+        if self.next_eq(']') {
+            let span = self.span_from(start);
+            return Ok(self.ast.simple_selector_attribute(span, namespace, name, None, None, None));
+        }
+        let matcher = match (self.peek(), self.nth_char(1)) {
+            ('=', _) => AttributeMatcher::Equal,
+            ('~', '=') => AttributeMatcher::Includes,
+            ('|', '=') => AttributeMatcher::DashMatch,
+            ('^', '=') => AttributeMatcher::Prefix,
+            ('$', '=') => AttributeMatcher::Suffix,
+            ('*', '=') => AttributeMatcher::Substring,
+            _ => return Err(Diagnostic::ExpectedChar(']', self.current_span())),
+        };
+        for _ in 0..matcher.as_str().len() {
+            self.bump();
+        }
+        self.whitespace()?;
+
+        let value = self.attribute_value()?;
+        self.whitespace()?;
+        let modifier = if self.peek().is_ascii_alphabetic() {
+            let modifier = self.identifier(false)?;
+            self.whitespace()?;
+            Some(Atom::from(modifier))
+        } else {
+            None
+        };
+        self.expect_char(']')?;
+        let span = self.span_from(start);
+        Ok(self.ast.simple_selector_attribute(
+            span,
+            namespace,
+            name,
+            Some(matcher),
+            Some(value),
+            modifier,
+        ))
+    }
+
+    fn attribute_value(&mut self) -> Result<StringExpression<'a>> {
+        if matches!(self.peek(), '"' | '\'') {
+            return self.interpolated_string_expression();
+        }
+        let name = self.interpolated_identifier()?;
+        Ok(self.ast.string_expression(name.span, name, false))
+    }
+
+    /// `:name`, `::name` or `:name(argument)`
+    fn pseudo_selector(&mut self) -> Result<SimpleSelector<'a>> {
+        let start = self.offset();
+        self.expect_char(':')?;
+        let is_element = self.next_eq(':');
+        let name = self.interpolated_identifier()?;
+        if !self.next_eq('(') {
+            let span = self.span_from(start);
+            return Ok(self.ast.simple_selector_pseudo(span, name, is_element, None, None));
+        }
+        self.whitespace()?;
+
+        let lowercase = name.as_plain().map(|name| unvendor(name).to_ascii_lowercase());
+        let takes_selector = lowercase.as_deref().is_some_and(|name| {
+            if is_element {
+                SELECTOR_PSEUDO_ELEMENTS.contains(&name)
+            } else {
+                SELECTOR_PSEUDO_CLASSES.contains(&name)
+            }
+        });
+        let (argument, selector) = if takes_selector {
+            (None, Some(self.selector_list()?))
+        } else if !is_element
+            && matches!(lowercase.as_deref(), Some("nth-child" | "nth-last-child"))
+        {
+            let argument = self.an_plus_b()?;
+            let selector =
+                if self.scan_identifier("of") { Some(self.selector_list()?) } else { None };
+            (Some(argument), selector)
+        } else {
+            (Some(self.interpolated_declaration_value(true)?), None)
+        };
+        self.whitespace()?;
+        self.expect_char(')')?;
+        let span = self.span_from(start);
+        Ok(self.ast.simple_selector_pseudo(span, name, is_element, argument, selector))
+    }
+
+    /// The `an+b` argument of `:nth-child()`, as text with whitespace collapsed, stopping before
+    /// `of` or `)`
+    fn an_plus_b(&mut self) -> Result<Interpolation<'a>> {
+        let start = self.offset();
+        let mut buffer = InterpolationBuffer::new(&self.ast, start);
+        let mut end = start;
+        loop {
+            match self.peek() {
+                ')' => break,
+                _ if self.is_eof() => break,
+                '#' if self.nth_char(1) == '{' => self.add_interpolation_expression(&mut buffer)?,
+                c if is_whitespace(c) => {
+                    self.lexer.eat_while(is_whitespace);
+                    self.whitespace()?;
+                    if self.peek() == ')' || self.looking_at_keyword("of") {
+                        break;
+                    }
+                    buffer.push(' ');
+                    continue;
+                }
+                c => {
+                    self.bump();
+                    buffer.push(c);
+                }
+            }
+            end = self.offset();
+        }
+        Ok(buffer.finish(&self.ast, end))
+    }
+}
+
+/// Where a selector starts that has a `prefix_len`-byte prefix before `name`, e.g. `.`
+const fn start_of(name: &Interpolation, prefix_len: u32) -> u32 {
+    name.span.start - prefix_len
+}
@@ -0,0 +1,1081 @@
+//! Statements and at-rules, like Dart Sass's `StylesheetParser`
+
+use scr_allocator::Vec;
+use scr_ast::{
+    context::{Context, WithContext},
+    node::{
+        ArgumentList, ConfiguredVariable, ElseClause, Expression, ForwardMember, Import,
+        Interpolation, ParameterList, Statement, Stylesheet,
+    },
+    Atom, Span,
+};
+use scr_diagnostics::{Diagnostic, Result};
+
+use crate::{
+    interpolation::InterpolationBuffer,
+    lexer::definitions::{is_ident, is_ident_start},
+    sassdoc::is_placeholder,
+    scss::ChildParser,
+    Parser,
+};
+
+/// Names `@function` may not use, because they're parsed specially in expressions
+const RESERVED_FUNCTION_NAMES: [&str; 8] =
+    ["calc", "element", "expression", "url", "and", "or", "not", "clamp"];
+
+impl<'a> Parser<'a> {
+    pub(crate) fn parse_stylesheet(&mut self) -> Stylesheet<'a> {
+        self.next_eq('\u{feff}');
+        let mut body = self.ast.new_vec();
+        if let Err(error) = self.statements_into(&mut body, Self::statement) {
+            self.error(error);
+        }
+        self.ast.stylesheet(self.source_span(), body)
+    }
+
+    /// Any statement allowed at the root or in a style rule
+    pub(crate) fn statement(&mut self) -> Result<Statement<'a>> {
+        if self.peek() == '@' {
+            return self.at_rule(Self::statement);
+        }
+        self.use_allowed = false;
+        if self.is_indented() {
+            let start = self.offset();
+            if self.peek() == '=' {
+                self.bump();
+                self.whitespace()?;
+                return self.mixin_rule(start);
+            }
+            if self.peek() == '+' && self.looking_at_identifier_at(1) {
+                self.bump();
+                return self.include_rule(start);
+            }
+        }
+        if self.context.in_keyframes() {
+            return self.keyframe_block();
+        }
+        if self.context.allows_declarations() {
+            return self.declaration_or_style_rule();
+        }
+        if self.looking_at_namespaced_variable() {
+            return self.variable_declaration_with_namespace();
+        }
+        let start = self.offset();
+        let checkpoint = self.checkpoint();
+        if let Ok(Some(_)) = self.try_declaration() {
+            return Err(Diagnostic::DeclarationOutsideStyleRule(self.span_from(start)));
+        }
+        self.rewind(checkpoint);
+        self.style_rule()
+    }
+
+    fn looking_at_namespaced_variable(&mut self) -> bool {
+        if !self.looking_at_identifier() {
+            return false;
+        }
+        let checkpoint = self.checkpoint();
+        let found = self.identifier(false).is_ok() && self.next_eq('.') && self.peek() == '$';
+        self.rewind(checkpoint);
+        found
+    }
+
+    fn declaration_or_style_rule(&mut self) -> Result<Statement<'a>> {
+        if self.looking_at_namespaced_variable() {
+            return self.variable_declaration_with_namespace();
+        }
+        if let Some(declaration) = self.try_declaration()? {
+            return Ok(declaration);
+        }
+        self.style_rule()
+    }
+
+    /// A declaration, or `None` with nothing consumed if a style rule starts here instead
+    ///
+    /// Like Dart Sass, this reads up to the first `:` and backtracks if it turns out to be part
+    /// of a selector:
+    /// - custom properties such as `--a: b` are always declarations;
+    /// - `a::before`, and `a:b` in the indented syntax, are selectors;
+    /// - `font: {` is a nested property, as is `font: 12px/1.5 {`;
+    /// - `a:hover` may be either, so it's a selector if the value can't be parsed or is followed
+    ///   by a block, unless the statement ends with `;` as in `a:b;`.
+    pub(crate) fn try_declaration(&mut self) -> Result<Option<Statement<'a>>> {
+        let start = self.offset();
+        let checkpoint = self.checkpoint();
+        let Some(name) = self.try_declaration_name()? else {
+            self.rewind(checkpoint);
+            return Ok(None);
+        };
+        self.whitespace()?;
+        if !self.next_eq(':') {
+            self.rewind(checkpoint);
+            return Ok(None);
+        }
+        if name.is_custom_property_name() {
+            return self.custom_property(start, name).map(Some);
+        }
+        if self.peek() == ':' || (self.is_indented() && self.looking_at_interpolated_identifier()) {
+            self.rewind(checkpoint);
+            return Ok(None);
+        }
+
+        let after_colon = self.offset();
+        self.whitespace()?;
+        if self.looking_at_children() {
+            return self.nested_declaration(start, name, None).map(Some);
+        }
+        let could_be_selector =
+            self.offset() == after_colon && self.looking_at_interpolated_identifier();
+        let before_value = self.checkpoint();
+        let value = match self.declaration_value(could_be_selector) {
+            Ok(value) => value,
+            Err(error) if could_be_selector => {
+                self.rewind(before_value);
+                self.almost_any_value()?;
+                if !self.is_indented() && self.peek() == ';' {
+                    return Err(error);
+                }
+                self.rewind(checkpoint);
+                return Ok(None);
+            }
+            Err(error) => return Err(error),
+        };
+        let span = self.span_from(start);
+        self.whitespace()?;
+        if self.looking_at_children() {
+            return self.nested_declaration(start, name, Some(value)).map(Some);
+        }
+        self.expect_statement_separator()?;
+        Ok(Some(self.ast.statement_declaration(span, name, Some(value), None)))
+    }
+
+    /// The value of a declaration that may turn out to be a selector, which fails if it isn't
+    /// followed by the end of the statement, or by a block when `could_be_selector`
+    fn declaration_value(&mut self, could_be_selector: bool) -> Result<Expression<'a>> {
+        let value = self.expression()?;
+        let checkpoint = self.checkpoint();
+        self.whitespace()?;
+        if self.looking_at_children() {
+            if could_be_selector {
+                self.expect_statement_separator()?;
+            }
+        } else if !self.at_end_of_statement() {
+            self.expect_statement_separator()?;
+        }
+        self.rewind(checkpoint);
+        Ok(value)
+    }
+
+    /// `name: value;`, or a nested property
+    fn declaration(&mut self) -> Result<Statement<'a>> {
+        let start = self.offset();
+        let name = self.declaration_name()?;
+        self.whitespace()?;
+        self.expect_char(':')?;
+        if name.is_custom_property_name() {
+            return self.custom_property(start, name);
+        }
+
+        self.whitespace()?;
+        if self.looking_at_children() {
+            return self.nested_declaration(start, name, None);
+        }
+        let value = self.expression()?;
+        let span = self.span_from(start);
+        self.whitespace()?;
+        if self.looking_at_children() {
+            return self.nested_declaration(start, name, Some(value));
+        }
+        self.expect_statement_separator()?;
+        Ok(self.ast.statement_declaration(span, name, Some(value), None))
+    }
+
+    /// `--name: value`, whose value is kept as written, after the `:`
+    fn custom_property(&mut self, start: u32, name: Interpolation<'a>) -> Result<Statement<'a>> {
+        self.whitespace()?;
+        let value_start = self.offset();
+        let text = if self.at_end_of_statement() {
+            self.ast.interpolation(Span::empty(value_start), self.ast.new_vec())
+        } else {
+            self.interpolated_declaration_value(false)?
+        };
+        let value = self.ast.expression_string(text.span, text, false);
+        let span = self.span_from(start);
+        if self.looking_at_children() {
+            return Err(Diagnostic::NestedCustomProperty(span));
+        }
+        self.expect_statement_separator()?;
+        Ok(self.ast.statement_declaration(span, name, Some(value), None))
+    }
+
+    /// The block of a nested property such as `font: { family: serif; }`
+    fn nested_declaration(
+        &mut self,
+        start: u32,
+        name: Interpolation<'a>,
+        value: Option<Expression<'a>>,
+    ) -> Result<Statement<'a>> {
+        if self.context.is_plain_css() {
+            return Err(Diagnostic::NotInPlainCss("Nested declarations", self.span_from(start)));
+        }
+        let body = self.children(Self::declaration_child)?;
+        Ok(self.ast.statement_declaration(self.span_from(start), name, value, Some(body)))
+    }
+
+    fn declaration_name(&mut self) -> Result<Interpolation<'a>> {
+        let name = self.try_declaration_name()?;
+        name.ok_or_else(|| Diagnostic::Expected("identifier", self.current_span()))
+    }
+
+    /// A property name, allowing the `*prop`, `:prop`, `.prop` and `#prop` hacks for old IE
+    fn try_declaration_name(&mut self) -> Result<Option<Interpolation<'a>>> {
+        let start = self.offset();
+        let hack = matches!(self.peek(), ':' | '*' | '.')
+            || (self.peek() == '#' && self.nth_char(1) != '{');
+        if !hack {
+            if !self.looking_at_interpolated_identifier() {
+                return Ok(None);
+            }
+            return self.interpolated_identifier().map(Some);
+        }
+        let mut buffer = InterpolationBuffer::new(&self.ast, start);
+        buffer.push(self.bump());
+        let whitespace_start = self.offset();
+        self.whitespace()?;
+        buffer.push_str(self.text_from(whitespace_start));
+        if !self.looking_at_interpolated_identifier() {
+            return Ok(None);
+        }
+        let name = self.interpolated_identifier()?;
+        buffer.add_interpolation(&self.ast, name);
+        Ok(Some(buffer.finish(&self.ast, self.offset())))
+    }
+
+    /// A child of a nested property
+    fn declaration_child(&mut self) -> Result<Statement<'a>> {
+        if self.peek() != '@' {
+            return self.declaration();
+        }
+        let start = self.offset();
+        self.bump();
+        let name = self.identifier(false)?;
+        self.whitespace()?;
+        match name.as_str() {
+            "content" => self.content_rule(start),
+            "debug" => self.debug_rule(start),
+            "each" => self.each_rule(start, Self::declaration_child),
+            "error" => self.error_rule(start),
+            "for" => self.for_rule(start, Self::declaration_child),
+            "if" => self.if_rule(start, Self::declaration_child),
+            "include" => self.include_rule(start),
+            "warn" => self.warn_rule(start),
+            "while" => self.while_rule(start, Self::declaration_child),
+            _ => self.disallowed_at_rule(start),
+        }
+    }
+
+    /// A child of `@function`
+    fn function_child(&mut self) -> Result<Statement<'a>> {
+        if self.peek() != '@' {
+            let checkpoint = self.checkpoint();
+            let error = match self.variable_declaration_with_namespace() {
+                Ok(statement) => return Ok(statement),
+                Err(error) => error,
+            };
+            self.rewind(checkpoint);
+            // Like Dart Sass, the child is parsed as if it were outside the function, so that only
+            // the child itself is reported and not the declarations in it.
+            let child = self.without_context(Context::FUNCTION, Self::declaration_or_style_rule);
+            return Err(match child {
+                Ok(Statement::StyleRule(rule)) => {
+                    Diagnostic::InvalidFunctionChild("style rules", rule.span)
+                }
+                Ok(statement) => Diagnostic::InvalidFunctionChild("declarations", statement.span()),
+                Err(_) => error,
+            });
+        }
+        let start = self.offset();
+        self.bump();
+        let name = self.identifier(false)?;
+        self.whitespace()?;
+        match name.as_str() {
+            "debug" => self.debug_rule(start),
+            "each" => self.each_rule(start, Self::function_child),
+            "error" => self.error_rule(start),
+            "for" => self.for_rule(start, Self::function_child),
+            "if" => self.if_rule(start, Self::function_child),
+            "return" => self.return_rule(start),
+            "warn" => self.warn_rule(start),
+            "while" => self.while_rule(start, Self::function_child),
+            _ => self.disallowed_at_rule(start),
+        }
+    }
+
+    pub(crate) fn variable_declaration_without_namespace(&mut self) -> Result<Statement<'a>> {
+        let start = self.offset();
+        self.variable_declaration(None, start)
+    }
+
+    fn variable_declaration_with_namespace(&mut self) -> Result<Statement<'a>> {
+        let start = self.offset();
+        let namespace = self.identifier(false)?;
+        self.expect_char('.')?;
+        self.variable_declaration(Some(Atom::from(namespace)), start)
+    }
+
+    /// `$name: value [!default] [!global]`, after the namespace if there is one
+    fn variable_declaration(
+        &mut self,
+        namespace: Option<Atom>,
+        start: u32,
+    ) -> Result<Statement<'a>> {
+        let doc = self.take_doc_comment();
+        let name = Atom::from(self.variable_name()?);
+        if self.context.is_plain_css() {
+            return Err(Diagnostic::NotInPlainCss("Sass variables", self.span_from(start)));
+        }
+        self.whitespace()?;
+        self.expect_char(':')?;
+        self.whitespace()?;
+        let value = self.expression()?;
+        let mut span = self.span_from(start);
+        let (mut guarded, mut global) = (false, false);
+        loop {
+            let checkpoint = self.checkpoint();
+            self.whitespace()?;
+            let flag_start = self.offset();
+            if !self.next_eq('!') {
+                self.rewind(checkpoint);
+                break;
+            }
+            let flag = self.identifier(false)?;
+            match flag.as_str() {
+                "default" => guarded = true,
+                "global" if namespace.is_some() => {
+                    return Err(Diagnostic::GlobalInOtherModule(self.span_from(flag_start)));
+                }
+                "global" => global = true,
+                _ => return Err(Diagnostic::InvalidFlag(self.span_from(flag_start))),
+            }
+            span = self.span_from(start);
+        }
+        self.whitespace()?;
+        self.expect_statement_separator()?;
+        Ok(self
+            .ast
+            .statement_variable_declaration(span, namespace, name, value, guarded, global, doc))
+    }
+
+    /// `selector { ... }`, which may be nested in plain CSS too, as in CSS Nesting
+    pub(crate) fn style_rule(&mut self) -> Result<Statement<'a>> {
+        let start = self.offset();
+        let doc_comment = self.doc_comment.take();
+        let selector = self.selector_list()?;
+        let doc = doc_comment
+            .filter(|_| is_placeholder(&selector))
+            .and_then(|span| self.parse_doc_comment(span));
+        self.whitespace()?;
+        let body = self.with_context(Context::STYLE_RULE, |p| p.children(Self::statement))?;
+        Ok(self.ast.statement_style_rule(self.span_from(start), selector, body, doc))
+    }
+
+    /// `from, 50% { ... }` inside `@keyframes`
+    fn keyframe_block(&mut self) -> Result<Statement<'a>> {
+        let start = self.offset();
+        let mut selectors = self.ast.new_vec();
+        loop {
+            selectors.push(self.keyframe_selector()?);
+            self.whitespace()?;
+            if !self.next_eq(',') {
+                break;
+            }
+            self.whitespace()?;
+        }
+        let body = self.without_context(Context::KEYFRAMES, |p| {
+            p.with_context(Context::STYLE_RULE, |p| p.children(Self::statement))
+        })?;
+        Ok(self.ast.statement_keyframe_block(self.span_from(start), selectors, body))
+    }
+
+    /// `from`, `to` or a percentage, any of which may be interpolated
+    fn keyframe_selector(&mut self) -> Result<Interpolation<'a>> {
+        let start = self.offset();
+        if self.looking_at_interpolated_identifier() {
+            let selector = self.interpolated_identifier()?;
+            let valid = selector.as_plain().is_none_or(|name| {
+                name.eq_ignore_ascii_case("from") || name.eq_ignore_ascii_case("to")
+            });
+            if !valid {
+                return Err(Diagnostic::InvalidKeyframeSelector(selector.span));
+            }
+            return Ok(selector);
+        }
+        self.next_eq('+');
+        if self.peek() != '.' {
+            self.digits().map_err(|_| Diagnostic::InvalidKeyframeSelector(self.current_span()))?;
+        }
+        if self.peek() == '.' {
+            self.bump();
+            self.digits()?;
+        }
+        if matches!(self.peek(), 'e' | 'E') {
+            self.bump();
+            if matches!(self.peek(), '+' | '-') {
+                self.bump();
+            }
+            self.digits()?;
+        }
+        if !self.next_eq('%') {
+            return Err(Diagnostic::InvalidKeyframeSelector(self.span_from(start)));
+        }
+        Ok(self.ast.plain_interpolation(self.span_from(start), self.text_from(start)))
+    }
+
+    /// Any at-rule, with `child` parsing the children of unknown at-rules and control directives
+    pub(crate) fn at_rule(&mut self, child: ChildParser<'a>) -> Result<Statement<'a>> {
+        let start = self.offset();
+        self.expect_char('@')?;
+        let name = self.interpolated_identifier()?;
+        self.whitespace()?;
+        if self.context.is_plain_css() {
+            return self.plain_css_at_rule(start, name, child);
+        }
+
+        let use_allowed = std::mem::replace(&mut self.use_allowed, false);
+        match name.as_plain() {
+            Some("at-root") => self.at_root_rule(start),
+            Some("charset") => {
+                self.use_allowed = use_allowed;
+                self.unknown_at_rule(start, name, child)
+            }
+            Some("content") => self.content_rule(start),
+            Some("debug") => self.debug_rule(start),
+            Some("each") => self.each_rule(start, child),
+            Some("else") => self.disallowed_at_rule(start),
+            Some("error") => self.error_rule(start),
+            Some("extend") => self.extend_rule(start),
+            Some("for") => self.for_rule(start, child),
+            Some("forward") => {
+                self.use_allowed = use_allowed;
+                if !self.context.is_top_level() {
+                    return self.disallowed_at_rule(start);
+                }
+                self.forward_rule(start)
+            }
+            Some("function") => self.function_rule(start),
+            Some("if") => self.if_rule(start, child),
+            Some("import") => self.import_rule(start),
+            Some("include") => self.include_rule(start),
+            Some("mixin") => self.mixin_rule(start),
+            Some("return") => {
+                self.almost_any_value()?;
+                Err(Diagnostic::ReturnOutsideFunction(self.span_from(start)))
+            }
+            Some("use") => {
+                self.use_allowed = use_allowed;
+                if !self.context.is_top_level() {
+                    return self.disallowed_at_rule(start);
+                }
+                self.use_rule(start, use_allowed)
+            }
+            Some("warn") => self.warn_rule(start),
+            Some("while") => self.while_rule(start, child),
+            _ => self.css_at_rule(start, name, child),
+        }
+    }
+
+    /// Consumes an at-rule that isn't allowed where it is and errors.
+    fn disallowed_at_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        self.almost_any_value()?;
+        Err(Diagnostic::DisallowedAtRule(self.span_from(start)))
+    }
+
+    /// `@at-root [(query)] { ... }` or `@at-root selector { ... }`
+    fn at_root_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        if self.peek() == '(' {
+            let query = self.at_root_query()?;
+            self.whitespace()?;
+            let body = self.children(Self::statement)?;
+            return Ok(self.ast.statement_at_root(self.span_from(start), Some(query), body));
+        }
+        let body = if self.looking_at_children() {
+            self.children(Self::statement)?
+        } else {
+            let rule = self.style_rule()?;
+            self.ast.vec_from_iter([rule])
+        };
+        Ok(self.ast.statement_at_root(self.span_from(start), None, body))
+    }
+
+    /// `(with: media)`, kept as text since it's only checked after evaluation
+    fn at_root_query(&mut self) -> Result<Interpolation<'a>> {
+        let start = self.offset();
+        let mut buffer = InterpolationBuffer::new(&self.ast, start);
+        self.expect_char('(')?;
+        buffer.push('(');
+        self.whitespace()?;
+        let expression = self.expression()?;
+        self.add_or_inject(&mut buffer, expression);
+        self.whitespace()?;
+        if self.next_eq(':') {
+            self.whitespace()?;
+            buffer.push_str(": ");
+            let expression = self.expression()?;
+            self.add_or_inject(&mut buffer, expression);
+            self.whitespace()?;
+        }
+        self.expect_char(')')?;
+        buffer.push(')');
+        Ok(buffer.finish(&self.ast, self.offset()))
+    }
+
+    /// `@content [(arguments)];`
+    fn content_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        if !self.context.contains(Context::MIXIN) {
+            self.almost_any_value()?;
+            return Err(Diagnostic::ContentOutsideMixin(self.span_from(start)));
+        }
+        self.mixin_has_content = true;
+        let arguments = if self.peek() == '(' {
+            self.mixin_argument_list()?
+        } else {
+            self.empty_argument_list()
+        };
+        let span = self.span_from(start);
+        self.whitespace()?;
+        self.expect_statement_separator()?;
+        Ok(self.ast.statement_content(span, arguments))
+    }
+
+    fn debug_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        let expression = self.expression()?;
+        let span = self.span_from(start);
+        self.whitespace()?;
+        self.expect_statement_separator()?;
+        Ok(self.ast.statement_debug(span, expression))
+    }
+
+    fn error_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        let expression = self.expression()?;
+        let span = self.span_from(start);
+        self.whitespace()?;
+        self.expect_statement_separator()?;
+        Ok(self.ast.statement_error(span, expression))
+    }
+
+    fn warn_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        let expression = self.expression()?;
+        let span = self.span_from(start);
+        self.whitespace()?;
+        self.expect_statement_separator()?;
+        Ok(self.ast.statement_warn(span, expression))
+    }
+
+    fn return_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        let expression = self.expression()?;
+        let span = self.span_from(start);
+        self.whitespace()?;
+        self.expect_statement_separator()?;
+        Ok(self.ast.statement_return(span, expression))
+    }
+
+    /// `@each $a, $b in list { ... }`
+    fn each_rule(&mut self, start: u32, child: ChildParser<'a>) -> Result<Statement<'a>> {
+        let mut variables = self.ast.new_vec();
+        loop {
+            variables.push(Atom::from(self.variable_name()?));
+            self.whitespace()?;
+            if !self.next_eq(',') {
+                break;
+            }
+            self.whitespace()?;
+        }
+        self.expect_keyword("in")?;
+        self.whitespace()?;
+        let list = self.expression()?;
+        self.whitespace()?;
+        let body = self.with_context(Context::CONTROL_DIRECTIVE, |p| p.children(child))?;
+        Ok(self.ast.statement_each(self.span_from(start), variables, list, body))
+    }
+
+    /// `@extend selector [!optional];`
+    fn extend_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        if !self.context.in_style_rule() && !self.context.in_mixin() {
+            self.almost_any_value()?;
+            return Err(Diagnostic::ExtendOutsideStyleRule(self.span_from(start)));
+        }
+        let selector = self.selector_list()?;
+        self.whitespace()?;
+        let optional = self.next_eq('!');
+        if optional {
+            self.expect_keyword("optional")?;
+        }
+        let span = self.span_from(start);
+        self.whitespace()?;
+        self.expect_statement_separator()?;
+        Ok(self.ast.statement_extend(span, selector, optional))
+    }
+
+    /// `@for $i from 1 through 10 { ... }`
+    fn for_rule(&mut self, start: u32, child: ChildParser<'a>) -> Result<Statement<'a>> {
+        let variable = Atom::from(self.variable_name()?);
+        self.whitespace()?;
+        self.expect_keyword("from")?;
+        self.whitespace()?;
+        let from = self.expression_until(Some(|p| {
+            p.looking_at_keyword("to") || p.looking_at_keyword("through")
+        }))?;
+        self.whitespace()?;
+        let exclusive = if self.scan_identifier("to") {
+            true
+        } else if self.scan_identifier("through") {
+            false
+        } else {
+            return Err(Diagnostic::Expected("\"to\" or \"through\"", self.current_span()));
+        };
+        self.whitespace()?;
+        let to = self.expression()?;
+        self.whitespace()?;
+        let body = self.with_context(Context::CONTROL_DIRECTIVE, |p| p.children(child))?;
+        Ok(self.ast.statement_for(self.span_from(start), variable, from, to, exclusive, body))
+    }
+
+    /// `@forward "url" [as prefix-*] [show|hide members] [with (...)];`
+    fn forward_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        let url = self.string()?;
+        let url = self.ast.str(&url);
+        self.whitespace()?;
+        let prefix = if self.scan_identifier("as") {
+            self.whitespace()?;
+            let prefix = self.identifier(false)?;
+            self.expect_char('*')?;
+            self.whitespace()?;
+            Some(Atom::from(prefix))
+        } else {
+            None
+        };
+        let (mut shown, mut hidden) = (None, None);
+        if self.scan_identifier("show") {
+            shown = Some(self.member_list()?);
+        } else if self.scan_identifier("hide") {
+            hidden = Some(self.member_list()?);
+        }
+        let configuration = if self.scan_identifier("with") {
+            self.configuration(true)?
+        } else {
+            self.ast.new_vec()
+        };
+        let span = self.span_from(start);
+        self.whitespace()?;
+        self.expect_statement_separator()?;
+        Ok(self.ast.statement_forward(span, url, prefix, shown, hidden, configuration))
+    }
+
+    /// `a, $b, c` after `show` or `hide`
+    fn member_list(&mut self) -> Result<Vec<'a, ForwardMember>> {
+        let mut members = self.ast.new_vec();
+        loop {
+            self.whitespace()?;
+            let start = self.offset();
+            let is_variable = self.peek() == '$';
+            let name = if is_variable { self.variable_name()? } else { self.identifier(false)? };
+            members.push(self.ast.forward_member(
+                self.span_from(start),
+                Atom::from(name),
+                is_variable,
+            ));
+            self.whitespace()?;
+            if !self.next_eq(',') {
+                return Ok(members);
+            }
+        }
+    }
+
+    /// `with ($name: value, ...)`, where `@forward` allows `!default`
+    fn configuration(&mut self, allow_guarded: bool) -> Result<Vec<'a, ConfiguredVariable<'a>>> {
+        self.whitespace()?;
+        self.expect_char('(')?;
+        let mut variables: Vec<'a, ConfiguredVariable<'a>> = self.ast.new_vec();
+        loop {
+            self.whitespace()?;
+            let start = self.offset();
+            let name = Atom::from(self.variable_name()?);
+            self.whitespace()?;
+            self.expect_char(':')?;
+            self.whitespace()?;
+            let value = self.expression_until_comma()?;
+            let mut span = self.span_from(start);
+            self.whitespace()?;
+            let mut guarded = false;
+            let flag_start = self.offset();
+            if allow_guarded && self.next_eq('!') {
+                if self.identifier(false)? != "default" {
+                    return Err(Diagnostic::InvalidFlag(self.span_from(flag_start)));
+                }
+                guarded = true;
+                span = self.span_from(start);
+                self.whitespace()?;
+            }
+            if variables.iter().any(|variable| variable.name == name) {
+                return Err(Diagnostic::DuplicateConfiguration(span));
+            }
+            variables.push(self.ast.configured_variable(span, name, value, guarded));
+            if !self.next_eq(',') {
+                break;
+            }
+            self.whitespace()?;
+            if !self.looking_at_expression() {
+                break;
+            }
+        }
+        self.expect_char(')')?;
+        Ok(variables)
+    }
+
+    /// `@function name(parameters) { ... }`
+    fn function_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        let doc = self.take_doc_comment();
+        let name_start = self.offset();
+        let name = self.identifier(false)?;
+        let name_span = self.span_from(name_start);
+        if self.context.in_mixin() {
+            return Err(Diagnostic::FunctionInMixin(self.span_from(start)));
+        }
+        if self.context.in_control_directive() {
+            return Err(Diagnostic::FunctionInControlDirective(self.span_from(start)));
+        }
+        if RESERVED_FUNCTION_NAMES.contains(&unvendor(&name)) {
+            return Err(Diagnostic::InvalidFunctionName(name_span));
+        }
+        self.whitespace()?;
+        let parameters = self.parameter_list()?;
+        self.whitespace()?;
+        let body = self.with_context(Context::FUNCTION, |p| p.children(Self::function_child))?;
+        let span = self.span_from(start);
+        Ok(self.ast.statement_function(span, Atom::from(name), parameters, body, doc))
+    }
+
+    /// `@if condition { ... } @else if condition { ... } @else { ... }`
+    fn if_rule(&mut self, start: u32, child: ChildParser<'a>) -> Result<Statement<'a>> {
+        let if_indentation = self.indentation;
+        let condition = self.expression()?;
+        self.whitespace()?;
+        let body = self.with_context(Context::CONTROL_DIRECTIVE, |p| p.children(child))?;
+        let mut clauses = self.ast.new_vec();
+        clauses.push(self.ast.if_clause(self.span_from(start), condition, body));
+
+        let mut else_clause: Option<ElseClause<'a>> = None;
+        while let Some(else_start) = self.scan_else(if_indentation)? {
+            self.whitespace()?;
+            if self.scan_identifier("if") {
+                self.whitespace()?;
+                let condition = self.expression()?;
+                self.whitespace()?;
+                let body = self.with_context(Context::CONTROL_DIRECTIVE, |p| p.children(child))?;
+                clauses.push(self.ast.if_clause(self.span_from(else_start), condition, body));
+            } else {
+                let body = self.with_context(Context::CONTROL_DIRECTIVE, |p| p.children(child))?;
+                else_clause = Some(self.ast.else_clause(self.span_from(else_start), body));
+                break;
+            }
+        }
+        Ok(self.ast.statement_if(self.span_from(start), clauses, else_clause))
+    }
+
+    /// `@import "a", "b.css" screen;`
+    fn import_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        let mut imports = self.ast.new_vec();
+        let mut end;
+        loop {
+            self.whitespace()?;
+            let import = self.import_argument()?;
+            end = self.offset();
+            let nested = self.context.in_control_directive() || self.context.in_mixin();
+            if matches!(import, Import::Dynamic(_)) && nested {
+                return Err(Diagnostic::DisallowedAtRule(self.span_from(start)));
+            }
+            imports.push(import);
+            self.whitespace()?;
+            if !self.next_eq(',') {
+                break;
+            }
+        }
+        self.expect_statement_separator()?;
+        Ok(self.ast.statement_import(Span::new(start, end), imports))
+    }
+
+    /// One URL of an `@import`, which is plain CSS if it looks like CSS or has media queries
+    fn import_argument(&mut self) -> Result<Import<'a>> {
+        if self.is_indented() {
+            if let Some(import) = self.try_unquoted_import_argument() {
+                return Ok(import);
+            }
+        }
+        if matches!(self.peek(), 'u' | 'U') {
+            return self.url_import();
+        }
+
+        let start = self.offset();
+        let url = self.string()?;
+        let url_span = self.span_from(start);
+        let modifiers = self.try_import_modifiers()?;
+        if is_plain_import_url(&url) || modifiers.is_some() {
+            let url = self.ast.plain_interpolation(url_span, self.source_text(url_span));
+            return Ok(self.ast.import_static(self.span_from(start), url, modifiers));
+        }
+        Ok(self.ast.import_dynamic(url_span, self.ast.str(&url)))
+    }
+
+    /// `url(...)` and any modifiers, which is always a plain CSS import
+    pub(crate) fn url_import(&mut self) -> Result<Import<'a>> {
+        let start = self.offset();
+        let url = self.dynamic_url()?;
+        let mut buffer = InterpolationBuffer::new(&self.ast, start);
+        self.add_or_inject(&mut buffer, url);
+        let url = buffer.finish(&self.ast, self.offset());
+        let modifiers = self.try_import_modifiers()?;
+        Ok(self.ast.import_static(self.span_from(start), url, modifiers))
+    }
+
+    /// `url(...)`, as a special function if it's a plain URL and a function call otherwise
+    pub(crate) fn dynamic_url(&mut self) -> Result<Expression<'a>> {
+        let start = self.offset();
+        self.expect_keyword("url")?;
+        if let Some(contents) = self.try_url_contents(start, None)? {
+            return Ok(self.ast.expression_string(self.span_from(start), contents, false));
+        }
+        let name = self.ast.plain_interpolation(self.span_from(start), "url");
+        let arguments = self.argument_list()?;
+        Ok(self.ast.expression_interpolated_function(self.span_from(start), name, arguments))
+    }
+
+    /// Media queries or `supports(...)` after an `@import` URL
+    pub(crate) fn try_import_modifiers(&mut self) -> Result<Option<Interpolation<'a>>> {
+        let checkpoint = self.checkpoint();
+        self.whitespace()?;
+        if self.at_end_of_statement() || self.peek() == ',' {
+            self.rewind(checkpoint);
+            return Ok(None);
+        }
+        self.almost_any_value().map(Some)
+    }
+
+    /// `@include [namespace.]name[(arguments)] [using (parameters)] [{ ... }]`
+    fn include_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        let mut namespace = None;
+        let mut name = self.identifier(false)?;
+        if self.next_eq('.') {
+            namespace = Some(Atom::from(name));
+            name = self.identifier(false)?;
+        }
+        self.whitespace()?;
+        let arguments = if self.peek() == '(' {
+            self.mixin_argument_list()?
+        } else {
+            self.empty_argument_list()
+        };
+        let mut span = self.span_from(start);
+        self.whitespace()?;
+
+        let parameters = if self.scan_identifier("using") {
+            self.whitespace()?;
+            let parameters = self.parameter_list()?;
+            self.whitespace()?;
+            Some(parameters)
+        } else {
+            None
+        };
+        let content = if parameters.is_some() || self.looking_at_children() {
+            let block_start = self.offset();
+            let parameters = parameters.unwrap_or_else(|| self.empty_parameter_list());
+            let body =
+                self.with_context(Context::CONTENT_BLOCK, |p| p.children(Self::statement))?;
+            span = self.span_from(start);
+            Some(self.ast.content_block(self.span_from(block_start), parameters, body))
+        } else {
+            self.expect_statement_separator()?;
+            None
+        };
+        Ok(self.ast.statement_include(span, namespace, Atom::from(name), arguments, content))
+    }
+
+    /// `@media query { ... }`
+    pub(crate) fn media_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        let query = self.media_query_list()?;
+        self.whitespace()?;
+        let body = self.children(Self::statement)?;
+        Ok(self.ast.statement_media(self.span_from(start), query, body))
+    }
+
+    /// `@mixin name[(parameters)] { ... }`
+    fn mixin_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        let doc = self.take_doc_comment();
+        let name = Atom::from(self.identifier(false)?);
+        if self.context.in_mixin() {
+            return Err(Diagnostic::MixinInMixin(self.span_from(start)));
+        }
+        if self.context.in_control_directive() {
+            return Err(Diagnostic::MixinInControlDirective(self.span_from(start)));
+        }
+        self.whitespace()?;
+        let parameters =
+            if self.peek() == '(' { self.parameter_list()? } else { self.empty_parameter_list() };
+        self.whitespace()?;
+        let outer_has_content = std::mem::replace(&mut self.mixin_has_content, false);
+        let body = self.with_context(Context::MIXIN, |p| p.children(Self::statement));
+        let has_content = std::mem::replace(&mut self.mixin_has_content, outer_has_content);
+        let span = self.span_from(start);
+        Ok(self.ast.statement_mixin(span, name, parameters, body?, has_content, doc))
+    }
+
+    /// `@supports condition { ... }`
+    pub(crate) fn supports_rule(&mut self, start: u32) -> Result<Statement<'a>> {
+        let condition = self.supports_condition()?;
+        self.whitespace()?;
+        let body = self.children(Self::statement)?;
+        Ok(self.ast.statement_supports(self.span_from(start), condition, body))
+    }
+
+    /// `@use "url" [as namespace] [with (...)];`
+    fn use_rule(&mut self, start: u32, use_allowed: bool) -> Result<Statement<'a>> {
+        let url_start = self.offset();
+        let url = self.string()?;
+        let url_span = self.span_from(url_start);
+        self.whitespace()?;
+        let namespace = if self.scan_identifier("as") {
+            self.whitespace()?;
+            if self.next_eq('*') {
+                None
+            } else {
+                Some(Atom::from(self.identifier(false)?))
+            }
+        } else {
+            Some(default_namespace(&url, url_span)?)
+        };
+        self.whitespace()?;
+        let configuration = if self.scan_identifier("with") {
+            self.configuration(false)?
+        } else {
+            self.ast.new_vec()
+        };
+        let span = self.span_from(start);
+        self.whitespace()?;
+        self.expect_statement_separator()?;
+        if !use_allowed {
+            return Err(Diagnostic::UseAfterRule(span));
+        }
+        Ok(self.ast.statement_use(span, self.ast.str(&url), namespace, configuration))
+    }
+
+    /// `@while condition { ... }`
+    fn while_rule(&mut self, start: u32, child: ChildParser<'a>) -> Result<Statement<'a>> {
+        let condition = self.expression()?;
+        self.whitespace()?;
+        let body = self.with_context(Context::CONTROL_DIRECTIVE, |p| p.children(child))?;
+        Ok(self.ast.statement_while(self.span_from(start), condition, body))
+    }
+
+    /// An at-rule Sass passes through without knowing its structure, such as `@font-face`
+    pub(crate) fn unknown_at_rule(
+        &mut self,
+        start: u32,
+        name: Interpolation<'a>,
+        child: ChildParser<'a>,
+    ) -> Result<Statement<'a>> {
+        let value = if self.looking_at_children() || self.at_end_of_statement() {
+            None
+        } else {
+            Some(self.almost_any_value()?)
+        };
+        if !self.looking_at_children() {
+            let span = self.span_from(start);
+            self.expect_statement_separator()?;
+            return Ok(self.ast.statement_at_rule(span, name, value, None));
+        }
+        let lowercase = name.as_plain().map(str::to_ascii_lowercase);
+        let flags = match lowercase.as_deref() {
+            Some("font-face") => Context::FONT_FACE,
+            _ => Context::DECLARATION_AT_RULE,
+        };
+        let body = self.with_context(flags, |p| p.children(child))?;
+        Ok(self.ast.statement_at_rule(self.span_from(start), name, value, Some(body)))
+    }
+
+    /// `($a, $b: default, $rest...)`
+    pub(crate) fn parameter_list(&mut self) -> Result<ParameterList<'a>> {
+        let start = self.offset();
+        self.expect_char('(')?;
+        self.whitespace()?;
+        let mut parameters: Vec<'a, _> = self.ast.new_vec();
+        let mut rest = None;
+        while self.peek() == '$' {
+            let parameter_start = self.offset();
+            let name = Atom::from(self.variable_name()?);
+            self.whitespace()?;
+            let default = if self.next_eq(':') {
+                self.whitespace()?;
+                Some(self.expression_until_comma()?)
+            } else if self.next_eq('.') {
+                self.expect_char('.')?;
+                self.expect_char('.')?;
+                self.whitespace()?;
+                rest = Some(name);
+                break;
+            } else {
+                None
+            };
+            let span = self.span_from(parameter_start);
+            if parameters.iter().any(|parameter: &scr_ast::node::Parameter| parameter.name == name)
+            {
+                return Err(Diagnostic::DuplicateParameter(span));
+            }
+            parameters.push(self.ast.parameter(span, name, default));
+            self.whitespace()?;
+            if !self.next_eq(',') {
+                break;
+            }
+            self.whitespace()?;
+        }
+        self.expect_char(')')?;
+        Ok(self.ast.parameter_list(self.span_from(start), parameters, rest))
+    }
+
+    fn empty_parameter_list(&self) -> ParameterList<'a> {
+        self.ast.parameter_list(Span::empty(self.offset()), self.ast.new_vec(), None)
+    }
+
+    pub(crate) fn empty_argument_list(&self) -> ArgumentList<'a> {
+        let span = Span::empty(self.offset());
+        self.ast.argument_list(span, self.ast.new_vec(), self.ast.new_vec(), None, None)
+    }
+}
+
+/// Strips a vendor prefix such as `-webkit-` from `name`.
+pub fn unvendor(name: &str) -> &str {
+    if !name.starts_with('-') || name.starts_with("--") {
+        return name;
+    }
+    name[1..].find('-').map_or(name, |index| &name[index + 2..])
+}
+
+/// Whether an `@import` URL refers to plain CSS, which is emitted as-is
+pub fn is_plain_import_url(url: &str) -> bool {
+    if url.len() < 5 {
+        return false;
+    }
+    url.to_ascii_lowercase().ends_with(".css")
+        || url.starts_with("http://")
+        || url.starts_with("https://")
+        || url.starts_with("//")
+}
+
+/// The namespace of `@use "url"` without `as`: the basename without extensions
+fn default_namespace(url: &str, span: Span) -> Result<Atom> {
+    let basename = url.rsplit(['/', ':']).next().unwrap_or(url);
+    let namespace = basename.split('.').next().unwrap_or(basename);
+    let mut chars = namespace.strip_prefix('-').unwrap_or(namespace).chars();
+    let valid = chars.next().is_some_and(|c| is_ident_start(c) || c == '-') && chars.all(is_ident);
+    if !valid {
+        return Err(Diagnostic::InvalidDefaultNamespace(namespace.to_string(), span));
+    }
+    Ok(Atom::from(namespace))
+}
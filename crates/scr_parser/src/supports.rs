@@ -0,0 +1,107 @@
+//! Supports conditions
+//!
+//! <https://drafts.csswg.org/css-conditional-3/#at-supports>
+
+use scr_ast::node::{Expression, InterpolationPart, SupportsCondition, SupportsOperator};
+use scr_diagnostics::{Diagnostic, Result};
+
+use crate::Parser;
+
+impl<'a> Parser<'a> {
+    /// `not (a)`, or conditions joined by one of `and` and `or`
+    pub(crate) fn supports_condition(&mut self) -> Result<SupportsCondition<'a>> {
+        let start = self.offset();
+        if self.scan_identifier("not") {
+            self.whitespace()?;
+            let condition = self.supports_condition_in_parens()?;
+            return Ok(self.ast.supports_condition_negation(self.span_from(start), condition));
+        }
+        let mut condition = self.supports_condition_in_parens()?;
+        let mut operator = None;
+        loop {
+            let checkpoint = self.checkpoint();
+            self.whitespace()?;
+            if !self.looking_at_identifier() {
+                self.rewind(checkpoint);
+                break;
+            }
+            let next = match operator {
+                Some(operator) => {
+                    self.expect_keyword(SupportsOperator::as_str(operator))?;
+                    operator
+                }
+                None if self.scan_identifier("or") => SupportsOperator::Or,
+                None => {
+                    self.expect_keyword("and")?;
+                    SupportsOperator::And
+                }
+            };
+            operator = Some(next);
+            self.whitespace()?;
+            let right = self.supports_condition_in_parens()?;
+            let span = self.span_from(start);
+            condition = self.ast.supports_condition_operation(span, condition, next, right);
+        }
+        Ok(condition)
+    }
+
+    /// `(name: value)`, `(condition)`, `function(...)`, `#{...}`, or anything else in parentheses
+    fn supports_condition_in_parens(&mut self) -> Result<SupportsCondition<'a>> {
+        let start = self.offset();
+        if self.looking_at_interpolated_identifier() {
+            let identifier = self.interpolated_identifier()?;
+            if self.next_eq('(') {
+                let arguments = self.interpolated_declaration_value(true)?;
+                self.expect_char(')')?;
+                let span = self.span_from(start);
+                return Ok(self.ast.supports_condition_function(span, identifier, arguments));
+            }
+            let mut parts = identifier.parts.into_iter();
+            return match (parts.next(), parts.next()) {
+                (Some(InterpolationPart::Expression(expression)), None) => {
+                    Ok(self.ast.supports_condition_interpolation(self.span_from(start), expression))
+                }
+                _ => Err(Diagnostic::Expected("@supports condition", identifier.span)),
+            };
+        }
+
+        self.expect_char('(')?;
+        self.whitespace()?;
+        if self.peek() == '(' || self.looking_at_keyword("not") {
+            let condition = self.supports_condition()?;
+            self.whitespace()?;
+            self.expect_char(')')?;
+            return Ok(condition);
+        }
+
+        let checkpoint = self.checkpoint();
+        if let Some((name, value)) = self.try_supports_declaration()? {
+            return Ok(self.ast.supports_condition_declaration(self.span_from(start), name, value));
+        }
+        self.rewind(checkpoint);
+        let contents = self.interpolated_declaration_value(true)?;
+        self.expect_char(')')?;
+        Ok(self.ast.supports_condition_anything(self.span_from(start), contents))
+    }
+
+    /// `name: value)` after the `(`, or `None` if it isn't a declaration
+    fn try_supports_declaration(&mut self) -> Result<Option<(Expression<'a>, Expression<'a>)>> {
+        let Ok(name) = self.expression() else { return Ok(None) };
+        self.whitespace()?;
+        if !self.next_eq(':') {
+            return Ok(None);
+        }
+        self.whitespace()?;
+        let is_custom_property = matches!(&name, Expression::String(string)
+            if !string.quoted && string.text.as_plain().is_some_and(|name| name.starts_with("--")));
+        let value = if is_custom_property {
+            let text = self.interpolated_declaration_value(false)?;
+            self.ast.expression_string(text.span, text, false)
+        } else {
+            self.expression()?
+        };
+        self.whitespace()?;
+        self.expect_char(')')?;
+        Ok(Some((name, value)))
+    }
+}
@@ -0,0 +1,142 @@
+//! Comparisons that treat numbers within [`EPSILON`] of each other as equal
+//!
+//! Sass numbers are printed with 10 digits after the decimal point, so numbers that differ only
+//! beyond that, e.g. after converting `1in` to `px` and back, are the same number.
+
+use std::hash::{Hash, Hasher};
+
+/// How far apart two numbers may be and still be equal
+pub const EPSILON: f64 = 1e-11;
+
+const INVERSE_EPSILON: f64 = 1.0 / EPSILON;
+
+#[must_use]
+#[allow(clippy::float_cmp)]
+pub fn equals(number1: f64, number2: f64) -> bool {
+    if number1 == number2 {
+        return true;
+    }
+    // Rounding as well keeps numbers that print the same equal, even across a multiple of EPSILON.
+    (number1 - number2).abs() <= EPSILON
+        && (number1 * INVERSE_EPSILON).round() == (number2 * INVERSE_EPSILON).round()
+}
+
+#[must_use]
+pub fn less_than(number1: f64, number2: f64) -> bool {
+    number1 < number2 && !equals(number1, number2)
+}
+
+#[must_use]
+pub fn less_than_or_equals(number1: f64, number2: f64) -> bool {
+    number1 < number2 || equals(number1, number2)
+}
+
+#[must_use]
+pub fn greater_than(number1: f64, number2: f64) -> bool {
+    number1 > number2 && !equals(number1, number2)
+}
+
+#[must_use]
+pub fn greater_than_or_equals(number1: f64, number2: f64) -> bool {
+    number1 > number2 || equals(number1, number2)
+}
+
+/// Whether `number` is within [`EPSILON`] of an integer
+#[must_use]
+pub fn is_int(number: f64) -> bool {
+    if !number.is_finite() {
+        return false;
+    }
+    // Checking against 0.5 catches numbers both slightly above and slightly below an integer.
+    equals((number - 0.5).abs().rem_euclid(1.0), 0.5)
+}
+
+/// The integer `number` is within [`EPSILON`] of, if it fits in an `i64`
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn as_int(number: f64) -> Option<i64> {
+    // 2^63, the first integer past `i64::MAX`, which a cast would saturate to.
+    const I64_END: f64 = 9_223_372_036_854_775_808.0;
+    if !number.is_finite() {
+        return None;
+    }
+    let rounded = number.round();
+    ((-I64_END..I64_END).contains(&rounded) && equals(number, rounded)).then_some(rounded as i64)
+}
+
+/// Rounds `number` to the nearest integer, numbers within [`EPSILON`] of a half away from zero
+#[must_use]
+pub fn round(number: f64) -> f64 {
+    let fraction = number.rem_euclid(1.0);
+    let down =
+        if number > 0.0 { less_than(fraction, 0.5) } else { less_than_or_equals(fraction, 0.5) };
+    if down {
+        number.floor()
+    } else {
+        number.ceil()
+    }
+}
+
+/// `number` if it's between `min` and `max`, where numbers fuzzily equal to a bound are that bound
+#[must_use]
+pub fn check_range(number: f64, min: f64, max: f64) -> Option<f64> {
+    if equals(number, min) {
+        Some(min)
+    } else if equals(number, max) {
+        Some(max)
+    } else {
+        (number > min && number < max).then_some(number)
+    }
+}
+
+/// Hashes `number` so that numbers [`equals`] treats as equal usually hash the same
+pub fn hash<H: Hasher>(number: f64, state: &mut H) {
+    // Adding zero turns `-0.0` into `0.0`, which rounds from the same numbers.
+    ((number * INVERSE_EPSILON).round() + 0.0).to_bits().hash(state);
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod test {
+    use std::{collections::hash_map::DefaultHasher, hash::Hasher};
+
+    use super::{as_int, equals, is_int, round};
+
+    #[test]
+    fn rounding_errors_are_ignored() {
+        assert!(equals(0.1 + 0.2, 0.3));
+        assert!(equals(1.0, 1.0 + 1e-12));
+        assert!(!equals(1.0, 1.0 + 1e-10));
+        assert!(is_int(2.999_999_999_999_9));
+        assert!(is_int(-3.000_000_000_000_1));
+        assert!(!is_int(2.5));
+        assert!(!is_int(f64::INFINITY));
+        assert_eq!(as_int(-3.000_000_000_000_1), Some(-3));
+        assert_eq!(as_int(0.5), None);
+        assert_eq!(as_int(1e19), None);
+        assert_eq!(as_int(-1e30), None);
+        assert_eq!(as_int(-9_223_372_036_854_775_808.0), Some(i64::MIN));
+    }
+
+    #[test]
+    fn hashes_large_numbers_apart() {
+        let hash = |number| {
+            let mut hasher = DefaultHasher::new();
+            super::hash(number, &mut hasher);
+            hasher.finish()
+        };
+        assert_ne!(hash(1e8), hash(2e8));
+        assert_ne!(hash(1e30), hash(-1e30));
+        assert_eq!(hash(0.0), hash(-1e-13));
+        assert_eq!(hash(1.0), hash(1.0 + 1e-13));
+    }
+
+    #[test]
+    fn rounds_halves_away_from_zero() {
+        assert_eq!(round(2.5), 3.0);
+        assert_eq!(round(2.499_999_999_999_9), 3.0);
+        assert_eq!(round(2.4), 2.0);
+        assert_eq!(round(-2.5), -3.0);
+        assert_eq!(round(-2.6), -3.0);
+    }
+}
@@ -0,0 +1,9 @@
+//! Sass values
+//!
+//! The values expressions evaluate to, with the unit and equality rules of Dart Sass.
+
+pub mod fuzzy;
+mod number;
+pub mod unit;
+
+pub use number::SassNumber;
@@ -0,0 +1,669 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+use scr_ast::{node::NumberLiteral, Atom, Span};
+use scr_diagnostics::{Diagnostic, Result};
+
+use crate::{
+    fuzzy,
+    unit::{canonical_multiplier, canonicalize, conversion_factor, UnitType},
+};
+
+/// A number with units, e.g. `1.5`, `10px` or `1px*em/s`
+///
+/// Units in the denominator come from divisions such as `$a / 1s`. Units that convert into each
+/// other never appear in both lists, they cancel out when the number is made.
+#[derive(Debug, Clone)]
+pub struct SassNumber {
+    value: f64,
+    numerator_units: Vec<Atom>,
+    denominator_units: Vec<Atom>,
+    /// The expression the number was produced by, for errors
+    span: Span,
+}
+
+impl SassNumber {
+    #[must_use]
+    pub const fn new(value: f64, span: Span) -> Self {
+        Self { value, numerator_units: Vec::new(), denominator_units: Vec::new(), span }
+    }
+
+    #[must_use]
+    pub fn with_unit(value: f64, unit: Atom, span: Span) -> Self {
+        Self { value, numerator_units: vec![unit], denominator_units: Vec::new(), span }
+    }
+
+    /// A number with `numerators` over `denominators`, where units of the same type cancel out,
+    /// e.g. `px` over `in` becomes a unitless number 96 times smaller.
+    #[must_use]
+    pub fn with_units(value: f64, numerators: &[Atom], denominators: &[Atom], span: Span) -> Self {
+        let (value, numerator_units, denominator_units) =
+            multiply_units(value, numerators, &[], &[], denominators);
+        Self { value, numerator_units, denominator_units, span }
+    }
+
+    #[must_use]
+    pub const fn value(&self) -> f64 {
+        self.value
+    }
+
+    #[must_use]
+    pub fn numerator_units(&self) -> &[Atom] {
+        &self.numerator_units
+    }
+
+    #[must_use]
+    pub fn denominator_units(&self) -> &[Atom] {
+        &self.denominator_units
+    }
+
+    #[must_use]
+    pub const fn span(&self) -> Span {
+        self.span
+    }
+
+    /// The same number for the expression at `span`
+    #[must_use]
+    pub fn with_span(self, span: Span) -> Self {
+        Self { span, ..self }
+    }
+
+    /// The same units with another value
+    #[must_use]
+    pub fn with_value(&self, value: f64) -> Self {
+        Self { value, ..self.clone() }
+    }
+
+    #[must_use]
+    pub const fn has_units(&self) -> bool {
+        !self.numerator_units.is_empty() || !self.denominator_units.is_empty()
+    }
+
+    /// Whether the units are more than a single numerator, such as `px*em` or `1/s`
+    #[must_use]
+    pub const fn has_complex_units(&self) -> bool {
+        self.numerator_units.len() > 1 || !self.denominator_units.is_empty()
+    }
+
+    /// Whether the number has exactly `unit`, without converting
+    #[must_use]
+    pub fn has_unit(&self, unit: &str) -> bool {
+        self.numerator_units.len() == 1
+            && self.denominator_units.is_empty()
+            && self.numerator_units[0] == unit
+    }
+
+    /// Whether the number can be coerced to `unit`
+    #[must_use]
+    pub fn compatible_with_unit(&self, unit: &str) -> bool {
+        if self.has_complex_units() {
+            return false;
+        }
+        self.numerator_units
+            .first()
+            .is_none_or(|numerator| conversion_factor(numerator, unit).is_some())
+    }
+
+    /// Whether the value is an integer, ignoring differences smaller than [`fuzzy::EPSILON`]
+    #[must_use]
+    pub fn is_int(&self) -> bool {
+        fuzzy::is_int(self.value)
+    }
+
+    #[must_use]
+    pub fn as_int(&self) -> Option<i64> {
+        fuzzy::as_int(self.value)
+    }
+
+    /// # Errors
+    ///
+    /// If the value isn't an integer.
+    pub fn assert_int(&self) -> Result<i64> {
+        self.as_int().ok_or_else(|| Diagnostic::NotAnInt(self.to_string(), self.span))
+    }
+
+    /// Whether both numbers have the same number of units, which convert into each other
+    #[must_use]
+    pub fn has_compatible_units(&self, other: &Self) -> bool {
+        self.numerator_units.len() == other.numerator_units.len()
+            && self.denominator_units.len() == other.denominator_units.len()
+            && self.is_comparable_to(other)
+    }
+
+    /// Whether the numbers can be added, subtracted or compared
+    #[must_use]
+    pub fn is_comparable_to(&self, other: &Self) -> bool {
+        !self.has_units() || !other.has_units() || self.coerce_value_to_match(other).is_ok()
+    }
+
+    /// The number in `numerators` over `denominators`, where a unitless number takes any units
+    /// and any number may lose all of its units
+    ///
+    /// # Errors
+    ///
+    /// If the units don't convert into each other.
+    pub fn coerce(&self, numerators: &[Atom], denominators: &[Atom]) -> Result<Self> {
+        let value = self.coerce_value(numerators, denominators)?;
+        Ok(Self::with_units(value, numerators, denominators, self.span))
+    }
+
+    /// The value of [`Self::coerce`]
+    ///
+    /// # Errors
+    ///
+    /// If the units don't convert into each other.
+    pub fn coerce_value(&self, numerators: &[Atom], denominators: &[Atom]) -> Result<f64> {
+        self.coerce_or_convert_value(numerators, denominators, true, None)
+    }
+
+    /// The value in `unit`
+    ///
+    /// # Errors
+    ///
+    /// If the number's units don't convert to `unit`.
+    pub fn coerce_value_to_unit(&self, unit: Atom) -> Result<f64> {
+        self.coerce_value(&[unit], &[])
+    }
+
+    /// The number in the units of `other`, as [`Self::coerce`]
+    ///
+    /// # Errors
+    ///
+    /// If the units don't convert into each other.
+    pub fn coerce_to_match(&self, other: &Self) -> Result<Self> {
+        let value = self.coerce_value_to_match(other)?;
+        Ok(Self { value, ..other.clone() }.with_span(self.span))
+    }
+
+    /// The value of [`Self::coerce_to_match`]
+    ///
+    /// # Errors
+    ///
+    /// If the units don't convert into each other.
+    pub fn coerce_value_to_match(&self, other: &Self) -> Result<f64> {
+        self.coerce_or_convert_value(
+            &other.numerator_units,
+            &other.denominator_units,
+            true,
+            Some(other),
+        )
+    }
+
+    /// The number in `numerators` over `denominators`, unlike [`Self::coerce`] a unitless number
+    /// stays unitless and a number with units keeps some
+    ///
+    /// # Errors
+    ///
+    /// If the units don't convert into each other, or only one side has units.
+    pub fn convert(&self, numerators: &[Atom], denominators: &[Atom]) -> Result<Self> {
+        let value = self.convert_value(numerators, denominators)?;
+        Ok(Self::with_units(value, numerators, denominators, self.span))
+    }
+
+    /// The value of [`Self::convert`]
+    ///
+    /// # Errors
+    ///
+    /// If the units don't convert into each other, or only one side has units.
+    pub fn convert_value(&self, numerators: &[Atom], denominators: &[Atom]) -> Result<f64> {
+        self.coerce_or_convert_value(numerators, denominators, false, None)
+    }
+
+    /// The number in the units of `other`, as [`Self::convert`]
+    ///
+    /// # Errors
+    ///
+    /// If the units don't convert into each other, or only one side has units.
+    pub fn convert_to_match(&self, other: &Self) -> Result<Self> {
+        let value = self.convert_value_to_match(other)?;
+        Ok(Self { value, ..other.clone() }.with_span(self.span))
+    }
+
+    /// The value of [`Self::convert_to_match`]
+    ///
+    /// # Errors
+    ///
+    /// If the units don't convert into each other, or only one side has units.
+    pub fn convert_value_to_match(&self, other: &Self) -> Result<f64> {
+        self.coerce_or_convert_value(
+            &other.numerator_units,
+            &other.denominator_units,
+            false,
+            Some(other),
+        )
+    }
+
+    /// Converts the value to `numerators` over `denominators`, `other` is the number those units
+    /// come from, if any, to name in the error.
+    fn coerce_or_convert_value(
+        &self,
+        numerators: &[Atom],
+        denominators: &[Atom],
+        coerce_unitless: bool,
+        other: Option<&Self>,
+    ) -> Result<f64> {
+        if self.numerator_units == numerators && self.denominator_units == denominators {
+            return Ok(self.value);
+        }
+        let other_has_units = !numerators.is_empty() || !denominators.is_empty();
+        if coerce_unitless && (!self.has_units() || !other_has_units) {
+            return Ok(self.value);
+        }
+        let error = || self.compatibility_error(numerators, denominators, other);
+        if self.has_units() != other_has_units {
+            return Err(error());
+        }
+
+        let mut value = self.value;
+        let mut old_numerators = self.numerator_units.clone();
+        for numerator in numerators {
+            let factor = remove_convertible(&mut old_numerators, *numerator).ok_or_else(error)?;
+            value *= factor;
+        }
+        let mut old_denominators = self.denominator_units.clone();
+        for denominator in denominators {
+            let factor =
+                remove_convertible(&mut old_denominators, *denominator).ok_or_else(error)?;
+            value /= factor;
+        }
+        if !old_numerators.is_empty() || !old_denominators.is_empty() {
+            return Err(error());
+        }
+        Ok(value)
+    }
+
+    fn compatibility_error(
+        &self,
+        numerators: &[Atom],
+        denominators: &[Atom],
+        other: Option<&Self>,
+    ) -> Diagnostic {
+        if let Some(other) = other {
+            let note = if self.has_units() && other.has_units() {
+                ""
+            } else {
+                " (one has units and the other doesn't)"
+            };
+            return Diagnostic::IncompatibleUnits(
+                self.to_string(),
+                other.to_string(),
+                note,
+                self.span.merge(other.span),
+            );
+        }
+        let expected = match (numerators, denominators) {
+            ([], []) => "no units".to_string(),
+            ([unit], []) if UnitType::of(unit).is_some() => {
+                let unit_type = UnitType::of(unit).unwrap_or(UnitType::Length);
+                let name = unit_type.as_str();
+                let article = if name.starts_with(['a', 'e', 'i', 'o', 'u']) { "an" } else { "a" };
+                let units = unit_type.units().collect::<Vec<_>>().join(", ");
+                format!("{article} {name} unit ({units})")
+            }
+            _ => {
+                let plural = if numerators.len() + denominators.len() == 1 { "" } else { "s" };
+                format!("unit{plural} {}", unit_string(numerators, denominators))
+            }
+        };
+        Diagnostic::ExpectedUnits(self.to_string(), expected, self.span)
+    }
+
+    /// `self + other`, where a unitless number takes the units of the other
+    ///
+    /// # Errors
+    ///
+    /// If the units don't convert into each other.
+    pub fn plus(&self, other: &Self) -> Result<Self> {
+        self.operate(other, |number1, number2| number1 + number2)
+    }
+
+    /// `self - other`, where a unitless number takes the units of the other
+    ///
+    /// # Errors
+    ///
+    /// If the units don't convert into each other.
+    pub fn minus(&self, other: &Self) -> Result<Self> {
+        self.operate(other, |number1, number2| number1 - number2)
+    }
+
+    /// `self % other`, which has the sign of `other` as in Ruby Sass
+    ///
+    /// # Errors
+    ///
+    /// If the units don't convert into each other.
+    pub fn modulo(&self, other: &Self) -> Result<Self> {
+        self.operate(other, modulo_like_sass)
+    }
+
+    /// `self * other`, where units of the same type in a numerator and a denominator cancel out
+    #[must_use]
+    pub fn times(&self, other: &Self) -> Self {
+        let (value, numerator_units, denominator_units) = multiply_units(
+            self.value * other.value,
+            &self.numerator_units,
+            &self.denominator_units,
+            &other.numerator_units,
+            &other.denominator_units,
+        );
+        Self { value, numerator_units, denominator_units, span: self.span.merge(other.span) }
+    }
+
+    /// `self / other`, where units of the same type in a numerator and a denominator cancel out
+    #[must_use]
+    pub fn divided_by(&self, other: &Self) -> Self {
+        let (value, numerator_units, denominator_units) = multiply_units(
+            self.value / other.value,
+            &self.numerator_units,
+            &self.denominator_units,
+            &other.denominator_units,
+            &other.numerator_units,
+        );
+        Self { value, numerator_units, denominator_units, span: self.span.merge(other.span) }
+    }
+
+    /// # Errors
+    ///
+    /// If the units don't convert into each other.
+    pub fn greater_than(&self, other: &Self) -> Result<bool> {
+        self.coerce_units(other, fuzzy::greater_than)
+    }
+
+    /// # Errors
+    ///
+    /// If the units don't convert into each other.
+    pub fn greater_than_or_equals(&self, other: &Self) -> Result<bool> {
+        self.coerce_units(other, fuzzy::greater_than_or_equals)
+    }
+
+    /// # Errors
+    ///
+    /// If the units don't convert into each other.
+    pub fn less_than(&self, other: &Self) -> Result<bool> {
+        self.coerce_units(other, fuzzy::less_than)
+    }
+
+    /// # Errors
+    ///
+    /// If the units don't convert into each other.
+    pub fn less_than_or_equals(&self, other: &Self) -> Result<bool> {
+        self.coerce_units(other, fuzzy::less_than_or_equals)
+    }
+
+    fn operate(&self, other: &Self, operation: impl Fn(f64, f64) -> f64) -> Result<Self> {
+        let span = self.span.merge(other.span);
+        if !self.has_units() {
+            let value = operation(self.value, other.value);
+            return Ok(Self { value, ..other.clone() }.with_span(span));
+        }
+        let value = self.coerce_units(other, operation)?;
+        Ok(self.with_value(value).with_span(span))
+    }
+
+    /// Applies `operation` to the value and `other`'s value in the units of `self`.
+    fn coerce_units<T>(&self, other: &Self, operation: impl Fn(f64, f64) -> T) -> Result<T> {
+        match other.coerce_value_to_match(self) {
+            Ok(value) => Ok(operation(self.value, value)),
+            Err(error) => {
+                // The other direction fails as well, with the numbers in the order they were
+                // written in.
+                self.coerce_value_to_match(other)?;
+                Err(error)
+            }
+        }
+    }
+
+    /// The value in the canonical units of their types, to compare and hash
+    fn canonical_value(&self) -> f64 {
+        let multiplier =
+            |units: &[Atom]| units.iter().map(|unit| canonical_multiplier(unit)).product::<f64>();
+        self.value * multiplier(&self.numerator_units) / multiplier(&self.denominator_units)
+    }
+}
+
+/// Removes the first of `units` that converts to `unit`, returning the number of `unit`s per
+/// removed unit.
+fn remove_convertible(units: &mut Vec<Atom>, unit: Atom) -> Option<f64> {
+    let (index, factor) = units
+        .iter()
+        .enumerate()
+        .find_map(|(index, old)| conversion_factor(&unit, old).map(|factor| (index, factor)))?;
+    units.remove(index);
+    Some(factor)
+}
+
+/// Multiplies `numerators / denominators` by `other_numerators / other_denominators`, cancelling
+/// units that convert into each other.
+fn multiply_units(
+    mut value: f64,
+    numerators: &[Atom],
+    denominators: &[Atom],
+    other_numerators: &[Atom],
+    other_denominators: &[Atom],
+) -> (f64, Vec<Atom>, Vec<Atom>) {
+    let mut new_numerators = vec![];
+    let mut remaining_other_denominators = other_denominators.to_vec();
+    let mut remaining_denominators = denominators.to_vec();
+    for numerator in numerators {
+        match remove_convertible(&mut remaining_other_denominators, *numerator) {
+            Some(factor) => value /= factor,
+            None => new_numerators.push(*numerator),
+        }
+    }
+    for numerator in other_numerators {
+        match remove_convertible(&mut remaining_denominators, *numerator) {
+            Some(factor) => value /= factor,
+            None => new_numerators.push(*numerator),
+        }
+    }
+    remaining_denominators.extend(remaining_other_denominators);
+    (value, new_numerators, remaining_denominators)
+}
+
+/// `number1 % number2` with the sign of `number2`
+fn modulo_like_sass(number1: f64, number2: f64) -> f64 {
+    if number1.is_infinite() {
+        return f64::NAN;
+    }
+    if number2.is_infinite() {
+        return if number1.is_sign_negative() == number2.is_sign_negative() {
+            number1
+        } else {
+            number2
+        };
+    }
+    if number2 == 0.0 {
+        return f64::NAN;
+    }
+    let result = number1.rem_euclid(number2);
+    if number2 > 0.0 || result == 0.0 {
+        result
+    } else {
+        result + number2
+    }
+}
+
+/// The units as written in errors, e.g. `px*em/s`
+fn unit_string(numerators: &[Atom], denominators: &[Atom]) -> String {
+    let join =
+        |units: &[Atom]| units.iter().map(|unit| unit.as_str()).collect::<Vec<_>>().join("*");
+    match (numerators, denominators) {
+        ([], []) => "no units".to_string(),
+        ([], [denominator]) => format!("{denominator}^-1"),
+        ([], _) => format!("({})^-1", join(denominators)),
+        (_, []) => join(numerators),
+        _ => format!("{}/{}", join(numerators), join(denominators)),
+    }
+}
+
+impl From<&NumberLiteral> for SassNumber {
+    fn from(literal: &NumberLiteral) -> Self {
+        literal.unit.map_or_else(
+            || Self::new(literal.value, literal.span),
+            |unit| Self::with_unit(literal.value, unit, literal.span),
+        )
+    }
+}
+
+/// Numbers are equal when their values in canonical units are [`fuzzy::equals`], e.g. `1in`
+/// and `96px`.
+impl PartialEq for SassNumber {
+    fn eq(&self, other: &Self) -> bool {
+        if self.numerator_units.len() != other.numerator_units.len()
+            || self.denominator_units.len() != other.denominator_units.len()
+        {
+            return false;
+        }
+        if !self.has_units() {
+            return fuzzy::equals(self.value, other.value);
+        }
+        canonical_units(&self.numerator_units) == canonical_units(&other.numerator_units)
+            && canonical_units(&self.denominator_units) == canonical_units(&other.denominator_units)
+            && fuzzy::equals(self.canonical_value(), other.canonical_value())
+    }
+}
+
+/// As in Dart Sass, fuzzy equality is treated as an equivalence so numbers can be map keys.
+impl Eq for SassNumber {}
+
+impl Hash for SassNumber {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        fuzzy::hash(self.canonical_value(), state);
+    }
+}
+
+/// Orders numbers with compatible units, as [`SassNumber::less_than`] does, except that a number
+/// with units and a unitless one aren't ordered, as they aren't equal either
+impl PartialOrd for SassNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.has_units() != other.has_units() {
+            return None;
+        }
+        let other_value = other.coerce_value_to_match(self).ok()?;
+        if self == other {
+            Some(Ordering::Equal)
+        } else {
+            self.value.partial_cmp(&other_value)
+        }
+    }
+}
+
+/// The units sorted and each in the canonical unit of its type
+fn canonical_units(units: &[Atom]) -> Vec<Atom> {
+    let mut canonical = units.iter().copied().map(canonicalize).collect::<Vec<_>>();
+    if canonical.len() > 1 {
+        canonical.sort();
+    }
+    canonical
+}
+
+/// The number as in errors and `inspect()`, with at most 10 digits after the decimal point
+impl fmt::Display for SassNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.value.is_nan() {
+            f.write_str("NaN")?;
+        } else if self.value.is_infinite() {
+            f.write_str(if self.value > 0.0 { "Infinity" } else { "-Infinity" })?;
+        } else if fuzzy::equals(self.value, self.value.round()) {
+            // Whole numbers are printed from the `f64`, those past `i64` have no `as_int`.
+            write!(f, "{}", self.value.round() + 0.0)?;
+        } else {
+            let digits = format!("{:.10}", self.value);
+            let digits = digits.trim_end_matches('0').trim_end_matches('.');
+            f.write_str(if digits == "-0" { "0" } else { digits })?;
+        }
+        if self.has_units() {
+            f.write_str(&unit_string(&self.numerator_units, &self.denominator_units))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod test {
+    use std::cmp::Ordering;
+
+    use scr_ast::{Atom, Span};
+    use scr_diagnostics::Diagnostic;
+
+    use super::SassNumber;
+
+    fn number(value: f64, numerators: &[&str], denominators: &[&str]) -> SassNumber {
+        let atoms = |units: &[&str]| units.iter().copied().map(Atom::new).collect::<Vec<_>>();
+        SassNumber::with_units(value, &atoms(numerators), &atoms(denominators), Span::new(0, 1))
+    }
+
+    #[test]
+    fn converts_units() {
+        assert_eq!(number(1.0, &["in"], &[]).coerce_value_to_unit(Atom::new("px")).unwrap(), 96.0);
+        let turn = number(0.5, &["turn"], &[]).coerce_value_to_unit(Atom::new("deg")).unwrap();
+        assert_eq!(turn, 180.0);
+        assert_eq!(number(1.0, &["kHz"], &[]), number(1000.0, &["Hz"], &[]));
+        assert_eq!(number(1.0, &["in"], &[]), number(96.0, &["px"], &[]));
+        assert_ne!(number(1.0, &["in"], &[]), number(96.0, &["em"], &[]));
+        assert_eq!(
+            number(3.0, &[], &[]).coerce(&[Atom::new("px")], &[]).unwrap().to_string(),
+            "3px"
+        );
+        assert_eq!(number(3.0, &["px"], &[]).coerce(&[], &[]).unwrap().to_string(), "3");
+        assert!(number(3.0, &[], &[]).convert(&[Atom::new("px")], &[]).is_err());
+    }
+
+    #[test]
+    fn cancels_units() {
+        let speed = number(10.0, &["px"], &["s"]);
+        assert_eq!(speed.to_string(), "10px/s");
+        assert_eq!(speed.times(&number(500.0, &["ms"], &[])).to_string(), "5px");
+        assert_eq!(number(1.0, &["px"], &["in"]).to_string(), "0.0104166667");
+        let area = number(2.0, &["px"], &[]).times(&number(3.0, &["em"], &[]));
+        assert_eq!(area.divided_by(&number(1.0, &["em"], &[])).to_string(), "6px");
+        assert_eq!(number(1.0, &[], &["s", "px"]).to_string(), "1(s*px)^-1");
+        assert_eq!(
+            number(1.0, &["in"], &[]).plus(&number(48.0, &["px"], &[])).unwrap().to_string(),
+            "1.5in"
+        );
+        assert_eq!(
+            number(1.0, &[], &[]).plus(&number(2.0, &["px"], &[])).unwrap().to_string(),
+            "3px"
+        );
+        assert_eq!(number(-1.0, &[], &[]).modulo(&number(3.0, &[], &[])).unwrap().value(), 2.0);
+        assert_eq!(number(1.0, &[], &[]).modulo(&number(-3.0, &[], &[])).unwrap().value(), -2.0);
+    }
+
+    #[test]
+    fn orders_like_equality() {
+        let (px, unitless) = (number(1.0, &["px"], &[]), number(1.0, &[], &[]));
+        assert_ne!(px, unitless);
+        assert_eq!(px.partial_cmp(&unitless), None);
+        assert!(px.less_than_or_equals(&unitless).unwrap());
+        assert!(number(1.0, &["in"], &[]) > number(95.0, &["px"], &[]));
+        let inch = number(1.0, &["in"], &[]);
+        assert_eq!(inch.partial_cmp(&number(96.0, &["px"], &[])), Some(Ordering::Equal));
+        assert_eq!(inch.partial_cmp(&number(1.0, &["s"], &[])), None);
+    }
+
+    #[test]
+    fn incompatible_units() {
+        let error = number(1.0, &["px"], &[]).plus(&number(1.0, &["s"], &[])).unwrap_err();
+        assert_eq!(error.to_string(), "1px and 1s have incompatible units.");
+        let error = number(1.0, &["px"], &[]).convert_to_match(&number(1.0, &[], &[])).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "1px and 1 have incompatible units (one has units and the other doesn't)."
+        );
+        let error = number(1.0, &["em"], &[]).coerce_value_to_unit(Atom::new("deg")).unwrap_err();
+        assert_eq!(error.to_string(), "Expected 1em to have an angle unit (deg, grad, rad, turn).");
+        let error = number(1.0, &["px"], &[]).convert(&[Atom::new("em")], &[Atom::new("s")]);
+        assert_eq!(error.unwrap_err().to_string(), "Expected 1px to have units em/s.");
+        assert!(matches!(
+            number(1.5, &[], &[]).assert_int(),
+            Err(Diagnostic::NotAnInt(number, span)) if number == "1.5" && span == Span::new(0, 1)
+        ));
+        assert_eq!(number(2.000_000_000_000_01, &[], &[]).assert_int().unwrap(), 2);
+        assert_eq!(number(1e19, &[], &[]).to_string(), "10000000000000000000");
+        assert!(number(-1e30, &[], &[]).assert_int().is_err());
+    }
+}
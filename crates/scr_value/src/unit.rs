@@ -0,0 +1,97 @@
+//! The units Sass converts between
+//!
+//! Units of the same type convert into each other, e.g. `1in` is `96px`. Any other unit, such as
+//! `em` or `%`, only matches itself.
+
+use scr_ast::Atom;
+
+/// A kind of unit whose units convert into each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitType {
+    Length,
+    Angle,
+    Time,
+    Frequency,
+    Resolution,
+}
+
+/// Every convertible unit, with how many of its type's canonical unit it makes
+///
+/// The canonical unit of a type is its first unit, as in Dart Sass.
+const UNITS: [(&str, UnitType, f64); 18] = [
+    ("in", UnitType::Length, 1.0),
+    ("cm", UnitType::Length, 1.0 / 2.54),
+    ("pc", UnitType::Length, 1.0 / 6.0),
+    ("mm", UnitType::Length, 1.0 / 25.4),
+    ("q", UnitType::Length, 1.0 / 101.6),
+    ("pt", UnitType::Length, 1.0 / 72.0),
+    ("px", UnitType::Length, 1.0 / 96.0),
+    ("deg", UnitType::Angle, 1.0),
+    ("grad", UnitType::Angle, 9.0 / 10.0),
+    ("rad", UnitType::Angle, 180.0 / std::f64::consts::PI),
+    ("turn", UnitType::Angle, 360.0),
+    ("s", UnitType::Time, 1.0),
+    ("ms", UnitType::Time, 1.0 / 1000.0),
+    ("Hz", UnitType::Frequency, 1.0),
+    ("kHz", UnitType::Frequency, 1000.0),
+    ("dpi", UnitType::Resolution, 1.0),
+    ("dpcm", UnitType::Resolution, 2.54),
+    ("dppx", UnitType::Resolution, 96.0),
+];
+
+impl UnitType {
+    /// The type of `unit`, if it converts into other units
+    #[must_use]
+    pub fn of(unit: &str) -> Option<Self> {
+        lookup(unit).map(|(unit_type, _)| unit_type)
+    }
+
+    /// The name used in error messages, e.g. "Expected 1s to have a length unit"
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Length => "length",
+            Self::Angle => "angle",
+            Self::Time => "time",
+            Self::Frequency => "frequency",
+            Self::Resolution => "pixel density",
+        }
+    }
+
+    /// The units of this type, starting with the canonical one
+    pub fn units(self) -> impl Iterator<Item = &'static str> {
+        UNITS.iter().filter(move |(_, unit_type, _)| *unit_type == self).map(|(unit, ..)| *unit)
+    }
+
+    #[must_use]
+    pub fn canonical_unit(self) -> &'static str {
+        self.units().next().unwrap_or_default()
+    }
+}
+
+fn lookup(unit: &str) -> Option<(UnitType, f64)> {
+    UNITS.iter().find(|(name, ..)| *name == unit).map(|&(_, unit_type, factor)| (unit_type, factor))
+}
+
+/// The number of `unit1`s per `unit2`, or `None` if they don't convert into each other
+#[must_use]
+pub fn conversion_factor(unit1: &str, unit2: &str) -> Option<f64> {
+    if unit1 == unit2 {
+        return Some(1.0);
+    }
+    let (type1, factor1) = lookup(unit1)?;
+    let (type2, factor2) = lookup(unit2)?;
+    (type1 == type2).then(|| factor2 / factor1)
+}
+
+/// What a value in `unit` is multiplied by to be in the canonical unit of its type
+#[must_use]
+pub fn canonical_multiplier(unit: &str) -> f64 {
+    lookup(unit).map_or(1.0, |(_, factor)| factor)
+}
+
+/// The canonical unit of `unit`'s type, or `unit` itself if it doesn't convert
+#[must_use]
+pub fn canonicalize(unit: Atom) -> Atom {
+    UnitType::of(&unit).map_or(unit, |unit_type| Atom::new(unit_type.canonical_unit()))
+}
@@ -1,15 +1,34 @@
 use std::path::{Path, PathBuf};
 
+use scr_allocator::Allocator;
+use scr_ast::{node::Statement, SourceType, Syntax};
+use scr_parser::{FragmentReturn, Parser};
 use serde::Deserialize;
-use serde_json::Value;
+use serde_json::{json, Value};
 
 use crate::{
+    csstree_ast::CsstreeAst,
     get_project_root,
     suite::{Case, Suite, TestResult},
 };
 
 const FIXTURES_PATH: &str = "tasks/coverage/csstree/fixtures/ast";
 
+/// The csstree parse contexts the cases can be parsed in, `atrulePrelude` has no counterpart
+const CONTEXTS: [&str; 11] = [
+    "stylesheet",
+    "atrule",
+    "rule",
+    "block",
+    "declarationList",
+    "declaration",
+    "selectorList",
+    "selector",
+    "value",
+    "mediaQueryList",
+    "mediaQuery",
+];
+
 #[derive(Debug, Default, Clone, Deserialize)]
 pub struct CsstreeCase {
     pub source: Option<String>,
@@ -32,20 +51,133 @@ pub struct TestCsstreeCase {
     path: PathBuf,
     code: String,
     virtual_path: Option<String>,
+    /// What the source is parsed as, named like csstree's parse contexts, e.g. `selector`
+    context: String,
+    /// The fixture's `ast`, error cases have none
+    expected: Option<Value>,
     result: TestResult,
 }
 
-impl TestCsstreeCase {}
+impl TestCsstreeCase {
+    /// Sets what the case is compared against, and the context it sets in its `options`.
+    fn with_expected(mut self, expected: Option<Value>, context: Option<&str>) -> Self {
+        self.expected = expected.map(|mut expected| {
+            strip_locations(&mut expected);
+            expected
+        });
+        if let Some(context) = context {
+            self.context = context.to_string();
+        }
+        self
+    }
+
+    /// Parses the source in the case's context and converts it into csstree's shape.
+    fn parse(&self) -> Result<Value, String> {
+        let allocator = Allocator::default();
+        let parser = |code| Parser::new(&allocator, code, self.source_type());
+        let code = self.code();
+        match self.context.as_str() {
+            "selectorList" => self.fragment(code, parser(code).parse_selector(), |ast, list| {
+                ast.selector_list(&list)
+            }),
+            "selector" => self.fragment(code, parser(code).parse_selector(), |ast, list| {
+                list.selectors.first().map_or(Value::Null, |selector| ast.selector(selector))
+            }),
+            "value" => self.fragment(code, parser(code).parse_expression(), |ast, expression| {
+                ast.value(&expression)
+            }),
+            "mediaQueryList" => {
+                self.fragment(code, parser(code).parse_media_query_list(), |ast, list| {
+                    ast.media_query_list(&list)
+                })
+            }
+            "mediaQuery" => {
+                self.fragment(code, parser(code).parse_media_query_list(), |ast, list| {
+                    list.queries.first().map_or(Value::Null, |query| ast.media_query(query))
+                })
+            }
+            "block" | "declarationList" | "declaration" => {
+                // Parsed as the body of a rule, a block already has its braces.
+                let code = if self.context == "block" {
+                    format!("a{code}")
+                } else {
+                    format!("a{{{code}}}")
+                };
+                let ret = parser(&code).parse();
+                if !ret.errors.is_empty() {
+                    return Err(self.render_errors(&code, ret.errors));
+                }
+                let ast = CsstreeAst::new(&code);
+                let body = match ret.stylesheet.body.first() {
+                    Some(Statement::StyleRule(rule)) => &rule.body[..],
+                    _ => &[],
+                };
+                Ok(match self.context.as_str() {
+                    "block" => ast.block(body),
+                    "declarationList" => {
+                        let mut block = ast.block(body);
+                        block["type"] = json!("DeclarationList");
+                        block
+                    }
+                    _ => match body.first() {
+                        Some(Statement::Declaration(declaration)) => ast.declaration(declaration),
+                        _ => Value::Null,
+                    },
+                })
+            }
+            _ => {
+                let ret = parser(code).parse();
+                if !ret.errors.is_empty() {
+                    return Err(self.render_errors(code, ret.errors));
+                }
+                let ast = CsstreeAst::new(code);
+                Ok(if self.context == "stylesheet" {
+                    ast.stylesheet(&ret.stylesheet)
+                } else {
+                    ret.stylesheet
+                        .body
+                        .first()
+                        .and_then(|statement| ast.statement(statement))
+                        .unwrap_or(Value::Null)
+                })
+            }
+        }
+    }
+
+    fn fragment<T>(
+        &self,
+        code: &str,
+        ret: FragmentReturn<T>,
+        convert: impl FnOnce(&CsstreeAst, T) -> Value,
+    ) -> Result<Value, String> {
+        match ret.fragment {
+            Some(fragment) if ret.errors.is_empty() => {
+                Ok(convert(&CsstreeAst::new(code), fragment))
+            }
+            _ => Err(self.render_errors(code, ret.errors)),
+        }
+    }
+}
 
 impl Case for TestCsstreeCase {
     fn new(path: PathBuf, code: String, virtual_path: Option<String>) -> Self {
-        Self { path, code, virtual_path, result: TestResult::ToBeRun }
+        let context = context_from_path(&path).to_string();
+        Self { path, code, virtual_path, context, expected: None, result: TestResult::ToBeRun }
+    }
+
+    fn skip_test_case(&self) -> bool {
+        !CONTEXTS.contains(&self.context.as_str())
     }
 
     fn should_fail(&self) -> bool {
         if let Some(p) = self.virtual_path() { p.starts_with("error #") } else { false }
     }
 
+    /// The fixtures are all plain CSS, whatever the JSON file is named
+    fn source_type(&self) -> SourceType {
+        SourceType::new(Syntax::Css)
+    }
+
     fn code(&self) -> &str {
         &self.code
     }
@@ -63,7 +195,15 @@ impl Case for TestCsstreeCase {
     }
 
     fn run(&mut self) {
-        self.result = self.execute();
+        let result = self.parse();
+        self.result = match (result, &self.expected) {
+            (Ok(ast), Some(expected)) if !self.should_fail() && ast != *expected => {
+                let pretty = |value| serde_json::to_string_pretty(value).unwrap();
+                TestResult::Mismatch(pretty(&ast), pretty(expected))
+            }
+            (Ok(_), None) if !self.should_fail() => TestResult::NotCompared,
+            (result, _) => self.parser_rusult_to_test_result(result.map(|_| String::new())),
+        };
     }
 }
 
@@ -78,7 +218,7 @@ impl<T: Case> TestCsstreeSuite<T> {
     }
 }
 
-impl<T: Case> Suite<T> for TestCsstreeSuite<T> {
+impl Suite<TestCsstreeCase> for TestCsstreeSuite<TestCsstreeCase> {
     fn get_test_root(&self) -> &Path {
         &self.test_root
     }
@@ -88,18 +228,18 @@ impl<T: Case> Suite<T> for TestCsstreeSuite<T> {
         false
     }
 
-    fn save_test_cases(&mut self, cases: Vec<T>) {
+    fn save_test_cases(&mut self, cases: Vec<TestCsstreeCase>) {
         self.test_cases = cases
     }
 
-    fn get_test_cases(&self) -> &Vec<T> {
+    fn get_test_cases(&self) -> &Vec<TestCsstreeCase> {
         &self.test_cases
     }
 
-    fn file_to_cases(&self, path: PathBuf, source: String) -> Vec<T> {
+    fn file_to_cases(&self, path: PathBuf, source: String) -> Vec<TestCsstreeCase> {
         let json: Value = serde_json::from_str(source.as_str()).unwrap();
         let json = json.as_object().unwrap();
-        let mut cases: Vec<T> = vec![];
+        let mut cases = vec![];
         // println!("Json: {json:#?}");
 
         json.keys().into_iter().for_each(|key| {
@@ -109,20 +249,27 @@ impl<T: Case> Suite<T> for TestCsstreeSuite<T> {
                     errors.iter().for_each(|error| {
                         index += 1;
                         let error = error.as_object().unwrap();
-                        cases.push(T::new(
+                        cases.push(TestCsstreeCase::new(
                             path.clone(),
-                            error["source"].to_string(),
+                            error["source"].as_str().unwrap_or_default().to_string(),
                             Some(format!("error #{index}").to_string()),
                         ));
                     });
                 }
             } else {
                 if let Some(case) = json.get(key).unwrap().as_object() {
-                    cases.push(T::new(
-                        path.clone(),
-                        case["source"].to_string(),
-                        Some(key.to_string()),
-                    ));
+                    let context = case
+                        .get("options")
+                        .and_then(|options| options.get("context"))
+                        .and_then(Value::as_str);
+                    cases.push(
+                        TestCsstreeCase::new(
+                            path.clone(),
+                            case["source"].as_str().unwrap_or_default().to_string(),
+                            Some(key.to_string()),
+                        )
+                        .with_expected(case.get("ast").cloned(), context),
+                    );
                 };
             }
         });
@@ -130,3 +277,26 @@ impl<T: Case> Suite<T> for TestCsstreeSuite<T> {
         cases
     }
 }
+
+/// The context of the cases in the fixture at `path`, from the nearest directory named after one
+/// as in csstree's own test runner, or else from the file's name
+fn context_from_path(path: &Path) -> &str {
+    let directories = path.parent().into_iter().flat_map(Path::ancestors);
+    directories
+        .chain([path])
+        .filter_map(|ancestor| ancestor.file_stem()?.to_str())
+        .find(|name| CONTEXTS.contains(name) || *name == "atrulePrelude")
+        .unwrap_or("stylesheet")
+}
+
+/// Removes the `loc` of every node, which the converted AST doesn't have.
+fn strip_locations(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            object.remove("loc");
+            object.values_mut().for_each(strip_locations);
+        }
+        Value::Array(array) => array.iter_mut().for_each(strip_locations),
+        _ => {}
+    }
+}
@@ -0,0 +1,570 @@
+//! The scr AST in the JSON shape of csstree's, to compare with the `ast` of its fixtures
+//! <https://github.com/csstree/csstree/blob/master/docs/ast.md>
+//!
+//! Locations aren't produced, since the fixtures are compared without them. Anything csstree
+//! parses that isn't mapped yet becomes a `Raw` node holding its source text, which then shows
+//! up as a mismatch rather than a crash.
+
+use scr_ast::{
+    node::{
+        AttributeMatcher, AttributeSelector, Combinator, ComplexSelector, Declaration, Expression,
+        Import, Interpolation, ListSeparator, MediaCondition, MediaQuery, MediaQueryList,
+        PseudoSelector, SelectorList, SimpleSelector, Statement, Stylesheet, SupportsCondition,
+    },
+    Span,
+};
+use serde_json::{json, Value};
+
+/// Pseudo-classes and elements whose argument csstree parses as a single selector rather than a
+/// list
+const SINGLE_SELECTOR_PSEUDOS: [&str; 3] = ["host", "host-context", "slotted"];
+
+const NTH_PSEUDOS: [&str; 6] =
+    ["nth-child", "nth-last-child", "nth-of-type", "nth-last-of-type", "nth-col", "nth-last-col"];
+
+/// Converts nodes parsed from `source` into csstree's shape.
+pub struct CsstreeAst<'s> {
+    source: &'s str,
+}
+
+impl<'s> CsstreeAst<'s> {
+    pub const fn new(source: &'s str) -> Self {
+        Self { source }
+    }
+
+    fn text(&self, span: Span) -> &'s str {
+        span.source_text(self.source)
+    }
+
+    fn raw(&self, span: Span) -> Value {
+        json!({ "type": "Raw", "value": self.text(span) })
+    }
+
+    /// The text of `interpolation`, as written if it isn't plain
+    fn interpolation<'b>(&self, interpolation: &'b Interpolation) -> &'b str
+    where
+        's: 'b,
+    {
+        interpolation.as_plain().unwrap_or_else(|| self.text(interpolation.span))
+    }
+
+    pub fn stylesheet(&self, stylesheet: &Stylesheet) -> Value {
+        // csstree keeps only top-level comments starting with `/*!`.
+        let children = stylesheet
+            .body
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::LoudComment(comment) if self.text(comment.span).starts_with("/*!") => {
+                    Some(self.comment(comment.span))
+                }
+                _ => self.statement(statement),
+            })
+            .collect::<Vec<_>>();
+        json!({ "type": "StyleSheet", "children": children })
+    }
+
+    /// `None` for comments, which csstree drops
+    pub fn statement(&self, statement: &Statement) -> Option<Value> {
+        let value = match statement {
+            Statement::LoudComment(_) | Statement::SilentComment(_) => return None,
+            Statement::StyleRule(rule) => {
+                self.rule(&self.selector_list(&rule.selector), &rule.body)
+            }
+            Statement::Declaration(declaration) => self.declaration(declaration),
+            Statement::KeyframeBlock(block) => {
+                let selectors = block
+                    .selectors
+                    .iter()
+                    .map(|selector| {
+                        let node = keyframe_selector(self.interpolation(selector));
+                        json!({ "type": "Selector", "children": [node] })
+                    })
+                    .collect::<Vec<_>>();
+                let prelude = json!({ "type": "SelectorList", "children": selectors });
+                self.rule(&prelude, &block.body)
+            }
+            Statement::Media(rule) => {
+                let prelude = vec![self.media_query_list(&rule.query)];
+                self.at_rule("media", Some(prelude), Some(&rule.body))
+            }
+            Statement::Supports(rule) => {
+                let mut prelude = vec![];
+                self.supports_condition(&rule.condition, &mut prelude);
+                self.at_rule("supports", Some(prelude), Some(&rule.body))
+            }
+            Statement::Keyframes(rule) => {
+                let name = format!(
+                    "{}keyframes",
+                    rule.vendor_prefix.as_ref().map_or("", |prefix| prefix.as_str())
+                );
+                let keyframes_name = self.text(rule.name.span);
+                let node = if keyframes_name.starts_with(['"', '\'']) {
+                    json!({ "type": "String", "value": &keyframes_name[1..keyframes_name.len() - 1] })
+                } else {
+                    json!({ "type": "Identifier", "name": keyframes_name })
+                };
+                self.at_rule(&name, Some(vec![node]), Some(&rule.body))
+            }
+            Statement::Import(rule) => {
+                let mut prelude = vec![];
+                for import in &rule.imports {
+                    match import {
+                        Import::Static(import) => {
+                            prelude.push(url_or_string(self.text(import.url.span)));
+                            if let Some(modifiers) = &import.modifiers {
+                                prelude.push(self.raw(modifiers.span));
+                            }
+                        }
+                        Import::Dynamic(import) => prelude.push(self.raw(import.span)),
+                    }
+                }
+                self.at_rule("import", Some(prelude), None)
+            }
+            Statement::AtRule(rule) => {
+                self.generic_at_rule(rule.span, rule.body.as_ref().map(|body| &body[..]))
+            }
+            Statement::Container(rule) => self.generic_at_rule(rule.span, Some(&rule.body)),
+            Statement::CounterStyle(rule) => self.generic_at_rule(rule.span, Some(&rule.body)),
+            Statement::FontFeatureValues(rule) => self.generic_at_rule(rule.span, Some(&rule.body)),
+            Statement::Layer(rule) => {
+                self.generic_at_rule(rule.span, rule.body.as_ref().map(|body| &body[..]))
+            }
+            Statement::Page(rule) => self.generic_at_rule(rule.span, Some(&rule.body)),
+            Statement::PageMargin(rule) => self.generic_at_rule(rule.span, Some(&rule.body)),
+            Statement::Property(rule) => self.generic_at_rule(rule.span, Some(&rule.body)),
+            Statement::Scope(rule) => self.generic_at_rule(rule.span, Some(&rule.body)),
+            Statement::StartingStyle(rule) => self.generic_at_rule(rule.span, Some(&rule.body)),
+            _ => self.raw(statement.span()),
+        };
+        Some(value)
+    }
+
+    fn comment(&self, span: Span) -> Value {
+        let text = self.text(span);
+        let text = text.strip_prefix("/*").unwrap_or(text);
+        json!({ "type": "Comment", "value": text.strip_suffix("*/").unwrap_or(text) })
+    }
+
+    fn rule(&self, prelude: &Value, body: &[Statement]) -> Value {
+        json!({ "type": "Rule", "prelude": prelude, "block": self.block(body) })
+    }
+
+    pub fn block(&self, body: &[Statement]) -> Value {
+        let children =
+            body.iter().filter_map(|statement| self.statement(statement)).collect::<Vec<_>>();
+        json!({ "type": "Block", "children": children })
+    }
+
+    fn at_rule(
+        &self,
+        name: &str,
+        prelude: Option<Vec<Value>>,
+        body: Option<&[Statement]>,
+    ) -> Value {
+        let prelude =
+            prelude.map(|children| json!({ "type": "AtrulePrelude", "children": children }));
+        json!({
+            "type": "Atrule",
+            "name": name,
+            "prelude": prelude,
+            "block": body.map(|body| self.block(body)),
+        })
+    }
+
+    /// An at-rule whose prelude isn't mapped, which is kept as `Raw`
+    fn generic_at_rule(&self, span: Span, body: Option<&[Statement]>) -> Value {
+        let text = &self.text(span)[1..];
+        let name_end = text
+            .find(|c: char| c.is_whitespace() || matches!(c, '{' | ';' | '(' | '"' | '\''))
+            .unwrap_or(text.len());
+        let prelude = text[name_end..].split(['{', ';']).next().unwrap_or_default().trim();
+        let prelude = (!prelude.is_empty()).then(|| json!({ "type": "Raw", "value": prelude }));
+        json!({
+            "type": "Atrule",
+            "name": &text[..name_end],
+            "prelude": prelude,
+            "block": body.map(|body| self.block(body)),
+        })
+    }
+
+    pub fn declaration(&self, declaration: &Declaration) -> Value {
+        let property = self.interpolation(&declaration.name);
+        let (value, important) = match &declaration.value {
+            // csstree doesn't parse custom property values.
+            Some(value) if declaration.is_custom_property() => (self.raw(value.span()), false),
+            Some(value) => {
+                let (children, important) = self.declaration_value(value);
+                (json!({ "type": "Value", "children": children }), important)
+            }
+            None => (json!({ "type": "Value", "children": [] }), false),
+        };
+        json!({ "type": "Declaration", "important": important, "property": property, "value": value })
+    }
+
+    /// The nodes of a declaration's value, without `!important`, and whether it was there
+    fn declaration_value(&self, value: &Expression) -> (Vec<Value>, bool) {
+        match value {
+            _ if is_important(value) => (vec![], true),
+            Expression::List(list)
+                if list.separator == ListSeparator::Space
+                    && list.elements.last().is_some_and(is_important) =>
+            {
+                let elements = &list.elements[..list.elements.len() - 1];
+                (elements.iter().flat_map(|element| self.value_children(element)).collect(), true)
+            }
+            _ => (self.value_children(value), false),
+        }
+    }
+
+    pub fn value(&self, expression: &Expression) -> Value {
+        json!({ "type": "Value", "children": self.value_children(expression) })
+    }
+
+    /// `expression` as the children of a `Value`, with lists and operations flattened
+    fn value_children(&self, expression: &Expression) -> Vec<Value> {
+        match expression {
+            Expression::List(list) => {
+                let mut children = vec![];
+                for (index, element) in list.elements.iter().enumerate() {
+                    if index > 0 {
+                        match list.separator {
+                            ListSeparator::Comma => children.push(operator(",")),
+                            ListSeparator::Slash => children.push(operator("/")),
+                            ListSeparator::Space | ListSeparator::Undecided => {}
+                        }
+                    }
+                    children.extend(self.value_children(element));
+                }
+                if list.bracketed {
+                    return vec![json!({ "type": "Brackets", "children": children })];
+                }
+                children
+            }
+            Expression::Binary(binary) => {
+                let mut children = self.value_children(&binary.left);
+                children.push(operator(binary.operator.as_str()));
+                children.extend(self.value_children(&binary.right));
+                children
+            }
+            Expression::Parenthesized(parenthesized) => {
+                let children = self.value_children(&parenthesized.expression);
+                vec![json!({ "type": "Parentheses", "children": children })]
+            }
+            _ => vec![self.value_node(expression)],
+        }
+    }
+
+    fn value_node(&self, expression: &Expression) -> Value {
+        match expression {
+            Expression::Number(number) => {
+                let text = self.text(number.span);
+                match number.unit.as_deref() {
+                    None => json!({ "type": "Number", "value": text }),
+                    Some("%") => json!({ "type": "Percentage", "value": &text[..text.len() - 1] }),
+                    Some(unit) => {
+                        let (value, unit) = text.split_at(text.len() - unit.len());
+                        json!({ "type": "Dimension", "value": value, "unit": unit })
+                    }
+                }
+            }
+            Expression::Color(color) => json!({ "type": "Hash", "value": color.value }),
+            Expression::String(string) if string.quoted => {
+                json!({ "type": "String", "value": self.interpolation(&string.text) })
+            }
+            Expression::String(string) => {
+                let text = self.interpolation(&string.text);
+                if text.starts_with(['u', 'U']) && text[1..].starts_with('+') {
+                    json!({ "type": "UnicodeRange", "value": text })
+                } else {
+                    url_or_identifier(text)
+                }
+            }
+            Expression::Boolean(_) | Expression::Null(_) => {
+                json!({ "type": "Identifier", "name": self.text(expression.span()) })
+            }
+            Expression::Function(function) => {
+                if function.namespace.is_none() && function.name.eq_ignore_ascii_case("url") {
+                    if let [Expression::String(url)] = &function.arguments.positional[..] {
+                        return json!({ "type": "Url", "value": self.interpolation(&url.text) });
+                    }
+                }
+                let children = self.arguments(&function.arguments.positional);
+                json!({ "type": "Function", "name": function.name.as_str(), "children": children })
+            }
+            Expression::InterpolatedFunction(function) => {
+                let children = self.arguments(&function.arguments.positional);
+                let name = self.interpolation(&function.name);
+                json!({ "type": "Function", "name": name, "children": children })
+            }
+            _ => self.raw(expression.span()),
+        }
+    }
+
+    /// Function arguments separated by `,` operators
+    fn arguments(&self, arguments: &[Expression]) -> Vec<Value> {
+        let mut children = vec![];
+        for (index, argument) in arguments.iter().enumerate() {
+            if index > 0 {
+                children.push(operator(","));
+            }
+            children.extend(self.value_children(argument));
+        }
+        children
+    }
+
+    pub fn selector_list(&self, list: &SelectorList) -> Value {
+        let children =
+            list.selectors.iter().map(|selector| self.selector(selector)).collect::<Vec<_>>();
+        json!({ "type": "SelectorList", "children": children })
+    }
+
+    /// The simple selectors and combinators of `selector`, in order
+    pub fn selector(&self, selector: &ComplexSelector) -> Value {
+        let mut children = vec![];
+        children.extend(selector.leading_combinator.map(combinator));
+        for component in &selector.components {
+            for simple in &component.selector.components {
+                children.push(self.simple_selector(simple));
+            }
+            children.extend(component.combinator.map(combinator));
+        }
+        json!({ "type": "Selector", "children": children })
+    }
+
+    fn simple_selector(&self, selector: &SimpleSelector) -> Value {
+        match selector {
+            SimpleSelector::Class(class) => {
+                json!({ "type": "ClassSelector", "name": self.interpolation(&class.name) })
+            }
+            SimpleSelector::Id(id) => {
+                json!({ "type": "IdSelector", "name": self.interpolation(&id.name) })
+            }
+            SimpleSelector::Type(selector) => {
+                let name = self
+                    .namespaced(selector.namespace.as_ref(), self.interpolation(&selector.name));
+                json!({ "type": "TypeSelector", "name": name })
+            }
+            // csstree has no universal selector node, `*` is a type selector.
+            SimpleSelector::Universal(selector) => {
+                json!({ "type": "TypeSelector", "name": self.namespaced(selector.namespace.as_ref(), "*") })
+            }
+            SimpleSelector::Parent(parent) if parent.suffix.is_none() => {
+                json!({ "type": "NestingSelector" })
+            }
+            SimpleSelector::Attribute(attribute) => self.attribute_selector(attribute),
+            SimpleSelector::Pseudo(pseudo) => self.pseudo_selector(pseudo),
+            SimpleSelector::Parent(parent) => self.raw(parent.span),
+            SimpleSelector::Placeholder(placeholder) => self.raw(placeholder.span),
+        }
+    }
+
+    fn namespaced(&self, namespace: Option<&Interpolation>, name: &str) -> String {
+        namespace.map_or_else(
+            || name.to_string(),
+            |namespace| format!("{}|{name}", self.interpolation(namespace)),
+        )
+    }
+
+    fn attribute_selector(&self, attribute: &AttributeSelector) -> Value {
+        let name =
+            self.namespaced(attribute.namespace.as_ref(), self.interpolation(&attribute.name));
+        let value = attribute.value.as_ref().map(|value| {
+            let text = self.interpolation(&value.text);
+            if value.quoted {
+                json!({ "type": "String", "value": text })
+            } else {
+                json!({ "type": "Identifier", "name": text })
+            }
+        });
+        json!({
+            "type": "AttributeSelector",
+            "name": { "type": "Identifier", "name": name },
+            "matcher": attribute.matcher.map(AttributeMatcher::as_str),
+            "value": value,
+            "flags": attribute.modifier.as_ref().map(|modifier| modifier.as_str()),
+        })
+    }
+
+    fn pseudo_selector(&self, pseudo: &PseudoSelector) -> Value {
+        let kind = if pseudo.is_element { "PseudoElementSelector" } else { "PseudoClassSelector" };
+        let name = self.interpolation(&pseudo.name);
+        let lowercase = name.to_ascii_lowercase();
+        let children = match (&pseudo.argument, &pseudo.selector) {
+            (None, None) => Value::Null,
+            (Some(argument), selector) if NTH_PSEUDOS.contains(&lowercase.as_str()) => {
+                let selector = selector.as_ref().map(|selector| self.selector_list(selector));
+                let nth = an_plus_b(self.interpolation(argument).trim());
+                json!([{ "type": "Nth", "nth": nth, "selector": selector }])
+            }
+            (Some(argument), _) if matches!(lowercase.as_str(), "dir" | "lang") => {
+                json!([{ "type": "Identifier", "name": self.interpolation(argument).trim() }])
+            }
+            (Some(argument), _) => {
+                json!([{ "type": "Raw", "value": self.interpolation(argument) }])
+            }
+            (None, Some(selector))
+                if SINGLE_SELECTOR_PSEUDOS.contains(&lowercase.as_str())
+                    && selector.selectors.len() == 1 =>
+            {
+                json!([self.selector(&selector.selectors[0])])
+            }
+            (None, Some(selector)) => json!([self.selector_list(selector)]),
+        };
+        json!({ "type": kind, "name": name, "children": children })
+    }
+
+    pub fn media_query_list(&self, list: &MediaQueryList) -> Value {
+        let children = list.queries.iter().map(|query| self.media_query(query)).collect::<Vec<_>>();
+        json!({ "type": "MediaQueryList", "children": children })
+    }
+
+    /// The words and features of `query`, flat as in csstree 2
+    pub fn media_query(&self, query: &MediaQuery) -> Value {
+        let mut children = vec![];
+        children.extend(query.modifier.as_ref().map(|modifier| identifier(modifier.as_str())));
+        if let Some(media_type) = &query.media_type {
+            children.push(identifier(self.interpolation(media_type)));
+            if query.condition.is_some() {
+                children.push(identifier("and"));
+            }
+        }
+        if let Some(condition) = &query.condition {
+            self.media_condition(condition, &mut children);
+        }
+        json!({ "type": "MediaQuery", "children": children })
+    }
+
+    fn media_condition(&self, condition: &MediaCondition, children: &mut Vec<Value>) {
+        match condition {
+            MediaCondition::Feature(feature) => {
+                let value = feature.value.as_ref().map(|value| match value {
+                    Expression::Binary(binary) if binary.allows_slash => json!({
+                        "type": "Ratio",
+                        "left": self.text(binary.left.span()),
+                        "right": self.text(binary.right.span()),
+                    }),
+                    _ => self.value_node(value),
+                });
+                let name = self.interpolation(&feature.name);
+                children.push(json!({ "type": "MediaFeature", "name": name, "value": value }));
+            }
+            MediaCondition::Not(not) => {
+                children.push(identifier("not"));
+                self.media_condition(&not.condition, children);
+            }
+            MediaCondition::Operation(operation) => {
+                for (index, condition) in operation.conditions.iter().enumerate() {
+                    if index > 0 {
+                        children.push(identifier(operation.operator.as_str()));
+                    }
+                    self.media_condition(condition, children);
+                }
+            }
+            MediaCondition::Range(range) => children.push(self.raw(range.span)),
+            MediaCondition::Style(style) => children.push(self.raw(style.span)),
+            MediaCondition::Interpolation(interpolation) => {
+                children.push(self.raw(interpolation.span));
+            }
+        }
+    }
+
+    /// The nodes of `condition`, with each declaration in its own `Parentheses`
+    fn supports_condition(&self, condition: &SupportsCondition, children: &mut Vec<Value>) {
+        match condition {
+            SupportsCondition::Declaration(declaration) => {
+                let declaration = json!({
+                    "type": "Declaration",
+                    "important": false,
+                    "property": self.text(declaration.name.span()),
+                    "value": self.value(&declaration.value),
+                });
+                children.push(json!({ "type": "Parentheses", "children": [declaration] }));
+            }
+            SupportsCondition::Negation(negation) => {
+                children.push(identifier("not"));
+                self.supports_condition(&negation.condition, children);
+            }
+            SupportsCondition::Operation(operation) => {
+                self.supports_condition(&operation.left, children);
+                children.push(identifier(operation.operator.as_str()));
+                self.supports_condition(&operation.right, children);
+            }
+            SupportsCondition::Anything(anything) => children.push(self.raw(anything.span)),
+            SupportsCondition::Function(function) => children.push(self.raw(function.span)),
+            SupportsCondition::Interpolation(interpolation) => {
+                children.push(self.raw(interpolation.span));
+            }
+        }
+    }
+}
+
+fn identifier(name: &str) -> Value {
+    json!({ "type": "Identifier", "name": name })
+}
+
+fn operator(value: &str) -> Value {
+    json!({ "type": "Operator", "value": value })
+}
+
+fn combinator(combinator: Combinator) -> Value {
+    json!({ "type": "Combinator", "name": combinator.as_str() })
+}
+
+/// `from`, `to` or a percentage in `@keyframes`
+fn keyframe_selector(text: &str) -> Value {
+    text.strip_suffix('%').map_or_else(
+        || json!({ "type": "TypeSelector", "name": text }),
+        |value| json!({ "type": "Percentage", "value": value }),
+    )
+}
+
+/// An unquoted `url(...)`, or else an identifier
+fn url_or_identifier(text: &str) -> Value {
+    url_contents(text).map_or_else(
+        || json!({ "type": "Identifier", "name": text }),
+        |url| json!({ "type": "Url", "value": url }),
+    )
+}
+
+/// A `url(...)` or a quoted string such as the URL of `@import`
+fn url_or_string(text: &str) -> Value {
+    if text.starts_with(['"', '\'']) && text.len() >= 2 {
+        return json!({ "type": "String", "value": &text[1..text.len() - 1] });
+    }
+    url_or_identifier(text)
+}
+
+fn is_important(expression: &Expression) -> bool {
+    matches!(expression, Expression::String(string)
+        if !string.quoted && string.text.as_plain() == Some("!important"))
+}
+
+/// The URL inside an unquoted `url(...)`
+fn url_contents(text: &str) -> Option<&str> {
+    let prefix = text.get(..4)?;
+    if !prefix.eq_ignore_ascii_case("url(") {
+        return None;
+    }
+    Some(text[4..].strip_suffix(')')?.trim())
+}
+
+/// csstree's `AnPlusB` of `text`, or an `Identifier` for `odd` and `even`
+fn an_plus_b(text: &str) -> Value {
+    if text.eq_ignore_ascii_case("odd") || text.eq_ignore_ascii_case("even") {
+        return identifier(text);
+    }
+    let text = text.replace(' ', "");
+    let (a, b) = text.find(['n', 'N']).map_or_else(
+        || (None, Some(text.trim_start_matches('+'))),
+        |index| {
+            let a = match &text[..index] {
+                "" | "+" => "1",
+                "-" => "-1",
+                a => a.trim_start_matches('+'),
+            };
+            let b = text[index + 1..].trim_start_matches('+');
+            (Some(a), (!b.is_empty()).then_some(b))
+        },
+    );
+    json!({ "type": "AnPlusB", "a": a, "b": b })
+}
@@ -0,0 +1,127 @@
+//! An in-memory filesystem holding the files of an HRX archive
+//! <https://github.com/google/hrx>
+
+use std::{
+    collections::BTreeMap,
+    path::{Component, Path, PathBuf},
+};
+
+/// Files by their path inside the archive or directory they were read from
+#[derive(Debug, Default)]
+pub struct VirtualFs {
+    files: BTreeMap<PathBuf, String>,
+}
+
+impl VirtualFs {
+    /// Reads the files of an HRX archive, skipping its comments and directories.
+    pub fn from_hrx(source: &str) -> Self {
+        let mut files = BTreeMap::new();
+        // The boundary is `<`, one or more `=`, then `>`, as the archive starts with.
+        let Some(boundary_end) = source.find('>') else { return Self { files } };
+        let boundary = &source[..=boundary_end];
+        if !boundary.starts_with('<') || !boundary[1..boundary_end].bytes().all(|b| b == b'=') {
+            return Self { files };
+        }
+        // A newline right before a boundary isn't part of the file before it.
+        let separator = format!("\n{boundary}");
+        for entry in source[boundary.len()..].split(&separator) {
+            let Some(entry) = entry.strip_prefix(' ') else { continue };
+            let (path, contents) = entry.split_once('\n').unwrap_or((entry, ""));
+            if !path.ends_with('/') {
+                files.insert(PathBuf::from(path), contents.to_string());
+            }
+        }
+        Self { files }
+    }
+
+    pub fn insert(&mut self, path: PathBuf, contents: String) {
+        self.files.insert(path, contents);
+    }
+
+    pub fn read(&self, path: &Path) -> Option<&str> {
+        self.files.get(path).map(String::as_str)
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.keys().map(PathBuf::as_path)
+    }
+
+    /// The file a `@use`, `@forward` or `@import` of `url` loads from the file at `from`, tried
+    /// as Sass does: with each extension, as a partial, then as a directory's index file
+    pub fn resolve(&self, from: &Path, url: &str) -> Option<PathBuf> {
+        let path = normalize(&from.parent().unwrap_or_else(|| Path::new("")).join(url));
+        let with_extension =
+            path.extension().is_some_and(|ext| ext == "scss" || ext == "sass" || ext == "css");
+        let candidates = |path: &Path| -> Vec<PathBuf> {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            let partial = path.with_file_name(format!("_{name}"));
+            if with_extension {
+                return vec![path.to_path_buf(), partial];
+            }
+            ["scss", "sass", "css"]
+                .iter()
+                .flat_map(|ext| [path.with_extension(ext), partial.with_extension(ext)])
+                .collect()
+        };
+        candidates(&path)
+            .into_iter()
+            .chain((!with_extension).then(|| candidates(&path.join("index"))).into_iter().flatten())
+            .find(|candidate| self.files.contains_key(candidate))
+    }
+}
+
+/// `path` without `.` and with each `..` removing the component before it
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::{Path, PathBuf};
+
+    use super::VirtualFs;
+
+    const ARCHIVE: &str = "<===> input.scss
+@use 'a';
+a {b: c}
+
+<===>
+================================================================================
+<===> _a.scss
+$x: 1;
+<===> dir/
+<===> b/_index.sass
+c
+  d: e
+";
+
+    #[test]
+    fn reads_files_with_their_newlines() {
+        let fs = VirtualFs::from_hrx(ARCHIVE);
+        assert_eq!(fs.paths().count(), 3);
+        assert_eq!(fs.read(Path::new("input.scss")), Some("@use 'a';\na {b: c}\n"));
+        assert_eq!(fs.read(Path::new("_a.scss")), Some("$x: 1;"));
+        assert_eq!(fs.read(Path::new("b/_index.sass")), Some("c\n  d: e\n"));
+    }
+
+    #[test]
+    fn resolves_partials_and_indexes() {
+        let fs = VirtualFs::from_hrx(ARCHIVE);
+        let from = Path::new("input.scss");
+        assert_eq!(fs.resolve(from, "a"), Some(PathBuf::from("_a.scss")));
+        assert_eq!(fs.resolve(from, "./a.scss"), Some(PathBuf::from("_a.scss")));
+        assert_eq!(fs.resolve(from, "b"), Some(PathBuf::from("b/_index.sass")));
+        assert_eq!(fs.resolve(Path::new("b/_index.sass"), "../a"), Some(PathBuf::from("_a.scss")));
+        assert_eq!(fs.resolve(from, "c"), None);
+    }
+}
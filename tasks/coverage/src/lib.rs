@@ -1,5 +1,8 @@
 mod csstree;
+mod csstree_ast;
+mod hrx;
 mod postcss;
+mod postcss_ast;
 mod sass_spec;
 mod suite;
 
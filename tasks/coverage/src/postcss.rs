@@ -1,10 +1,16 @@
-use std::path::{Path, PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
+use scr_allocator::Allocator;
+use scr_parser::Parser;
 use serde::Deserialize;
 use serde_json::Value;
 
 use crate::{
     get_project_root,
+    postcss_ast::PostcssAst,
     suite::{Case, Suite, TestResult},
 };
 
@@ -15,12 +21,43 @@ pub struct TestPostcssCase {
     code: String,
     virtual_path: Option<String>,
     should_fail: bool,
+    /// The tree in the case's `.json` sidecar, or why the sidecar isn't valid JSON
+    expected: Option<Result<Value, String>>,
     result: TestResult,
 }
 
+impl TestPostcssCase {
+    fn with_expected(mut self, expected: Option<Result<Value, String>>) -> Self {
+        self.expected = expected.map(|expected| {
+            expected.map(|mut expected| {
+                strip_non_raws(&mut expected);
+                expected
+            })
+        });
+        self
+    }
+
+    /// Parses the source and converts it into postcss's shape.
+    fn parse(&self) -> Result<Value, String> {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, self.code(), self.source_type()).parse();
+        if !ret.errors.is_empty() {
+            return Err(self.render_errors(self.code(), ret.errors));
+        }
+        Ok(PostcssAst::new(self.code()).root(&ret.stylesheet))
+    }
+}
+
 impl Case for TestPostcssCase {
     fn new(path: PathBuf, code: String, virtual_path: Option<String>) -> Self {
-        Self { path, code, should_fail: false, virtual_path, result: TestResult::ToBeRun }
+        Self {
+            path,
+            code,
+            should_fail: false,
+            virtual_path,
+            expected: None,
+            result: TestResult::ToBeRun,
+        }
     }
 
     fn code(&self) -> &str {
@@ -44,7 +81,16 @@ impl Case for TestPostcssCase {
     }
 
     fn run(&mut self) {
-        self.result = self.execute();
+        let result = self.parse();
+        let pretty = |value| serde_json::to_string_pretty(value).unwrap();
+        self.result = match (result, &self.expected) {
+            (Ok(ast), Some(Err(error))) => TestResult::Mismatch(pretty(&ast), error.clone()),
+            (Ok(ast), Some(Ok(expected))) if ast != *expected => {
+                TestResult::Mismatch(pretty(&ast), pretty(expected))
+            }
+            (Ok(_), None) => TestResult::NotCompared,
+            (result, _) => self.parser_rusult_to_test_result(result.map(|_| String::new())),
+        };
     }
 }
 
@@ -59,8 +105,8 @@ impl<T: Case> TestPostcssSuite<T> {
     }
 }
 
-impl<T: Case> Suite<T> for TestPostcssSuite<T> {
-    fn get_test_cases(&self) -> &Vec<T> {
+impl Suite<TestPostcssCase> for TestPostcssSuite<TestPostcssCase> {
+    fn get_test_cases(&self) -> &Vec<TestPostcssCase> {
         &self.test_cases
     }
 
@@ -68,11 +114,35 @@ impl<T: Case> Suite<T> for TestPostcssSuite<T> {
         &self.test_root
     }
 
+    /// The `.json` sidecars are read along with their `.css` case.
     fn skip_test_path(&self, path: &Path) -> bool {
         path.extension().map_or(true, |ext| ext == "json")
     }
 
-    fn save_test_cases(&mut self, cases: Vec<T>) {
+    fn save_test_cases(&mut self, cases: Vec<TestPostcssCase>) {
         self.test_cases = cases
     }
+
+    fn file_to_cases(&self, path: PathBuf, code: String) -> Vec<TestPostcssCase> {
+        let sidecar = self.test_root.join(&path).with_extension("json");
+        let expected = fs::read_to_string(&sidecar).ok().map(|json| {
+            serde_json::from_str(&json)
+                .map_err(|error| format!("Invalid sidecar {}: {error}", sidecar.display()))
+        });
+        vec![TestPostcssCase::new(path, code, None).with_expected(expected)]
+    }
+}
+
+/// The properties of postcss nodes that the converted tree has
+const NODE_PROPERTIES: [&str; 10] =
+    ["type", "nodes", "raws", "selector", "name", "params", "prop", "value", "important", "text"];
+
+/// Removes what postcss adds to its nodes besides their contents and `raws`, such as `source`.
+fn strip_non_raws(value: &mut Value) {
+    if let Value::Object(node) = value {
+        node.retain(|key, _| NODE_PROPERTIES.contains(&key.as_str()));
+        if let Some(Value::Array(nodes)) = node.get_mut("nodes") {
+            nodes.iter_mut().for_each(strip_non_raws);
+        }
+    }
 }
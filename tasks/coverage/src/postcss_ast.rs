@@ -0,0 +1,254 @@
+//! The scr AST in the JSON shape of postcss's, to compare with the sidecars of
+//! postcss-parser-tests
+//! <https://postcss.org/api/>
+//!
+//! postcss keeps the whitespace around each node in its `raws`, so those are sliced out of the
+//! source between the spans of the nodes. Sass-only statements have no postcss counterpart and
+//! become `unknown` nodes holding their source text.
+
+use scr_ast::{
+    node::{Declaration, Expression, ListSeparator, Statement, Stylesheet},
+    Span,
+};
+use serde_json::{json, Map, Value};
+
+/// Converts nodes parsed from `source` into postcss's shape.
+pub struct PostcssAst<'s> {
+    source: &'s str,
+}
+
+/// The nodes of a container and the raws that depend on them
+struct Children<'s> {
+    nodes: Vec<Value>,
+    /// Whether the last node is followed by a `;`
+    semicolon: bool,
+    /// The whitespace after the last node
+    after: &'s str,
+}
+
+impl<'s> PostcssAst<'s> {
+    pub const fn new(source: &'s str) -> Self {
+        Self { source }
+    }
+
+    fn text(&self, span: Span) -> &'s str {
+        span.source_text(self.source)
+    }
+
+    pub fn root(&self, stylesheet: &Stylesheet) -> Value {
+        let Children { nodes, semicolon, after } =
+            self.nodes(&stylesheet.body, 0, self.source.len());
+        json!({
+            "type": "root",
+            "nodes": nodes,
+            "raws": { "semicolon": semicolon, "after": after },
+        })
+    }
+
+    /// The nodes of `body`, which lies between `start` and `end`
+    fn nodes(&self, body: &[Statement], start: usize, end: usize) -> Children<'s> {
+        let mut nodes = vec![];
+        let mut position = start;
+        let mut semicolon = false;
+        for statement in body {
+            if matches!(statement, Statement::SilentComment(_)) {
+                continue;
+            }
+            let span = statement.span();
+            let before = &self.source[position.min(span.start as usize)..span.start as usize];
+            nodes.push(self.node(statement, before));
+            position = span.end as usize;
+            semicolon = self.source[..position].ends_with(';');
+            let rest = &self.source[position..end];
+            let trimmed = rest.trim_start();
+            if trimmed.starts_with(';') {
+                position = end - trimmed.len() + 1;
+                semicolon = true;
+            }
+        }
+        Children { nodes, semicolon, after: &self.source[position.min(end)..end] }
+    }
+
+    fn node(&self, statement: &Statement, before: &str) -> Value {
+        match statement {
+            Statement::Declaration(declaration) => self.decl(declaration, before),
+            Statement::LoudComment(comment) => self.comment(comment.span, before),
+            Statement::StyleRule(rule) => self.rule(rule.span, &rule.body, before),
+            Statement::KeyframeBlock(block) => self.rule(block.span, &block.body, before),
+            _ if self.text(statement.span()).starts_with('@') => {
+                self.at_rule(statement.span(), at_rule_body(statement), before)
+            }
+            _ => json!({ "type": "unknown", "text": self.text(statement.span()) }),
+        }
+    }
+
+    fn decl(&self, declaration: &Declaration, before: &str) -> Value {
+        let name_end = declaration.name.span.end as usize;
+        let mut node = Map::new();
+        node.insert("type".into(), json!("decl"));
+        node.insert("prop".into(), json!(self.text(declaration.name.span)));
+        let mut raws = Map::new();
+        raws.insert("before".into(), json!(before));
+        let Some(value) = &declaration.value else {
+            let end = declaration.span.end as usize;
+            raws.insert("between".into(), json!(&self.source[name_end..end]));
+            node.insert("value".into(), json!(""));
+            node.insert("raws".into(), Value::Object(raws));
+            return Value::Object(node);
+        };
+        let value_start = value.span().start as usize;
+        raws.insert("between".into(), json!(&self.source[name_end..value_start]));
+        let value_end = important_start(value).map_or_else(
+            || value.span().end as usize,
+            |important_start| {
+                let value_end = self.source[..important_start as usize].trim_end().len();
+                let important = &self.source[value_end..value.span().end as usize];
+                node.insert("important".into(), json!(true));
+                if important != " !important" {
+                    raws.insert("important".into(), json!(important));
+                }
+                value_end
+            },
+        );
+        node.insert("value".into(), json!(&self.source[value_start..value_end]));
+        node.insert("raws".into(), Value::Object(raws));
+        Value::Object(node)
+    }
+
+    fn comment(&self, span: Span, before: &str) -> Value {
+        let text = self.text(span);
+        let text = text.strip_prefix("/*").unwrap_or(text);
+        let text = text.strip_suffix("*/").unwrap_or(text);
+        let trimmed = text.trim();
+        // Only whitespace is all `left`, as in postcss.
+        let (left, right) = if trimmed.is_empty() {
+            (text, "")
+        } else {
+            (&text[..text.len() - text.trim_start().len()], &text[text.trim_end().len()..])
+        };
+        json!({
+            "type": "comment",
+            "text": trimmed,
+            "raws": { "before": before, "left": left, "right": right },
+        })
+    }
+
+    fn rule(&self, span: Span, body: &[Statement], before: &str) -> Value {
+        let start = span.start as usize;
+        let text = self.text(span);
+        let open = find_block(text).unwrap_or(text.len());
+        let header = &text[..open];
+        let selector = header.trim_end();
+        let Children { nodes, semicolon, after } =
+            self.nodes(body, start + open + 1, block_end(span, text));
+        json!({
+            "type": "rule",
+            "selector": selector,
+            "nodes": nodes,
+            "raws": {
+                "before": before,
+                "between": &header[selector.len()..],
+                "semicolon": semicolon,
+                "after": after,
+            },
+        })
+    }
+
+    fn at_rule(&self, span: Span, body: Option<&[Statement]>, before: &str) -> Value {
+        let start = span.start as usize;
+        let text = self.text(span);
+        let name_end = text[1..]
+            .find(|c: char| c.is_whitespace() || matches!(c, '{' | ';' | '(' | '"' | '\'' | '/'))
+            .map_or(text.len(), |index| index + 1);
+        let open = body.and_then(|_| find_block(text));
+        let header = &text[name_end..open.unwrap_or(text.len())];
+        let header = header.strip_suffix(';').unwrap_or(header);
+        let params = header.trim();
+        let after_name = &header[..header.len() - header.trim_start().len()];
+        let between = &header[after_name.len() + params.len()..];
+
+        let mut raws = Map::new();
+        raws.insert("before".into(), json!(before));
+        raws.insert("between".into(), json!(between));
+        raws.insert("afterName".into(), json!(after_name));
+        let mut node = Map::new();
+        node.insert("type".into(), json!("atrule"));
+        node.insert("name".into(), json!(&text[1..name_end]));
+        node.insert("params".into(), json!(params));
+        if let (Some(body), Some(open)) = (body, open) {
+            let Children { nodes, semicolon, after } =
+                self.nodes(body, start + open + 1, block_end(span, text));
+            raws.insert("semicolon".into(), json!(semicolon));
+            raws.insert("after".into(), json!(after));
+            node.insert("nodes".into(), json!(nodes));
+        }
+        node.insert("raws".into(), Value::Object(raws));
+        Value::Object(node)
+    }
+}
+
+/// The children of an at-rule with a block
+fn at_rule_body<'b, 'a>(statement: &'b Statement<'a>) -> Option<&'b [Statement<'a>]> {
+    let body = match statement {
+        Statement::AtRule(rule) => return rule.body.as_ref().map(|body| &body[..]),
+        Statement::Layer(rule) => return rule.body.as_ref().map(|body| &body[..]),
+        Statement::Container(rule) => &rule.body,
+        Statement::CounterStyle(rule) => &rule.body,
+        Statement::FontFeatureValues(rule) => &rule.body,
+        Statement::Keyframes(rule) => &rule.body,
+        Statement::Media(rule) => &rule.body,
+        Statement::Page(rule) => &rule.body,
+        Statement::PageMargin(rule) => &rule.body,
+        Statement::Property(rule) => &rule.body,
+        Statement::Scope(rule) => &rule.body,
+        Statement::StartingStyle(rule) => &rule.body,
+        Statement::Supports(rule) => &rule.body,
+        _ => return None,
+    };
+    Some(body)
+}
+
+/// Where the trailing `!important` of a declaration's value starts
+fn important_start(value: &Expression) -> Option<u32> {
+    let important = match value {
+        Expression::List(list) if list.separator == ListSeparator::Space => list.elements.last()?,
+        _ => value,
+    };
+    match important {
+        Expression::String(string)
+            if !string.quoted && string.text.as_plain() == Some("!important") =>
+        {
+            Some(string.span.start)
+        }
+        _ => None,
+    }
+}
+
+/// The offset of the `{` opening the block in `text`, skipping strings and comments
+fn find_block(text: &str) -> Option<usize> {
+    let mut chars = text.char_indices().peekable();
+    let mut quote = None;
+    while let Some((index, c)) = chars.next() {
+        match (quote, c) {
+            (Some(_), '\\') => {
+                chars.next();
+            }
+            (Some(open), c) if c == open => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '/') if chars.peek().is_some_and(|&(_, next)| next == '*') => {
+                let end = text[index + 2..].find("*/")?;
+                while chars.peek().is_some_and(|&(next, _)| next < index + 2 + end + 2) {
+                    chars.next();
+                }
+            }
+            (None, '{') => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Where the block of a rule spanning `span` ends, before its `}`
+fn block_end(span: Span, text: &str) -> usize {
+    span.end as usize - usize::from(text.ends_with('}'))
+}
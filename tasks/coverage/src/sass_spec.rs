@@ -1,29 +1,195 @@
-use std::path::{Path, PathBuf};
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use scr_allocator::Allocator;
+use scr_ast::{
+    node::{DynamicImport, ForwardRule, UseRule},
+    SourceType, Visit,
+};
+use scr_parser::Parser;
+use walkdir::WalkDir;
 
 use crate::{
     get_project_root,
+    hrx::VirtualFs,
     suite::{Case, Suite, TestResult},
 };
 
 const PATH: &str = "tasks/coverage/sass-spec/spec";
-const HRX_SECTION_SEPARATOR: &str = r"
-<===>
-================================================================================
-";
-const FILE_PREFIX: &str = "<===>";
+
+/// The implementation whose expectations are followed, as named in `options.yml` and in files
+/// such as `output-dart-sass.css`
+const IMPLEMENTATION: &str = "dart-sass";
+
+/// The markers of an `options.yml` that concern [`IMPLEMENTATION`]
+#[derive(Debug, Default, Clone, Copy)]
+struct SpecOptions {
+    /// `:todo`, the spec isn't expected to pass yet
+    todo: bool,
+    /// `:warning_todo`, the warnings aren't expected to match yet
+    warning_todo: bool,
+    /// `:ignore_for`, the spec doesn't apply
+    ignore: bool,
+}
+
+impl SpecOptions {
+    /// Reads the lists of an `options.yml`, e.g. `:todo:` followed by `- dart-sass` lines.
+    fn parse(yaml: &str) -> Self {
+        let mut options = Self::default();
+        let mut key = "";
+        for line in yaml.lines() {
+            if let Some(item) = line.trim().strip_prefix("- ") {
+                // Entries may be issue links such as `sass/dart-sass#1234`.
+                if item.trim().contains(IMPLEMENTATION) {
+                    match key {
+                        ":todo:" => options.todo = true,
+                        ":warning_todo:" => options.warning_todo = true,
+                        ":ignore_for:" => options.ignore = true,
+                        _ => {}
+                    }
+                }
+            } else {
+                key = line.trim();
+            }
+        }
+        options
+    }
+
+    const fn merge(self, other: Self) -> Self {
+        Self {
+            todo: self.todo || other.todo,
+            warning_todo: self.warning_todo || other.warning_todo,
+            ignore: self.ignore || other.ignore,
+        }
+    }
+}
+
+/// What a spec is expected to produce, from the files next to its input
+#[derive(Debug, Default)]
+struct Expected {
+    /// `output.css`, compared once there's an evaluator to produce CSS
+    css: Option<String>,
+    error: Option<String>,
+    warning: Option<String>,
+}
+
+/// What compiling a spec's input produced
+struct Output {
+    /// `None` until there's an evaluator, the input and the modules it loads are only parsed and
+    /// the spec is reported as [`TestResult::NotCompared`].
+    css: Option<String>,
+    warning: String,
+}
+
+/// Why compiling a spec's input failed
+struct Failure {
+    /// The first line of the error, as in an `error` file, e.g. `Error: expected ";".`
+    message: String,
+    /// The errors rendered with the code they point at, for the snapshot
+    rendered: String,
+}
 
 pub struct TestSassSpecCase {
     path: PathBuf,
     virtual_path: Option<String>,
     code: String,
+    /// The files of the archive or directory the spec is in
+    fs: Rc<VirtualFs>,
+    expected: Expected,
+    options: SpecOptions,
     result: TestResult,
 }
 
-impl TestSassSpecCase {}
+impl TestSassSpecCase {
+    fn with_spec(mut self, fs: Rc<VirtualFs>, options: SpecOptions) -> Self {
+        let input = PathBuf::from(self.virtual_path.as_deref().unwrap_or_default());
+        let dir = input.parent().unwrap_or_else(|| Path::new(""));
+        // An implementation's own expectation overrides the shared one.
+        let read = |name: &str, extension: &str| {
+            [format!("{name}-{IMPLEMENTATION}{extension}"), format!("{name}{extension}")]
+                .iter()
+                .find_map(|name| fs.read(&dir.join(name)))
+                .map(str::to_string)
+        };
+        self.expected = Expected {
+            css: read("output", ".css"),
+            error: read("error", ""),
+            warning: read("warning", ""),
+        };
+        self.options = options;
+        self.fs = fs;
+        self
+    }
+
+    /// Parses the input and every module it loads from the spec's files.
+    fn compile(&self) -> Result<Output, Failure> {
+        let input = PathBuf::from(self.virtual_path.as_deref().unwrap_or_default());
+        let mut loaded = HashSet::from([input.clone()]);
+        let mut queue = vec![(input, self.code.clone())];
+        while let Some((path, code)) = queue.pop() {
+            let allocator = Allocator::default();
+            let source_type = SourceType::default().from_path(&path);
+            let ret = Parser::new(&allocator, &code, source_type).parse();
+            if let Some(error) = ret.errors.first() {
+                let message = format!("Error: {error}");
+                return Err(Failure { message, rendered: self.render_errors(&code, ret.errors) });
+            }
+            let mut loads = Loads::default();
+            loads.visit_stylesheet(&ret.stylesheet);
+            for url in loads.urls {
+                if url.starts_with("sass:") {
+                    continue;
+                }
+                let Some(resolved) = self.fs.resolve(&path, &url) else {
+                    let message = "Error: Can't find stylesheet to import.".to_string();
+                    let rendered = format!("{message}\n  {url} in {}\n", path.display());
+                    return Err(Failure { message, rendered });
+                };
+                if loaded.insert(resolved.clone()) {
+                    let code = self.fs.read(&resolved).unwrap_or_default().to_string();
+                    queue.push((resolved, code));
+                }
+            }
+        }
+        Ok(Output { css: None, warning: String::new() })
+    }
+}
+
+/// The URLs of the modules a stylesheet loads
+#[derive(Default)]
+struct Loads {
+    urls: Vec<String>,
+}
+
+impl<'a> Visit<'a> for Loads {
+    fn visit_use_rule(&mut self, it: &UseRule<'a>) {
+        self.urls.push(it.url.to_string());
+    }
+
+    fn visit_forward_rule(&mut self, it: &ForwardRule<'a>) {
+        self.urls.push(it.url.to_string());
+    }
+
+    fn visit_dynamic_import(&mut self, it: &DynamicImport) {
+        self.urls.push(it.url.to_string());
+    }
+}
 
 impl Case for TestSassSpecCase {
     fn new(path: PathBuf, code: String, virtual_path: Option<String>) -> Self {
-        Self { path, virtual_path, code, result: TestResult::ToBeRun }
+        Self {
+            path,
+            virtual_path,
+            code,
+            fs: Rc::default(),
+            expected: Expected::default(),
+            options: SpecOptions::default(),
+            result: TestResult::ToBeRun,
+        }
     }
 
     fn code(&self) -> &str {
@@ -42,20 +208,42 @@ impl Case for TestSassSpecCase {
         &self.result
     }
 
-    fn should_fail(&self) -> bool {
-        let path = self.path().to_string_lossy();
-
-        let virtual_path_contain_error = if let Some(virtual_path) = self.virtual_path() {
-            virtual_path.contains("error")
-        } else {
-            false
-        };
+    fn skip_test_case(&self) -> bool {
+        self.options.todo || self.options.ignore
+    }
 
-        path.contains("error") || virtual_path_contain_error
+    fn should_fail(&self) -> bool {
+        self.expected.error.is_some()
     }
 
     fn run(&mut self) {
-        self.result = self.execute();
+        self.result = match (self.compile(), &self.expected.error) {
+            (Err(failure), Some(error)) => {
+                let expected = error.lines().next().unwrap_or_default();
+                if failure.message == expected {
+                    TestResult::CorrectError(failure.rendered)
+                } else {
+                    TestResult::Mismatch(failure.message, expected.to_string())
+                }
+            }
+            (Err(failure), None) => TestResult::PassError(failure.rendered),
+            // Until there's an evaluator there's no CSS, and neither the warnings nor the errors
+            // raised while evaluating can be compared.
+            (Ok(Output { css: None, .. }), _) => TestResult::NotCompared,
+            (Ok(_), Some(_)) => TestResult::IncorrectPassed,
+            (Ok(Output { css: Some(css), warning }), None) => {
+                let expected_warning = self.expected.warning.as_deref().unwrap_or_default();
+                match &self.expected.css {
+                    Some(expected) if css != *expected => {
+                        TestResult::Mismatch(css, expected.clone())
+                    }
+                    _ if !self.options.warning_todo && warning != expected_warning => {
+                        TestResult::Mismatch(warning, expected_warning.to_string())
+                    }
+                    _ => TestResult::Passed,
+                }
+            }
+        };
     }
 }
 
@@ -64,40 +252,38 @@ pub struct TestSassSpecSuite<T: Case> {
     test_cases: Vec<T>,
 }
 
-impl<T: Case> TestSassSpecSuite<T> {
-    fn create_case_from_hrx(&self, cases: &mut Vec<T>, path: PathBuf, source: String) {
-        source.split(HRX_SECTION_SEPARATOR).filter(|code| !code.is_empty()).for_each(|c| {
-            let mut code = String::new();
-            let mut is_case_file = false;
-            let mut virtual_path: Option<String> = None;
-
-            c.lines().for_each(|line| {
-                if line.starts_with(FILE_PREFIX) {
-                    if is_case_file {
-                        cases.push(T::new(path.clone(), code.clone(), virtual_path.clone()));
-                    }
-
-                    is_case_file = false;
-                    virtual_path = None;
-                    code = String::new();
-
-                    let file_path = Path::new(line.strip_prefix(FILE_PREFIX).unwrap().trim());
-                    if file_path
-                        .file_name()
-                        .map_or(false, |name| name == "input.scss" || name == "input.sass")
-                    {
-                        is_case_file = true;
-                        virtual_path = Some(file_path.to_string_lossy().to_string());
-                    }
-                } else if is_case_file {
-                    code += line;
-                }
-            });
+impl TestSassSpecSuite<TestSassSpecCase> {
+    /// A case for every `input.scss` and `input.sass` in `fs`, the archive at `path`
+    fn cases_from_fs(&self, path: &Path, fs: VirtualFs) -> Vec<TestSassSpecCase> {
+        let disk_options = self.disk_options(path);
+        let fs = Rc::new(fs);
+        fs.paths()
+            .filter(|path| {
+                path.file_name().is_some_and(|name| name == "input.scss" || name == "input.sass")
+            })
+            .map(|input| {
+                // Options apply to the directory they're in and everything below it.
+                let options = input
+                    .ancestors()
+                    .skip(1)
+                    .filter_map(|dir| fs.read(&dir.join("options.yml")))
+                    .map(SpecOptions::parse)
+                    .fold(disk_options, SpecOptions::merge);
+                let code = fs.read(input).unwrap_or_default().to_string();
+                let virtual_path = Some(input.to_string_lossy().to_string());
+                TestSassSpecCase::new(path.to_path_buf(), code, virtual_path)
+                    .with_spec(Rc::clone(&fs), options)
+            })
+            .collect()
+    }
 
-            if is_case_file {
-                cases.push(T::new(path.clone(), code.clone(), virtual_path.clone()));
-            }
-        });
+    /// The options of the directories on disk that `path` is in
+    fn disk_options(&self, path: &Path) -> SpecOptions {
+        path.ancestors()
+            .skip(1)
+            .filter_map(|dir| fs::read_to_string(self.test_root.join(dir).join("options.yml")).ok())
+            .map(|yaml| SpecOptions::parse(&yaml))
+            .fold(SpecOptions::default(), SpecOptions::merge)
     }
 }
 
@@ -107,8 +293,8 @@ impl<T: Case> TestSassSpecSuite<T> {
     }
 }
 
-impl<T: Case> Suite<T> for TestSassSpecSuite<T> {
-    fn get_test_cases(&self) -> &Vec<T> {
+impl Suite<TestSassSpecCase> for TestSassSpecSuite<TestSassSpecCase> {
+    fn get_test_cases(&self) -> &Vec<TestSassSpecCase> {
         &self.test_cases
     }
 
@@ -121,20 +307,30 @@ impl<T: Case> Suite<T> for TestSassSpecSuite<T> {
             || path.extension().map_or(true, |ext| ext == "md" || ext == "yml")
     }
 
-    fn save_test_cases(&mut self, cases: Vec<T>) {
+    fn save_test_cases(&mut self, cases: Vec<TestSassSpecCase>) {
         self.test_cases = cases
     }
 
-    fn file_to_cases(&self, path: PathBuf, code: String) -> Vec<T> {
-        let is_hrx = path.extension().map_or(false, |ext| ext == "hrx");
-        let mut cases = vec![];
-
-        if is_hrx {
-            self.create_case_from_hrx(&mut cases, path, code)
-        } else {
-            cases.push(T::new(path, code, None));
+    /// The specs of an HRX archive, or of a spec directory on disk when `path` is its input
+    fn file_to_cases(&self, path: PathBuf, code: String) -> Vec<TestSassSpecCase> {
+        if path.extension().is_some_and(|ext| ext == "hrx") {
+            return self.cases_from_fs(&path, VirtualFs::from_hrx(&code));
         }
-
+        if !path.file_name().is_some_and(|name| name == "input.scss" || name == "input.sass") {
+            return vec![];
+        }
+        let dir = self.test_root.join(path.parent().unwrap_or_else(|| Path::new("")));
+        let mut fs = VirtualFs::default();
+        for entry in WalkDir::new(&dir).into_iter().filter_map(Result::ok) {
+            if let Ok(contents) = fs::read_to_string(entry.path()) {
+                fs.insert(entry.path().strip_prefix(&dir).unwrap().to_path_buf(), contents);
+            }
+        }
+        let mut cases = self.cases_from_fs(&path, fs);
+        // The specs in subdirectories are read from their own input.
+        cases.retain(|case| {
+            case.virtual_path().as_deref().is_some_and(|input| !input.contains('/'))
+        });
         cases
     }
 }
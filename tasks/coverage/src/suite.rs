@@ -8,6 +8,11 @@ use std::{
 use console::Style;
 use encoding_rs::UTF_16LE;
 use encoding_rs_io::DecodeReaderBytesBuilder;
+use miette::{GraphicalReportHandler, GraphicalTheme, NamedSource, Report};
+use scr_allocator::Allocator;
+use scr_ast::SourceType;
+use scr_diagnostics::Diagnostic;
+use scr_parser::Parser;
 use similar::{ChangeTag, TextDiff};
 use walkdir::WalkDir;
 
@@ -21,6 +26,8 @@ pub enum TestResult {
     PassError(String),
     Mismatch(String, String),
     CorrectError(String),
+    /// Parsed, but either the output or the expected output is missing, so nothing was compared
+    NotCompared,
 }
 
 pub trait Case: Sized + UnwindSafe {
@@ -38,10 +45,37 @@ pub trait Case: Sized + UnwindSafe {
 
     fn run(&mut self);
 
+    /// The syntax to parse the case with, from the extension of its file
+    fn source_type(&self) -> SourceType {
+        let path = self.virtual_path().as_deref().map_or_else(|| self.path(), Path::new);
+        SourceType::default().from_path(path)
+    }
+
     fn execute(&self) -> TestResult {
-        // let source_text = self.code();
-        // println!("source_text: {source_text:#?}");
-        self.parser_rusult_to_test_result(Err(String::new()))
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, self.code(), self.source_type()).parse();
+        let result = if ret.errors.is_empty() {
+            Ok(String::new())
+        } else {
+            Err(self.render_errors(self.code(), ret.errors))
+        };
+        self.parser_rusult_to_test_result(result)
+    }
+
+    /// Renders `errors` with the `code` they point at, without colors so snapshots stay readable
+    fn render_errors(&self, code: &str, errors: Vec<Diagnostic>) -> String {
+        let name = self.virtual_path().as_ref().map_or_else(
+            || self.path().to_path_buf(),
+            |virtual_path| self.path().join(virtual_path),
+        );
+        let handler = GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor());
+        let mut out = String::new();
+        for error in errors {
+            let source = NamedSource::new(name.to_string_lossy(), code.to_owned());
+            let report = Report::new(error).with_source_code(source);
+            handler.render_report(&mut out, report.as_ref()).unwrap();
+        }
+        out
     }
 
     fn parser_rusult_to_test_result(&self, result: Result<String, String>) -> TestResult {
@@ -64,7 +98,7 @@ pub trait Case: Sized + UnwindSafe {
     fn test_parsed(&self) -> bool {
         let result = self.test_result();
         assert!(!matches!(result, TestResult::ToBeRun), "test should be run");
-        matches!(result, TestResult::Passed | TestResult::Mismatch(_, _))
+        matches!(result, TestResult::Passed | TestResult::Mismatch(_, _) | TestResult::NotCompared)
     }
 
     fn print<W: Write>(&self, args: &CliArgs, writer: &mut W) -> io::Result<()> {
@@ -82,7 +116,11 @@ pub trait Case: Sized + UnwindSafe {
             }
             TestResult::Mismatch(ast_string, except_string) => {
                 if args.diff {
-                    writer.write_all(format!("Mismatch: {:?}\n", self.path()).as_bytes())?;
+                    writer.write_all(format!("Mismatch: {:?}", self.path()).as_bytes())?;
+                    if let Some(virtual_path) = self.virtual_path() {
+                        writer.write_all(format!(" -> {virtual_path:?}").as_bytes())?;
+                    }
+                    writer.write_all(b"\n")?;
                     self.print_diff(writer, &ast_string, &except_string)?;
                 }
             }
@@ -144,7 +182,8 @@ pub trait Suite<T: Case> {
 
         self.print_coverage(name, args, &report, &mut output).unwrap();
 
-        if args.filter.is_none() {
+        // Without the submodule there are no cases, and the snapshot would be emptied.
+        if args.filter.is_none() && !self.get_test_cases().is_empty() {
             self.snapshot_errors(name, &report).unwrap();
         }
     }
@@ -245,10 +284,10 @@ pub trait Suite<T: Case> {
     ) -> io::Result<()> {
         let CoverageReport {
             all_positives,
-            // all_negatives,
+            all_negatives,
             parsed_positives,
-            //  pass_positives,
-            //  pass_negatives,
+            passed_positives,
+            passed_negatives,
             ..
         } = report;
 
@@ -259,6 +298,28 @@ pub trait Suite<T: Case> {
             format!("AST Parsed: {parsed_positives}/{all_positives} ({parse_diff:.2}%)\n")
                 .as_bytes(),
         )?;
+        let passed_diff = (*passed_positives as f64) / (*all_positives as f64) * 100.0;
+        writer.write_all(
+            format!("Positive Passed: {passed_positives}/{all_positives} ({passed_diff:.2}%)\n")
+                .as_bytes(),
+        )?;
+        if *all_negatives > 0 {
+            let passed_diff = (*passed_negatives as f64) / (*all_negatives as f64) * 100.0;
+            writer.write_all(
+                format!(
+                    "Negative Passed: {passed_negatives}/{all_negatives} ({passed_diff:.2}%)\n"
+                )
+                .as_bytes(),
+            )?;
+        }
+        let not_compared = self
+            .get_test_cases()
+            .iter()
+            .filter(|case| matches!(case.test_result(), TestResult::NotCompared))
+            .count();
+        if not_compared > 0 {
+            writer.write_all(format!("Not Compared: {not_compared}\n").as_bytes())?;
+        }
 
         if args.should_print_detail() {
             for case in &report.failed_positives {